@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use currency::native::Nls;
+use finance::{coin::Coin, duration::Duration};
 use sdk::schemars::{self, JsonSchema};
+use swap::DexProtocol;
 
 /// Parameters needed to operate with the Dex network
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -10,6 +13,12 @@ pub struct ConnectionParams {
     /// The IBC ICS-20 channel used to transfer assets in/out.
     /// It must be established over the same connection.
     pub transfer_channel: Ics20Channel,
+    /// Timeout and relayer fees of the IBC packets sent to the DEX
+    #[serde(default)]
+    pub relaying: RelayingParams,
+    /// The protocol the swaps are performed with
+    #[serde(default)]
+    pub protocol: DexProtocol,
 }
 
 /// IBC ICS-20 channel parameters
@@ -20,3 +29,43 @@ pub struct Ics20Channel {
     /// The id of the remote endpoint
     pub remote_endpoint: String,
 }
+
+/// Parameters of relaying the IBC packets to and from the DEX network
+///
+/// The tips are paid out of the fee reserve of the lease, that is its Nls balance, at
+/// the time a packet is sent. The reserve is set aside with any Nls sent along the
+/// downpayment on opening, and is topped up with
+/// [`ExecuteMsg::TopUpFeeReserve`](crate::api::ExecuteMsg::TopUpFeeReserve).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RelayingParams {
+    /// The period after which a not yet delivered packet times out
+    pub timeout: Duration,
+    /// The tip for relaying the acknowledgement of an ICS-20 transfer
+    pub transfer_ack_tip: Coin<Nls>,
+    /// The tip for relaying the timeout of an ICS-20 transfer
+    pub transfer_timeout_tip: Coin<Nls>,
+    /// The tip for relaying the acknowledgement of an ICA transaction
+    pub ica_ack_tip: Coin<Nls>,
+    /// The tip for relaying the timeout of an ICA transaction
+    pub ica_timeout_tip: Coin<Nls>,
+    /// The least fee reserve a lease should hold to start a DEX interaction on request
+    ///
+    /// The interactions started by an alarm are not subject to it.
+    #[serde(default)]
+    pub fee_reserve: Coin<Nls>,
+}
+
+impl Default for RelayingParams {
+    fn default() -> Self {
+        const TIP: Coin<Nls> = Coin::new(1);
+        Self {
+            timeout: Duration::from_secs(60),
+            transfer_ack_tip: TIP,
+            transfer_timeout_tip: TIP,
+            ica_ack_tip: TIP,
+            ica_timeout_tip: TIP,
+            fee_reserve: Coin::default(),
+        }
+    }
+}
@@ -1,12 +1,19 @@
 use serde::{Deserialize, Serialize};
 
 use currency::{lease::LeaseGroup, lpn::Lpns, payment::PaymentGroup};
-use finance::coin::CoinDTO;
-use sdk::schemars::{self, JsonSchema};
+use finance::{coin::CoinDTO, price::dto::PriceDTO};
+use sdk::{
+    cosmwasm_std::Addr,
+    schemars::{self, JsonSchema},
+};
 
 pub use self::{
-    open::{InterestPaymentSpec, LoanForm, NewLeaseContract, NewLeaseForm},
-    query::{opened, opening, paid, StateQuery, StateResponse},
+    open::{default_max_slippage, InterestPaymentSpec, LoanForm, NewLeaseContract, NewLeaseForm},
+    query::{
+        opened, opening, paid, AnyQueryMsg, IcaError, LedgerEntry, LedgerRecord, LiquidationCause,
+        LiquidationQuoteResponse, QueryMsg, RepayQuoteResponse, RepayReceipt, StateQuery,
+        StateResponse,
+    },
 };
 
 pub mod dex;
@@ -18,6 +25,7 @@ pub type PaymentCoin = CoinDTO<PaymentGroup>;
 pub type DownpaymentCoin = PaymentCoin;
 pub type LeaseCoin = CoinDTO<LeaseGroup>;
 pub type LpnCoin = CoinDTO<Lpns>;
+pub type LeasePrice = PriceDTO<LeaseGroup, Lpns>;
 
 #[derive(Serialize, Deserialize)]
 pub struct MigrateMsg {}
@@ -25,10 +33,151 @@ pub struct MigrateMsg {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Repay(),
     // it is not an enum variant to represent it as a JSON object instead of JSON string
-    Close(),
     // that is a limitation of cosmjs library
+    Repay(),
     PriceAlarm(),
     TimeAlarm {},
+    /// Close a paid lease and deliver the lease asset to the customer
+    ///
+    /// By default, the asset is transferred back from the DEX and sent to the customer.
+    /// If `dex_recipient` is set, the asset is sent directly to that address at the DEX
+    /// network instead, skipping the transfer back. Should the send fail, the lease remains
+    /// paid and may be closed again. The legacy `{"close":[]}` form is still accepted.
+    ///
+    /// Only the customer, or an operator, may specify a DEX recipient.
+    Close {
+        #[serde(default)]
+        dex_recipient: Option<String>,
+    },
+    /// Top up the lease with the sent funds
+    ///
+    /// The payment is swapped into the lease asset and added to the lease amount
+    /// reducing the LTV without repaying the loan.
+    AddCollateral(),
+    /// Cancel the opening of the lease
+    ///
+    /// Any funds already transferred to the DEX are transferred back, the loan is repaid
+    /// in full and the rest of the funds are returned to the customer. The loan interest
+    /// is paid in LPN, so if the downpayment is not in LPN, the due interest should be
+    /// sent along with the message.
+    ///
+    /// Only the customer, or the administrator of the lease, may cancel it.
+    Cancel(),
+    /// Borrow an additional amount from the Liquidity Pool
+    ///
+    /// The margin interest due is paid out of the borrowed amount and the rest is swapped
+    /// into the lease asset and added to the lease amount. The loan interest due is added
+    /// to the principal. The resulting LTV should not exceed the initial one.
+    ///
+    /// Only the customer, or an operator, may borrow more.
+    BorrowMore {
+        amount: LpnCoin,
+    },
+    /// Sell a part, or all, of the lease asset and repay the loan with the proceeds
+    ///
+    /// If an amount is specified, it should be positive and not greater than the lease amount.
+    /// The lease remains open with the rest of the asset.
+    ///
+    /// If no amount is specified, the full position is closed in one step. Enough of
+    /// the asset is sold to cover the principal and all due interest even at the maximum
+    /// swap slippage, and the remaining asset is returned to the customer. Should the proceeds
+    /// still fall short, more of the asset is sold for the rest of the debt, unless that would
+    /// take the whole remaining asset without repaying the loan, in which case the lease stays
+    /// open. If `payout_lpn` is set, the whole asset is sold and the remaining amount is
+    /// returned in LPN. Selling the whole lease amount is rejected unless the proceeds
+    /// would repay the loan in full.
+    ///
+    /// Only the customer, or an operator, may close a position.
+    ClosePosition {
+        amount: Option<LeaseCoin>,
+        #[serde(default)]
+        payout_lpn: bool,
+    },
+    /// Set, or remove, the prices of the lease asset at which the position is fully closed
+    ///
+    /// The position is closed when the price of the asset in LPN falls to, or below,
+    /// the stop-loss price, or rises to, or above, the take-profit price. A price
+    /// that is not specified removes the respective order. The stop-loss price should
+    /// be below, and the take-profit price above, the current market price. A lease whose sale
+    /// would not repay the loan in full is not closed but left to the liquidation checks,
+    /// and the crossed price is removed.
+    ///
+    /// Only the customer, or an operator, may change the close policy.
+    ChangeClosePolicy {
+        stop_loss: Option<LeasePrice>,
+        take_profit: Option<LeasePrice>,
+    },
+    /// Allow an address to act on behalf of the customer
+    ///
+    /// An operator may close a position, borrow more, or change the close policy, of an open
+    /// lease, and may close a paid lease sending the asset to a DEX recipient. Anyone, an
+    /// operator included, may repay, add collateral or close a paid lease otherwise.
+    ///
+    /// Only the customer may grant an operator.
+    GrantOperator {
+        operator: Addr,
+    },
+    /// Disallow an operator to act on behalf of the customer
+    ///
+    /// Only the customer may revoke an operator.
+    RevokeOperator {
+        operator: Addr,
+    },
+    /// Transfer the ownership of the lease to another address
+    ///
+    /// The operators are revoked and the leaser is notified of the new customer.
+    ///
+    /// Only the customer may transfer the ownership of an open, or a paid, lease.
+    TransferOwnership {
+        new_customer: Addr,
+    },
+    /// Update the relaying parameters of the DEX connectivity
+    ///
+    /// A lease in the middle of a DEX interaction completes it with the parameters
+    /// it started with, and applies the new ones once it gets back to an active state.
+    ///
+    /// Only the administrator of the lease may update them.
+    UpdateDexRelaying(dex::RelayingParams),
+    /// Top up the fee reserve of the lease with the Nls sent along
+    ///
+    /// The reserve pays the relayer tips of the IBC packets sent to the DEX. Anyone may
+    /// top up the reserve of a lease that is not closed.
+    TopUpFeeReserve(),
+}
+
+/// The leaser execute messages a lease sends
+///
+/// They should be a subset of the leaser execute messages.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaserExecuteMsg {
+    ChangeLeaseCustomer { customer: Addr, new_customer: Addr },
+}
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::from_slice;
+
+    use super::ExecuteMsg;
+
+    #[test]
+    fn close_legacy() {
+        assert_eq!(
+            ExecuteMsg::Close {
+                dex_recipient: None
+            },
+            from_slice(br#"{"close":[]}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn close_dex_recipient() {
+        assert_eq!(
+            ExecuteMsg::Close {
+                dex_recipient: Some("osmo1recipient".into())
+            },
+            from_slice(br#"{"close":{"dex_recipient":"osmo1recipient"}}"#).unwrap()
+        );
+    }
 }
@@ -38,6 +38,22 @@ pub struct NewLeaseForm {
     pub time_alarms: Addr,
     /// The oracle contract that sends market price alerts to the lease
     pub market_price_oracle: Addr,
+    /// The maximum tolerated deviation of a swap output from its oracle price
+    ///
+    /// Swaps yielding less are rejected by the DEX and retried.
+    #[serde(default = "default_max_slippage")]
+    pub max_slippage: Percent,
+    /// The fee charged on the downpayment and on the payments that are not in LPN
+    ///
+    /// The fee is charged in LPN and goes to the Profit contract. The fee on the downpayment
+    /// is withheld from the loan and paid once the lease is opened.
+    #[serde(default)]
+    pub swap_fee: Percent,
+}
+
+/// The maximum swap slippage of the leases and configurations stored before it was introduced
+pub fn default_max_slippage() -> Percent {
+    Percent::from_permille(50)
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -65,6 +81,11 @@ pub struct InterestPaymentSpec {
     due_period: Duration,
     /// How long after the due period ends the interest may be paid before initiating a liquidation
     grace_period: Duration,
+    /// The annual interest rate charged on the overdue interest during the grace period
+    ///
+    /// If not specified, no late payment penalty is charged.
+    #[serde(default)]
+    late_payment_interest: Option<Percent>,
 }
 
 impl InterestPaymentSpec {
@@ -73,11 +94,20 @@ impl InterestPaymentSpec {
         let res = Self {
             due_period,
             grace_period,
+            late_payment_interest: None,
         };
         debug_assert_eq!(Ok(()), res.invariant_held());
         res
     }
 
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_late_payment_interest(self, late_payment_interest: Percent) -> Self {
+        Self {
+            late_payment_interest: Some(late_payment_interest),
+            ..self
+        }
+    }
+
     pub fn grace_period(&self) -> Duration {
         self.grace_period
     }
@@ -86,6 +116,10 @@ impl InterestPaymentSpec {
         self.due_period
     }
 
+    pub fn late_payment_interest(&self) -> Option<Percent> {
+        self.late_payment_interest
+    }
+
     fn invariant_held(&self) -> ContractResult<()> {
         ContractError::broken_invariant_if::<InterestPaymentSpec>(
             self.due_period == Duration::default(),
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use finance::duration::Duration;
+use finance::{duration::Duration, percent::Percent};
 
 use crate::error::ContractError;
 
@@ -11,6 +11,8 @@ use super::InterestPaymentSpec as ValidatedInterestPaymentSpec;
 pub(super) struct InterestPaymentSpec {
     due_period: Duration,
     grace_period: Duration,
+    #[serde(default)]
+    late_payment_interest: Option<Percent>,
 }
 
 impl TryFrom<InterestPaymentSpec> for ValidatedInterestPaymentSpec {
@@ -20,6 +22,7 @@ impl TryFrom<InterestPaymentSpec> for ValidatedInterestPaymentSpec {
         let res = Self {
             due_period: dto.due_period,
             grace_period: dto.grace_period,
+            late_payment_interest: dto.late_payment_interest,
         };
         res.invariant_held()?;
         Ok(res)
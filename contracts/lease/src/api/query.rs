@@ -6,12 +6,162 @@ use sdk::{
     schemars::{self, JsonSchema},
 };
 
-use super::{DownpaymentCoin, LeaseCoin, LpnCoin};
+use super::{DownpaymentCoin, LeaseCoin, LeasePrice, LpnCoin};
 
+/// The queries supported by a lease
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// The current state of the lease
+    ///
+    /// The response is a `StateResponse`.
+    State {},
+    /// Simulate a repayment without executing it
+    ///
+    /// Only an opened lease with no transaction in progress may be quoted.
+    /// The payment is in LPN so no swap fee is charged on it.
+    RepayQuote { payment: LpnCoin },
+    /// Simulate the liquidation checks at a hypothetical price of the lease asset and time
+    ///
+    /// The time should not be in the past. Only an opened lease with no transaction
+    /// in progress may be quoted.
+    LiquidationQuote { price: LeasePrice, at: Timestamp },
+    /// Page through the ledger entries in ascending order of their ids
+    ///
+    /// The response is a list of `LedgerEntry`.
+    Ledger {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+}
+
+/// A query as received by a lease
+///
+/// Besides a [`QueryMsg`], the empty JSON object, `{}`, is accepted as a state query
+/// to stay backward compatible. Any other object is rejected.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum AnyQueryMsg {
+    Query(QueryMsg),
+    State(StateQuery),
+}
+
+impl From<AnyQueryMsg> for QueryMsg {
+    fn from(value: AnyQueryMsg) -> Self {
+        match value {
+            AnyQueryMsg::Query(msg) => msg,
+            AnyQueryMsg::State(StateQuery {}) => Self::State {},
+        }
+    }
+}
+
+/// The legacy state query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct StateQuery {}
 
+/// How a payment would be distributed if repaid now
+pub type RepayQuoteResponse = RepayReceipt;
+
+/// How a payment is distributed over the loan obligations
+#[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(rename_all = "snake_case")]
+pub struct RepayReceipt {
+    pub late_payment_penalty: LpnCoin,
+    pub previous_margin_interest: LpnCoin,
+    pub previous_loan_interest: LpnCoin,
+    pub current_margin_interest: LpnCoin,
+    pub current_loan_interest: LpnCoin,
+    pub principal: LpnCoin,
+    pub change: LpnCoin,
+    /// Whether the loan would be fully repaid and the lease become paid
+    pub paid: bool,
+}
+
+/// The outcome of the liquidation checks at a hypothetical price and time
+///
+/// The `ltv` is the one reported by the respective liquidation events.
+#[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(rename_all = "snake_case")]
+pub enum LiquidationQuoteResponse {
+    None(),
+    Warning {
+        level: u8,
+        ltv: Percent,
+    },
+    PartialLiquidation {
+        cause: LiquidationCause,
+        ltv: Percent,
+        /// The amount that would be repaid from the liquidated asset
+        amount: LpnCoin,
+        penalty: LpnCoin,
+        healthy_ltv: Percent,
+    },
+    FullLiquidation {
+        cause: LiquidationCause,
+        ltv: Percent,
+        /// The amount that would be repaid from the liquidated asset
+        amount: LpnCoin,
+        penalty: LpnCoin,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(rename_all = "snake_case")]
+pub enum LiquidationCause {
+    Overdue,
+    Liability,
+}
+
+/// An entry of the append-only ledger of a lease
+#[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(rename_all = "snake_case")]
+pub struct LedgerEntry {
+    /// The sequence number of the entry starting from zero
+    pub id: u32,
+    pub at: Timestamp,
+    pub record: LedgerRecord,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerRecord {
+    Open {
+        amount: LeaseCoin,
+        downpayment: DownpaymentCoin,
+        /// The swap fee on the downpayment, charged in LPN
+        swap_fee: LpnCoin,
+        loan: LpnCoin,
+    },
+    Repay {
+        /// The payment net of the swap fee
+        payment: LpnCoin,
+        receipt: RepayReceipt,
+    },
+    PositionClose {
+        amount: LeaseCoin,
+        /// The amount obtained from selling the closed position
+        payment: LpnCoin,
+        receipt: RepayReceipt,
+    },
+    Liquidation {
+        cause: LiquidationCause,
+        amount: LeaseCoin,
+        /// The price the amount has been valued at
+        price: LeasePrice,
+        receipt: RepayReceipt,
+        penalty: LpnCoin,
+    },
+    Close {
+        amount: LeaseCoin,
+    },
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
 #[serde(rename_all = "snake_case")]
@@ -21,6 +171,7 @@ pub enum StateResponse {
         loan: LpnCoin,
         loan_interest_rate: Percent,
         in_progress: opening::OngoingTrx,
+        last_error: Option<IcaError>,
     },
     Opened {
         amount: LeaseCoin,
@@ -29,18 +180,38 @@ pub enum StateResponse {
         principal_due: LpnCoin,
         previous_margin_due: LpnCoin,
         previous_interest_due: LpnCoin,
+        late_payment_penalty_due: LpnCoin,
         current_margin_due: LpnCoin,
         current_interest_due: LpnCoin,
+        ltv: Percent,
+        liquidation_prices: opened::LiquidationPrices,
         validity: Timestamp,
         in_progress: Option<opened::OngoingTrx>,
+        last_error: Option<IcaError>,
     },
     Paid {
         amount: LeaseCoin,
         in_progress: Option<paid::ClosingTrx>,
+        last_error: Option<IcaError>,
     },
     Closed(),
 }
 
+/// The last error acknowledgement of a transaction on the DEX
+///
+/// It is cleared as soon as a following transaction succeeds.
+#[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(rename_all = "snake_case")]
+pub struct IcaError {
+    /// The details of the error as reported by the DEX
+    pub details: String,
+    /// The time the error was delivered at
+    pub at: Timestamp,
+    /// The number of consecutive errors
+    pub count: u16,
+}
+
 pub mod opening {
     use sdk::schemars::{self, JsonSchema};
     use serde::{Deserialize, Serialize};
@@ -52,6 +223,7 @@ pub mod opening {
         OpenIcaAccount,
         TransferOut { ica_account: String },
         BuyAsset { ica_account: String },
+        Cancel { ica_account: String },
     }
 }
 
@@ -59,20 +231,40 @@ pub mod opened {
     use sdk::schemars::{self, JsonSchema};
     use serde::{Deserialize, Serialize};
 
-    use crate::api::{LpnCoin, PaymentCoin};
+    use crate::api::{LeaseCoin, LeasePrice, LpnCoin, PaymentCoin};
+
+    /// The prices of the lease asset at which the liquidation warnings are issued,
+    /// and the lease is liquidated, provided the liability does not change
+    #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+    #[serde(rename_all = "snake_case")]
+    pub struct LiquidationPrices {
+        pub first_warning: LeasePrice,
+        pub second_warning: LeasePrice,
+        pub third_warning: LeasePrice,
+        pub liquidation: LeasePrice,
+    }
 
     #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
     #[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
     #[serde(rename_all = "snake_case")]
     pub enum OngoingTrx {
         Repayment {
-            payment: PaymentCoin,
+            payments: Vec<PaymentCoin>,
             in_progress: RepayTrx,
         },
         Liquidation {
             amount_out: LpnCoin,
             in_progress: LiquidateTrx,
         },
+        PositionClose {
+            close: LeaseCoin,
+            in_progress: PositionCloseTrx,
+        },
+        CollateralAddition {
+            payment: PaymentCoin,
+            in_progress: CollateralTrx,
+        },
     }
 
     #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -92,6 +284,23 @@ pub mod opened {
         Swap,
         TransferIn,
     }
+
+    #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+    #[serde(rename_all = "snake_case")]
+    pub enum PositionCloseTrx {
+        Swap,
+        TransferInInit,
+        TransferInFinish,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+    #[serde(rename_all = "snake_case")]
+    pub enum CollateralTrx {
+        TransferOut,
+        Swap,
+    }
 }
 
 pub mod paid {
@@ -104,5 +313,63 @@ pub mod paid {
     pub enum ClosingTrx {
         TransferInInit,
         TransferInFinish,
+        DexSend,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currency::{lease::Atom, lpn::Usdc};
+    use finance::{coin::Coin, price};
+    use sdk::cosmwasm_std::{from_slice, to_vec, Timestamp};
+
+    use super::{AnyQueryMsg, QueryMsg, StateQuery};
+
+    fn parse(msg: &[u8]) -> QueryMsg {
+        from_slice::<AnyQueryMsg>(msg).unwrap().into()
+    }
+
+    #[test]
+    fn state_query() {
+        assert_eq!(QueryMsg::State {}, parse(br#"{"state": {}}"#));
+        assert_eq!(QueryMsg::State {}, parse(br#"{}"#));
+        assert_eq!(QueryMsg::State {}, parse(&to_vec(&StateQuery {}).unwrap()));
+    }
+
+    #[test]
+    fn unknown_query() {
+        assert!(from_slice::<AnyQueryMsg>(br#"{"unknown": {}}"#).is_err());
+        assert!(from_slice::<AnyQueryMsg>(br#"{"ledger": {"start_after": "4"}}"#).is_err());
+        assert!(from_slice::<AnyQueryMsg>(br#"{"state": {}, "ledger": {}}"#).is_err());
+    }
+
+    #[test]
+    fn repay_quote_query() {
+        let msg = QueryMsg::RepayQuote {
+            payment: Coin::<Usdc>::new(100).into(),
+        };
+        assert_eq!(msg, parse(&to_vec(&msg).unwrap()));
+    }
+
+    #[test]
+    fn liquidation_quote_query() {
+        let msg = QueryMsg::LiquidationQuote {
+            price: price::total_of(Coin::<Atom>::new(2))
+                .is(Coin::<Usdc>::new(15))
+                .into(),
+            at: Timestamp::from_seconds(100),
+        };
+        assert_eq!(msg, parse(&to_vec(&msg).unwrap()));
+    }
+
+    #[test]
+    fn ledger_query() {
+        assert_eq!(
+            QueryMsg::Ledger {
+                start_after: Some(4),
+                limit: None
+            },
+            parse(br#"{"ledger": {"start_after": 4}}"#)
+        );
     }
 }
@@ -0,0 +1,48 @@
+use serde::Serialize;
+
+use finance::currency::Currency;
+use lpp::stub::lender::LppLender as LppLenderTrait;
+use oracle::stub::Oracle as OracleTrait;
+use profit::stub::Profit as ProfitTrait;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
+
+use crate::{
+    api::LeaseCoin,
+    error::ContractError,
+    lease::{with_lease::WithLease, IntoDTOResult, Lease},
+};
+
+pub struct AddCollateral {
+    amount: LeaseCoin,
+    now: Timestamp,
+}
+
+impl AddCollateral {
+    pub fn new(amount: LeaseCoin, now: Timestamp) -> Self {
+        Self { amount, now }
+    }
+}
+
+impl WithLease for AddCollateral {
+    type Output = IntoDTOResult;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        mut lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        lease.add_collateral(self.amount.try_into()?, &self.now)?;
+
+        Ok(lease.into_dto())
+    }
+}
@@ -1,8 +1,9 @@
-use finance::currency::Currency;
+use finance::{coin::Coin, currency::Currency, price::Price};
 use platform::batch::{Batch, Emit, Emitter};
 use sdk::{cosmwasm_ext::Response, cosmwasm_std::Env};
 
 use crate::{
+    api::LedgerRecord,
     event::Type,
     lease::{LeaseDTO, LeaseInfo, LiquidationInfo, Status, WarningLevel},
 };
@@ -13,6 +14,34 @@ pub mod time;
 pub struct AlarmResult {
     pub response: Response,
     pub lease_dto: LeaseDTO,
+    /// The ledger record of the liquidation, if any
+    pub record: Option<LedgerRecord>,
+}
+
+fn ledger_record<Lpn, Asset>(
+    liquidation: Status<Lpn, Asset>,
+    liquidated: Coin<Asset>,
+    price: Price<Asset, Lpn>,
+) -> Option<LedgerRecord>
+where
+    Lpn: Currency,
+    Asset: Currency,
+{
+    match liquidation {
+        Status::None | Status::Warning(..) => None,
+        Status::PartialLiquidation {
+            liquidation_info, ..
+        }
+        | Status::FullLiquidation {
+            liquidation_info, ..
+        } => Some(LedgerRecord::Liquidation {
+            cause: liquidation_info.cause.into(),
+            amount: liquidated.into(),
+            price: price.into(),
+            receipt: liquidation_info.receipt.into(),
+            penalty: liquidation_info.penalty.into(),
+        }),
+    }
 }
 
 fn emit_events<Lpn, Asset>(env: &Env, liquidation: &Status<Lpn, Asset>, batch: Batch) -> Response
@@ -65,12 +94,17 @@ where
         .emit("of", info.lease.as_str())
         .emit_coin("liquidation", info.receipt.total())
         .emit_to_string_value("type", info.cause.to_uint())
+        .emit_coin_amount(
+            "late-payment-penalty",
+            info.receipt.late_payment_penalty_paid(),
+        )
         .emit_coin_amount("prev-margin-interest", info.receipt.previous_margin_paid())
         .emit_coin_amount("prev-loan-interest", info.receipt.previous_interest_paid())
         .emit_coin_amount("curr-margin-interest", info.receipt.current_margin_paid())
         .emit_coin_amount("curr-loan-interest", info.receipt.current_interest_paid())
         .emit_coin_amount("principal", info.receipt.principal_paid())
         .emit_coin_amount("excess", info.receipt.change())
+        .emit_coin_amount("penalty", info.penalty)
 }
 
 fn emit_liquidation<Lpn, Asset>(
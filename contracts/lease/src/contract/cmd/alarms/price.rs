@@ -9,7 +9,7 @@ use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
 
 use crate::{
     error::ContractError,
-    lease::{with_lease::WithLease, Lease, OnAlarmResult},
+    lease::{with_lease::WithLease, ClosePolicyTrigger, Lease, OnAlarmResult},
 };
 
 use super::AlarmResult;
@@ -18,11 +18,25 @@ pub struct PriceAlarm<'a> {
     env: &'a Env,
     sender: &'a Addr,
     now: Timestamp,
+    cancelled: Option<ClosePolicyTrigger>,
 }
 
 impl<'a> PriceAlarm<'a> {
     pub fn new(env: &'a Env, sender: &'a Addr, now: Timestamp) -> Self {
-        Self { env, sender, now }
+        Self {
+            env,
+            sender,
+            now,
+            cancelled: None,
+        }
+    }
+
+    /// Cancel a close policy trigger the position cannot be closed on before the checks
+    pub fn cancel_trigger(self, trigger: ClosePolicyTrigger) -> Self {
+        Self {
+            cancelled: Some(trigger),
+            ..self
+        }
     }
 }
 
@@ -33,7 +47,7 @@ impl<'a> WithLease for PriceAlarm<'a> {
 
     fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
         self,
-        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+        mut lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
     ) -> Result<Self::Output, Self::Error>
     where
         Lpn: Currency + Serialize,
@@ -47,15 +61,22 @@ impl<'a> WithLease for PriceAlarm<'a> {
             return Err(Self::Error::Unauthorized {});
         }
 
+        if let Some(trigger) = self.cancelled {
+            lease.cancel_close_policy_trigger(trigger);
+        }
+
         let OnAlarmResult {
             batch,
             lease_dto,
             liquidation_status,
+            liquidated,
+            price,
         } = lease.on_price_alarm(self.now)?;
 
         Ok(AlarmResult {
             response: super::emit_events(self.env, &liquidation_status, batch),
             lease_dto,
+            record: super::ledger_record(liquidation_status, liquidated, price),
         })
     }
 }
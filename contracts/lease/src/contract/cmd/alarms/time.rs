@@ -51,11 +51,14 @@ impl<'a> WithLease for TimeAlarm<'a> {
             batch,
             lease_dto,
             liquidation_status,
+            liquidated,
+            price,
         } = lease.on_time_alarm(self.now)?;
 
         Ok(AlarmResult {
             response: super::emit_events(self.env, &liquidation_status, batch),
             lease_dto,
+            record: super::ledger_record(liquidation_status, liquidated, price),
         })
     }
 }
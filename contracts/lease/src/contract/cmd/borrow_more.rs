@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+use finance::currency::Currency;
+use lpp::stub::lender::LppLender as LppLenderTrait;
+use oracle::stub::Oracle as OracleTrait;
+use platform::batch::Batch;
+use profit::stub::Profit as ProfitTrait;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
+
+use crate::{
+    api::LpnCoin,
+    error::ContractError,
+    lease::{with_lease::WithLease, IntoDTOResult, Lease, LeaseDTO},
+};
+
+pub struct BorrowMore {
+    amount: LpnCoin,
+    now: Timestamp,
+}
+
+impl BorrowMore {
+    pub fn new(amount: LpnCoin, now: Timestamp) -> Self {
+        Self { amount, now }
+    }
+}
+
+pub struct BorrowMoreResult {
+    pub lease: LeaseDTO,
+    pub batch: Batch,
+    pub collateral: LpnCoin,
+}
+
+impl WithLease for BorrowMore {
+    type Output = BorrowMoreResult;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        mut lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        let collateral = lease.borrow_more(self.amount.try_into()?, &self.now)?;
+
+        let IntoDTOResult { lease, batch } = lease.into_dto();
+        Ok(BorrowMoreResult {
+            lease,
+            batch,
+            collateral: collateral.into(),
+        })
+    }
+}
@@ -0,0 +1,183 @@
+use serde::Serialize;
+
+use finance::currency::Currency;
+use lpp::stub::lender::{LppLender as LppLenderTrait, WithLppLender};
+use platform::{
+    bank::{self, BankAccount, BankAccountView},
+    batch::Batch,
+};
+use sdk::cosmwasm_std::{Addr, QuerierWrapper, Timestamp};
+
+use crate::{api::DownpaymentCoin, error::ContractError};
+
+/// Repay the loan of a lease being opened, and send the rest of the funds back to the customer
+pub struct CancelOpen<'a> {
+    lease: &'a Addr,
+    customer: &'a Addr,
+    downpayment: &'a DownpaymentCoin,
+    now: Timestamp,
+    querier: &'a QuerierWrapper<'a>,
+}
+
+impl<'a> CancelOpen<'a> {
+    pub fn new(
+        lease: &'a Addr,
+        customer: &'a Addr,
+        downpayment: &'a DownpaymentCoin,
+        now: Timestamp,
+        querier: &'a QuerierWrapper<'a>,
+    ) -> Self {
+        Self {
+            lease,
+            customer,
+            downpayment,
+            now,
+            querier,
+        }
+    }
+}
+
+impl<'a> WithLppLender for CancelOpen<'a> {
+    type Output = Batch;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, LppLender>(self, mut lpp: LppLender) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        LppLender: LppLenderTrait<Lpn>,
+    {
+        let loan = lpp
+            .loan(self.lease.clone())?
+            .ok_or(ContractError::LoanClosed())?;
+        let loan_due = loan.principal_due + loan.interest_due(self.now);
+
+        let mut lease_account = bank::account(self.lease, self.querier);
+        let balance_lpn = lease_account.balance::<Lpn>()?;
+        if balance_lpn < loan_due {
+            return Err(ContractError::InsufficientPayment(
+                (loan_due - balance_lpn).into(),
+            ));
+        }
+
+        lpp.repay_loan_req(loan_due)?;
+
+        // the downpayment in LPN, if so, is included in the surplus
+        let surplus_lpn = balance_lpn - loan_due;
+        if !surplus_lpn.is_zero() {
+            lease_account.send(surplus_lpn, self.customer);
+        }
+
+        let batch = lpp.into().batch.merge(lease_account.into());
+        if self.downpayment.ticker() == Lpn::TICKER {
+            Ok(batch)
+        } else {
+            super::send(self.downpayment, self.lease, self.customer, self.querier)
+                .map(|refund| batch.merge(refund))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currency::{lease::Atom, lpn::Usdc};
+    use finance::{coin::Coin, currency::Currency, duration::Duration, percent::Percent};
+    use lpp::{
+        error::ContractError as LppError,
+        msg::{LoanResponse, QueryLoanResponse, QueryQuoteResponse},
+        stub::{
+            lender::{LppLender, LppLenderRef, WithLppLender},
+            LppBatch,
+        },
+    };
+    use platform::batch::Batch;
+    use sdk::cosmwasm_std::{
+        coin as cw_coin, testing::MockQuerier, Addr, QuerierWrapper, Reply, Timestamp,
+    };
+
+    use crate::{api::DownpaymentCoin, error::ContractError};
+
+    use super::CancelOpen;
+
+    const LEASE: &str = "lease";
+    const CUSTOMER: &str = "customer";
+    const PRINCIPAL: u128 = 1000;
+
+    struct LppLenderStub {
+        opened_at: Timestamp,
+    }
+
+    impl LppLender<Usdc> for LppLenderStub {
+        fn open_loan_req(&mut self, _amount: Coin<Usdc>) -> Result<(), LppError> {
+            unreachable!()
+        }
+
+        fn open_loan_resp(&self, _resp: Reply) -> Result<LoanResponse<Usdc>, LppError> {
+            unreachable!()
+        }
+
+        fn repay_loan_req(&mut self, _repayment: Coin<Usdc>) -> Result<(), LppError> {
+            Ok(())
+        }
+
+        fn loan(&self, _lease: impl Into<Addr>) -> Result<QueryLoanResponse<Usdc>, LppError> {
+            Ok(Some(LoanResponse {
+                principal_due: PRINCIPAL.into(),
+                annual_interest_rate: Percent::from_percent(10),
+                interest_paid: self.opened_at,
+            }))
+        }
+
+        fn quote(&self, _amount: Coin<Usdc>) -> Result<QueryQuoteResponse, LppError> {
+            unreachable!()
+        }
+    }
+
+    impl From<LppLenderStub> for LppBatch<LppLenderRef> {
+        fn from(_: LppLenderStub) -> Self {
+            Self {
+                lpp_ref: LppLenderRef::unchecked::<_, Usdc>("lpp", 0),
+                batch: Batch::default(),
+            }
+        }
+    }
+
+    fn cancel(balance_lpn: u128) -> Result<Batch, ContractError> {
+        let opened_at = Timestamp::from_seconds(100);
+        let lease = Addr::unchecked(LEASE);
+        let customer = Addr::unchecked(CUSTOMER);
+        let downpayment: DownpaymentCoin = Coin::<Atom>::new(300).into();
+        let querier = MockQuerier::new(&[(
+            LEASE,
+            &[
+                cw_coin(balance_lpn, Usdc::BANK_SYMBOL),
+                cw_coin(300, Atom::BANK_SYMBOL),
+            ],
+        )]);
+        let querier = QuerierWrapper::new(&querier);
+
+        CancelOpen::new(
+            &lease,
+            &customer,
+            &downpayment,
+            opened_at + Duration::YEAR,
+            &querier,
+        )
+        .exec(LppLenderStub { opened_at })
+    }
+
+    #[test]
+    fn interest_not_covered() {
+        // only the loan principal is back in LPN, the downpayment is not
+        assert!(matches!(
+            cancel(PRINCIPAL),
+            Err(ContractError::InsufficientPayment(_))
+        ));
+    }
+
+    #[test]
+    fn interest_paid_along() {
+        // the LPN paid along with the cancel covers the interest due over a year
+        assert!(!cancel(PRINCIPAL + 200).unwrap().is_empty());
+    }
+}
@@ -14,11 +14,23 @@ use crate::{
 
 pub struct Close<Bank> {
     lease_account: Bank,
+    amount_delivered: bool,
 }
 
 impl<Bank> Close<Bank> {
     pub fn new(lease_account: Bank) -> Self {
-        Self { lease_account }
+        Self {
+            lease_account,
+            amount_delivered: false,
+        }
+    }
+
+    /// Close a lease whose amount has already been delivered to the customer
+    pub fn delivered(lease_account: Bank) -> Self {
+        Self {
+            lease_account,
+            amount_delivered: true,
+        }
     }
 }
 
@@ -42,6 +54,10 @@ where
         Profit: ProfitTrait,
         Asset: Currency + Serialize,
     {
-        lease.close(self.lease_account)
+        if self.amount_delivered {
+            lease.close_delivered(self.lease_account)
+        } else {
+            lease.close(self.lease_account)
+        }
     }
 }
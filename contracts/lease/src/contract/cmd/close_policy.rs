@@ -0,0 +1,82 @@
+use serde::Serialize;
+
+use finance::currency::Currency;
+use lpp::stub::lender::LppLender as LppLenderTrait;
+use oracle::stub::Oracle as OracleTrait;
+use profit::stub::Profit as ProfitTrait;
+use sdk::cosmwasm_std::{Addr, Timestamp};
+use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
+
+use crate::{
+    error::ContractError,
+    lease::{with_lease::WithLease, ClosePolicy, ClosePolicyTrigger, IntoDTOResult, Lease},
+};
+
+pub struct ChangeClosePolicy {
+    policy: ClosePolicy,
+    now: Timestamp,
+}
+
+impl ChangeClosePolicy {
+    pub fn new(policy: ClosePolicy, now: Timestamp) -> Self {
+        Self { policy, now }
+    }
+}
+
+impl WithLease for ChangeClosePolicy {
+    type Output = IntoDTOResult;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        mut lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        lease.change_close_policy(self.policy, &self.now)?;
+
+        Ok(lease.into_dto())
+    }
+}
+
+pub struct ClosePolicyCheck<'a> {
+    sender: &'a Addr,
+}
+
+impl<'a> ClosePolicyCheck<'a> {
+    pub fn new(sender: &'a Addr) -> Self {
+        Self { sender }
+    }
+}
+
+impl<'a> WithLease for ClosePolicyCheck<'a> {
+    type Output = Option<ClosePolicyTrigger>;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        if !lease.sent_by_oracle(self.sender) {
+            return Err(Self::Error::Unauthorized {});
+        }
+
+        lease.close_policy_trigger()
+    }
+}
@@ -0,0 +1,158 @@
+use serde::Serialize;
+
+use finance::{currency::Currency, percent::Percent};
+use lpp::stub::lender::LppLender as LppLenderTrait;
+use oracle::stub::Oracle as OracleTrait;
+use platform::{
+    bank::BankAccount,
+    batch::{Emit, Emitter},
+};
+use profit::stub::Profit as ProfitTrait;
+use sdk::cosmwasm_std::{Env, Timestamp};
+use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
+
+use crate::{
+    api::{LeaseCoin, LedgerRecord, LpnCoin},
+    error::ContractError,
+    event::Type,
+    lease::{with_lease::WithLease, Lease, RepayResult as LeaseRepayResult},
+};
+
+use super::{repay, RepayResult};
+
+pub struct ClosePosition<'a, Bank> {
+    amount: LeaseCoin,
+    payment: LpnCoin,
+    env: &'a Env,
+    lease_account: Bank,
+}
+
+impl<'a, Bank> ClosePosition<'a, Bank> {
+    pub fn new(amount: LeaseCoin, payment: LpnCoin, env: &'a Env, lease_account: Bank) -> Self {
+        Self {
+            amount,
+            payment,
+            env,
+            lease_account,
+        }
+    }
+}
+
+impl<'a, Bank> WithLease for ClosePosition<'a, Bank>
+where
+    Bank: BankAccount,
+{
+    type Output = RepayResult;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        let amount = (&self.amount).try_into()?;
+        let payment = (&self.payment).try_into()?;
+
+        let LeaseRepayResult {
+            batch,
+            lease,
+            receipt,
+        } = lease.close_position(amount, payment, self.env.block.time, self.lease_account)?;
+
+        let emitter = repay::emit_payment_info(
+            Emitter::of_type(Type::PositionClose)
+                .emit_tx_info(self.env)
+                .emit("to", lease.addr.clone())
+                .emit_currency::<_, Asset>("amount-symbol")
+                .emit_coin_amount("amount-amount", amount),
+            payment,
+            &receipt,
+        );
+
+        Ok(RepayResult {
+            lease,
+            paid: receipt.close(),
+            batch,
+            emitter,
+            record: LedgerRecord::PositionClose {
+                amount: self.amount,
+                payment: self.payment,
+                receipt: receipt.into(),
+            },
+        })
+    }
+}
+
+pub struct FullCloseAmount {
+    max_slippage: Percent,
+    now: Timestamp,
+}
+
+impl FullCloseAmount {
+    pub fn new(max_slippage: Percent, now: Timestamp) -> Self {
+        Self { max_slippage, now }
+    }
+}
+
+impl WithLease for FullCloseAmount {
+    type Output = LeaseCoin;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        lease
+            .full_close_amount(self.max_slippage, self.now)
+            .map(Into::into)
+    }
+}
+
+pub struct FullSaleRepays {
+    max_slippage: Percent,
+    now: Timestamp,
+}
+
+impl FullSaleRepays {
+    pub fn new(max_slippage: Percent, now: Timestamp) -> Self {
+        Self { max_slippage, now }
+    }
+}
+
+impl WithLease for FullSaleRepays {
+    type Output = bool;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        lease.full_sale_repays(self.max_slippage, self.now)
+    }
+}
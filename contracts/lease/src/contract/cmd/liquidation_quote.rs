@@ -0,0 +1,99 @@
+use serde::Serialize;
+
+use finance::{currency::Currency, price::Price};
+use lpp::stub::lender::LppLender as LppLenderTrait;
+use oracle::stub::Oracle as OracleTrait;
+use profit::stub::Profit as ProfitTrait;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
+
+use crate::{
+    api::{LeasePrice, LiquidationQuoteResponse},
+    error::ContractError,
+    lease::{with_lease::WithLease, Lease, LiquidationInfo, Status},
+};
+
+/// Simulate the liquidation checks at a hypothetical price and time
+///
+/// The updated lease and the produced messages are discarded.
+pub struct LiquidationQuote {
+    price: LeasePrice,
+    at: Timestamp,
+}
+
+impl LiquidationQuote {
+    pub fn new(price: LeasePrice, at: Timestamp) -> Self {
+        Self { price, at }
+    }
+}
+
+impl WithLease for LiquidationQuote {
+    type Output = LiquidationQuoteResponse;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        let price = Price::<Asset, Lpn>::try_from(&self.price)?;
+
+        lease.liquidation_status_at(price, self.at).map(Into::into)
+    }
+}
+
+impl<Lpn, Asset> From<Status<Lpn, Asset>> for LiquidationQuoteResponse
+where
+    Lpn: Currency,
+    Asset: Currency,
+{
+    fn from(status: Status<Lpn, Asset>) -> Self {
+        match status {
+            Status::None => Self::None(),
+            Status::Warning(info, level) => Self::Warning {
+                level: level.to_uint(),
+                ltv: info.ltv,
+            },
+            Status::PartialLiquidation {
+                info,
+                liquidation_info:
+                    LiquidationInfo {
+                        cause,
+                        receipt,
+                        penalty,
+                        ..
+                    },
+                healthy_ltv,
+            } => Self::PartialLiquidation {
+                cause: cause.into(),
+                ltv: info.ltv,
+                amount: receipt.total().into(),
+                penalty: penalty.into(),
+                healthy_ltv,
+            },
+            Status::FullLiquidation {
+                info,
+                liquidation_info:
+                    LiquidationInfo {
+                        cause,
+                        receipt,
+                        penalty,
+                        ..
+                    },
+            } => Self::FullLiquidation {
+                cause: cause.into(),
+                ltv: info.ltv,
+                amount: receipt.total().into(),
+                penalty: penalty.into(),
+            },
+        }
+    }
+}
@@ -1,11 +1,27 @@
+pub use add_collateral::AddCollateral;
 pub use alarms::{price::PriceAlarm, time::TimeAlarm, AlarmResult};
+pub use borrow_more::{BorrowMore, BorrowMoreResult};
+pub use cancel_open::CancelOpen;
 pub use close::Close;
+pub use close_policy::{ChangeClosePolicy, ClosePolicyCheck};
+pub use close_position::{ClosePosition, FullCloseAmount, FullSaleRepays};
+pub use liquidation_quote::LiquidationQuote;
 pub use open::{OpenLoanReq, OpenLoanReqResult, OpenLoanResp, OpenLoanRespResult};
-pub use repay::{Repay, RepayResult};
+pub use repay::{FullRepayAmount, Repay, RepayResult};
+pub use repay_quote::RepayQuote;
+pub use send::send;
 pub use state::LeaseState;
 
+mod add_collateral;
 mod alarms;
+mod borrow_more;
+mod cancel_open;
 mod close;
+mod close_policy;
+mod close_position;
+mod liquidation_quote;
 mod open;
 mod repay;
+mod repay_quote;
+mod send;
 mod state;
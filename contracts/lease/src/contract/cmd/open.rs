@@ -23,6 +23,7 @@ pub struct OpenLoanReq<'a> {
     liability: &'a Liability,
     funds_in: Vec<CwCoin>,
     max_ltv: Option<Percent>,
+    swap_fee: Percent,
     oracle: OracleRef,
     querier: &'a QuerierWrapper<'a>,
 }
@@ -32,6 +33,7 @@ impl<'a> OpenLoanReq<'a> {
         liability: &'a Liability,
         funds_in: Vec<CwCoin>,
         max_ltv: Option<Percent>,
+        swap_fee: Percent,
         oracle: OracleRef,
         querier: &'a QuerierWrapper<'a>,
     ) -> Self {
@@ -39,6 +41,7 @@ impl<'a> OpenLoanReq<'a> {
             liability,
             funds_in,
             max_ltv,
+            swap_fee,
             oracle,
             querier,
         }
@@ -55,7 +58,10 @@ impl<'a> WithLppLender for OpenLoanReq<'a> {
         Lpn: Currency + Serialize,
         LppLender: LppLenderTrait<Lpn>,
     {
-        let (downpayment, downpayment_lpn) = bank::may_received::<PaymentGroup, _>(
+        let Downpayment {
+            amount: downpayment,
+            amount_lpn: downpayment_lpn,
+        } = bank::may_received::<PaymentGroup, _>(
             self.funds_in,
             DownpaymentHandler {
                 oracle: self.oracle,
@@ -65,6 +71,8 @@ impl<'a> WithLppLender for OpenLoanReq<'a> {
         )
         .ok_or_else(Self::Error::NoPaymentError)??;
 
+        let swap_fee = self.swap_fee.of(downpayment_lpn);
+        let downpayment_lpn = downpayment_lpn - swap_fee;
         if downpayment_lpn.is_zero() {
             return Err(Self::Error::InsufficientPayment(downpayment));
         }
@@ -72,16 +80,28 @@ impl<'a> WithLppLender for OpenLoanReq<'a> {
         let borrow_lpn = self
             .liability
             .init_borrow_amount(downpayment_lpn, self.max_ltv);
+        if borrow_lpn < swap_fee {
+            return Err(Self::Error::InvalidParameters(format!(
+                "The loan {} does not cover the swap fee {}",
+                borrow_lpn, swap_fee
+            )));
+        }
 
         lpp.open_loan_req(borrow_lpn)?;
 
         Ok(Self::Output {
             batch: lpp.into().batch,
             downpayment,
+            swap_fee: swap_fee.into(),
         })
     }
 }
 
+struct Downpayment<Lpn> {
+    amount: DownpaymentCoin,
+    amount_lpn: Coin<Lpn>,
+}
+
 struct DownpaymentHandler<'a, Lpn> {
     oracle: OracleRef,
     _lpn: PhantomData<Lpn>,
@@ -91,7 +111,7 @@ impl<'a, Lpn> WithCoin for DownpaymentHandler<'a, Lpn>
 where
     Lpn: Currency,
 {
-    type Output = (DownpaymentCoin, Coin<Lpn>);
+    type Output = Downpayment<Lpn>;
 
     type Error = ContractError;
 
@@ -99,17 +119,22 @@ where
     where
         C: Currency,
     {
-        let downpayment_lpn = convert::to_base(self.oracle.clone(), in_amount, self.querier)?;
+        let amount_lpn = convert::to_base(self.oracle.clone(), in_amount, self.querier)?;
 
-        let downpayment = in_amount.into();
-
-        Ok((downpayment, downpayment_lpn))
+        Ok(Downpayment {
+            amount: in_amount.into(),
+            amount_lpn,
+        })
     }
 }
 
 pub struct OpenLoanReqResult {
     pub(in crate::contract) batch: Batch,
     pub(in crate::contract) downpayment: DownpaymentCoin,
+    /// The swap fee on the downpayment
+    ///
+    /// It is withheld from the loan and paid once the lease asset is bought.
+    pub(in crate::contract) swap_fee: LpnCoin,
 }
 
 pub struct OpenLoanResp {
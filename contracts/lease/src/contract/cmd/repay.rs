@@ -1,28 +1,57 @@
 use serde::Serialize;
 
-use finance::currency::Currency;
+use finance::{coin::Coin, currency::Currency, percent::Percent};
 use lpp::stub::lender::LppLender as LppLenderTrait;
 use oracle::stub::Oracle as OracleTrait;
-use platform::batch::{Batch, Emit, Emitter};
+use platform::{
+    bank::{FixedAddressSender, LazySenderStub},
+    batch::{Batch, Emit, Emitter},
+};
 use profit::stub::Profit as ProfitTrait;
-use sdk::cosmwasm_std::Env;
+use sdk::cosmwasm_std::{Addr, Env, Timestamp};
 use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
 
 use crate::{
-    api::LpnCoin,
+    api::{LedgerRecord, LpnCoin},
     error::ContractError,
     event::Type,
     lease::{with_lease::WithLease, Lease, LeaseDTO, RepayResult as LeaseRepayResult},
+    loan::RepayReceipt,
 };
 
 pub struct Repay<'a> {
-    payment: LpnCoin,
+    paid: Option<LpnCoin>,
+    swapped: Option<LpnCoin>,
+    swap_fee: Percent,
     env: &'a Env,
 }
 
 impl<'a> Repay<'a> {
+    /// Repay with a `payment` made in LPN
     pub fn new(payment: LpnCoin, env: &'a Env) -> Self {
-        Self { payment, env }
+        Self {
+            paid: Some(payment),
+            swapped: None,
+            swap_fee: Percent::ZERO,
+            env,
+        }
+    }
+
+    /// Repay with LPN obtained from swaps, and optionally with a payment made in LPN
+    ///
+    /// The `swap_fee` is charged only on the `swapped` amount and sent to the Profit contract.
+    pub fn swapped(
+        swapped: LpnCoin,
+        paid: Option<LpnCoin>,
+        swap_fee: Percent,
+        env: &'a Env,
+    ) -> Self {
+        Self {
+            paid,
+            swapped: Some(swapped),
+            swap_fee,
+            env,
+        }
     }
 }
 
@@ -31,6 +60,7 @@ pub struct RepayResult {
     pub paid: bool,
     pub batch: Batch,
     pub emitter: Emitter,
+    pub record: LedgerRecord,
 }
 
 impl<'a> WithLease for Repay<'a> {
@@ -50,7 +80,18 @@ impl<'a> WithLease for Repay<'a> {
         Profit: ProfitTrait,
         Asset: Currency + Serialize,
     {
-        let payment = self.payment.try_into()?;
+        let paid: Coin<Lpn> = self
+            .paid
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+        let swapped: Coin<Lpn> = self
+            .swapped
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+        let swap_fee = self.swap_fee.of(swapped);
+        let payment = paid + swapped - swap_fee;
 
         let LeaseRepayResult {
             batch,
@@ -58,24 +99,92 @@ impl<'a> WithLease for Repay<'a> {
             receipt,
         } = lease.repay(payment, self.env.block.time)?;
 
-        let emitter = Emitter::of_type(Type::PaidActive)
-            .emit_tx_info(self.env)
-            .emit("to", lease.addr.clone())
-            .emit_currency::<_, Lpn>("payment-symbol")
-            .emit_coin_amount("payment-amount", payment)
-            .emit_to_string_value("loan-close", receipt.close())
-            .emit_coin_amount("prev-margin-interest", receipt.previous_margin_paid())
-            .emit_coin_amount("prev-loan-interest", receipt.previous_interest_paid())
-            .emit_coin_amount("curr-margin-interest", receipt.current_margin_paid())
-            .emit_coin_amount("curr-loan-interest", receipt.current_interest_paid())
-            .emit_coin_amount("principal", receipt.principal_paid())
-            .emit_coin_amount("change", receipt.change());
+        let batch = if swap_fee.is_zero() {
+            batch
+        } else {
+            batch.merge(send_fee(swap_fee, lease.loan.profit().clone().into()))
+        };
+
+        let emitter = emit_payment_info(
+            Emitter::of_type(Type::PaidActive)
+                .emit_tx_info(self.env)
+                .emit("to", lease.addr.clone()),
+            payment,
+            &receipt,
+        )
+        .emit_coin_amount("swap-fee", swap_fee);
 
         Ok(RepayResult {
             lease,
             paid: receipt.close(),
             batch,
             emitter,
+            record: LedgerRecord::Repay {
+                payment: payment.into(),
+                receipt: receipt.into(),
+            },
         })
     }
 }
+
+/// The amount of LPN that pays off the lease
+pub struct FullRepayAmount {
+    now: Timestamp,
+}
+
+impl FullRepayAmount {
+    pub fn new(now: Timestamp) -> Self {
+        Self { now }
+    }
+}
+
+impl WithLease for FullRepayAmount {
+    type Output = LpnCoin;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        lease.full_repay_amount(self.now).map(Into::into)
+    }
+}
+
+pub(super) fn emit_payment_info<Lpn>(
+    emitter: Emitter,
+    payment: Coin<Lpn>,
+    receipt: &RepayReceipt<Lpn>,
+) -> Emitter
+where
+    Lpn: Currency,
+{
+    emitter
+        .emit_currency::<_, Lpn>("payment-symbol")
+        .emit_coin_amount("payment-amount", payment)
+        .emit_to_string_value("loan-close", receipt.close())
+        .emit_coin_amount("late-payment-penalty", receipt.late_payment_penalty_paid())
+        .emit_coin_amount("prev-margin-interest", receipt.previous_margin_paid())
+        .emit_coin_amount("prev-loan-interest", receipt.previous_interest_paid())
+        .emit_coin_amount("curr-margin-interest", receipt.current_margin_paid())
+        .emit_coin_amount("curr-loan-interest", receipt.current_interest_paid())
+        .emit_coin_amount("principal", receipt.principal_paid())
+        .emit_coin_amount("change", receipt.change())
+}
+
+fn send_fee<Lpn>(fee: Coin<Lpn>, profit: Addr) -> Batch
+where
+    Lpn: Currency,
+{
+    let mut sender = LazySenderStub::new(profit);
+    sender.send(fee);
+    sender.into()
+}
@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+use finance::currency::Currency;
+use lpp::stub::lender::LppLender as LppLenderTrait;
+use oracle::stub::Oracle as OracleTrait;
+use profit::stub::Profit as ProfitTrait;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
+
+use crate::{
+    api::{LpnCoin, RepayQuoteResponse},
+    error::ContractError,
+    lease::{with_lease::WithLease, Lease, RepayResult as LeaseRepayResult},
+};
+
+/// Simulate a repayment
+///
+/// The updated lease and the produced messages are discarded.
+pub struct RepayQuote {
+    payment: LpnCoin,
+    now: Timestamp,
+}
+
+impl RepayQuote {
+    pub fn new(payment: LpnCoin, now: Timestamp) -> Self {
+        Self { payment, now }
+    }
+}
+
+impl WithLease for RepayQuote {
+    type Output = RepayQuoteResponse;
+
+    type Error = ContractError;
+
+    fn exec<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>(
+        self,
+        lease: Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Lpn: Currency + Serialize,
+        Lpp: LppLenderTrait<Lpn>,
+        TimeAlarms: TimeAlarmsTrait,
+        Oracle: OracleTrait<Lpn>,
+        Profit: ProfitTrait,
+        Asset: Currency + Serialize,
+    {
+        let payment = self.payment.try_into()?;
+
+        lease
+            .repay(payment, self.now)
+            .map(|LeaseRepayResult { receipt, .. }| receipt.into())
+    }
+}
@@ -0,0 +1,45 @@
+use finance::{
+    coin::{Coin, CoinDTO, WithCoin, WithCoinResult},
+    currency::{Currency, Group},
+};
+use platform::{
+    bank::{self, BankAccount},
+    batch::Batch,
+};
+use sdk::cosmwasm_std::{Addr, QuerierWrapper};
+
+use crate::error::{ContractError, ContractResult};
+
+/// Send an amount of any currency from an account to another
+pub fn send<G>(
+    amount: &CoinDTO<G>,
+    from: &Addr,
+    to: &Addr,
+    querier: &QuerierWrapper<'_>,
+) -> ContractResult<Batch>
+where
+    G: Group,
+{
+    amount.with_coin(SendTo { from, to, querier })
+}
+
+struct SendTo<'a> {
+    from: &'a Addr,
+    to: &'a Addr,
+    querier: &'a QuerierWrapper<'a>,
+}
+
+impl<'a> WithCoin for SendTo<'a> {
+    type Output = Batch;
+
+    type Error = ContractError;
+
+    fn on<C>(&self, amount: Coin<C>) -> WithCoinResult<Self>
+    where
+        C: Currency,
+    {
+        let mut account = bank::account(self.from, self.querier);
+        account.send(amount, self.to);
+        Ok(account.into())
+    }
+}
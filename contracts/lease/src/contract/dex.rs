@@ -1,38 +1,37 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
-use currency::native::Nls;
 use finance::{
-    coin::{Coin, CoinDTO},
+    coin::{self, Amount, CoinDTO},
     currency::{Group, Symbol},
-    duration::Duration,
+    percent::Percent,
 };
+use marketprice::SpotPrice;
 use oracle::stub::OracleRef;
 use platform::{
     bank_ibc::{local::Sender as LocalSender, remote::Sender as RemoteSender},
+    bank_remote::Sender as DexSender,
     batch::Batch as LocalBatch,
     ica::{self, HostAccount},
     trx::Transaction,
 };
-use sdk::cosmwasm_std::{Addr, QuerierWrapper, Timestamp};
-use swap::trx;
-
-use crate::{api::dex::ConnectionParams, error::ContractResult};
-
-pub(super) const IBC_TIMEOUT: Duration = Duration::from_secs(60);
-
-//TODO take them as input from the client
-const ICA_TRANSFER_ACK_TIP: Coin<Nls> = Coin::new(1);
-const ICA_TRANSFER_TIMEOUT_TIP: Coin<Nls> = ICA_TRANSFER_ACK_TIP;
+use sdk::{
+    cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData,
+    cosmwasm_std::{Addr, QuerierWrapper, StdError, Timestamp, Uint128, Uint512},
+};
+use swap::{trx::Dex, DexProtocol, SwapPath};
 
-//TODO take them as input from the client
-const ICA_SWAP_ACK_TIP: Coin<Nls> = Coin::new(1);
-const ICA_SWAP_TIMEOUT_TIP: Coin<Nls> = ICA_SWAP_ACK_TIP;
+use crate::{
+    api::dex::{ConnectionParams, RelayingParams},
+    error::{ContractError, ContractResult},
+};
 
 pub(crate) trait DexConnectable {
     fn dex(&self) -> &ConnectionParams;
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct Account {
     /// The contract at Nolus that owns the account
     owner: Addr,
@@ -45,6 +44,10 @@ impl Account {
         &self.dex_account
     }
 
+    pub(super) fn update_relaying(&mut self, relaying: RelayingParams) {
+        self.dex.relaying = relaying;
+    }
+
     pub(super) fn register_request(dex: &ConnectionParams) -> LocalBatch {
         ica::register_account(&dex.connection_id)
     }
@@ -67,6 +70,7 @@ impl Account {
             &self.dex.transfer_channel.local_endpoint,
             &self.owner,
             &self.dex_account,
+            &self.dex.relaying,
             now,
         )
     }
@@ -74,9 +78,32 @@ impl Account {
     pub(super) fn swap<'a>(
         &'a self,
         oracle: &'a OracleRef,
+        max_slippage: Percent,
+        routes: &'a SwapRoutes,
         querier: &'a QuerierWrapper<'a>,
     ) -> SwapTrx<'a> {
-        SwapTrx::new(&self.dex.connection_id, &self.dex_account, oracle, querier)
+        SwapTrx::new(
+            &self.dex,
+            &self.dex_account,
+            oracle,
+            max_slippage,
+            RoutesIter::new(routes),
+            querier,
+        )
+    }
+
+    pub(super) fn swap_resp<'a>(
+        &'a self,
+        oracle: &'a OracleRef,
+        routes: &'a SwapRoutes,
+        querier: &'a QuerierWrapper<'a>,
+    ) -> SwapResp<'a> {
+        SwapResp {
+            protocol: &self.dex.protocol,
+            oracle,
+            routes: RoutesIter::new(routes),
+            querier,
+        }
     }
 
     pub(super) fn transfer_from(&self, now: Timestamp) -> TransferInTrx<'_> {
@@ -85,9 +112,31 @@ impl Account {
             &self.dex.transfer_channel.remote_endpoint,
             &self.dex_account,
             &self.owner,
+            &self.dex.relaying,
             now,
         )
     }
+
+    /// Send an amount from the DEX account to another account at the DEX network
+    pub(super) fn send_on_dex<G>(
+        &self,
+        receiver: HostAccount,
+        amount: &CoinDTO<G>,
+    ) -> ContractResult<LocalBatch>
+    where
+        G: Group,
+    {
+        let mut sender = DexSender::new(self.dex_account.clone(), receiver);
+        sender.send(amount)?;
+        Ok(ica::submit_transaction(
+            &self.dex.connection_id,
+            sender.into(),
+            "memo",
+            self.dex.relaying.timeout,
+            self.dex.relaying.ica_ack_tip,
+            self.dex.relaying.ica_timeout_tip,
+        ))
+    }
 }
 
 impl From<Account> for HostAccount {
@@ -107,14 +156,20 @@ pub(super) struct TransferOutTrx<'a> {
 }
 
 impl<'a> TransferOutTrx<'a> {
-    fn new(channel: &'a str, sender: &Addr, receiver: &HostAccount, now: Timestamp) -> Self {
+    fn new(
+        channel: &'a str,
+        sender: &Addr,
+        receiver: &HostAccount,
+        relaying: &RelayingParams,
+        now: Timestamp,
+    ) -> Self {
         let sender = LocalSender::new(
             channel,
             sender.clone(),
             receiver.clone(),
-            now + IBC_TIMEOUT,
-            ICA_TRANSFER_ACK_TIP,
-            ICA_TRANSFER_TIMEOUT_TIP,
+            now + relaying.timeout,
+            relaying.transfer_ack_tip,
+            relaying.transfer_timeout_tip,
         );
 
         TransferOutTrx { sender }
@@ -134,27 +189,96 @@ impl<'r> From<TransferOutTrx<'r>> for LocalBatch {
     }
 }
 
+/// The routes of the swaps, one entry per swap in the order of the requests
+///
+/// They are resolved on creating a swap state so that its responses are decoded against
+/// the same routes as the requests were sent along, even if the alternative pools get
+/// changed meanwhile. The swap states stored by a previous version have none and query
+/// the routes on each request and response.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub(crate) struct SwapRoutes(Vec<Vec<SwapPath>>);
+
+impl SwapRoutes {
+    /// Resolve the routes of the next swap, that is of `amount` into `currency_out`
+    pub fn resolve<G>(
+        &mut self,
+        oracle: &OracleRef,
+        amount: &CoinDTO<G>,
+        currency_out: Symbol<'_>,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<()>
+    where
+        G: Group,
+    {
+        let routes = oracle.swap_routes(amount.ticker().into(), currency_out.into(), querier)?;
+        self.0.push(routes);
+        Ok(())
+    }
+}
+
+/// Iterate over the resolved swap routes, falling back to querying them
+struct RoutesIter<'a> {
+    routes: &'a SwapRoutes,
+    next: usize,
+}
+
+impl<'a> RoutesIter<'a> {
+    fn new(routes: &'a SwapRoutes) -> Self {
+        Self { routes, next: 0 }
+    }
+
+    fn next<G>(
+        &mut self,
+        oracle: &OracleRef,
+        amount: &CoinDTO<G>,
+        currency_out: Symbol<'_>,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Cow<'a, [SwapPath]>>
+    where
+        G: Group,
+    {
+        let routes = if let Some(routes) = self.routes.0.get(self.next) {
+            Cow::Borrowed(routes.as_slice())
+        } else {
+            Cow::Owned(oracle.swap_routes(amount.ticker().into(), currency_out.into(), querier)?)
+        };
+        self.next += 1;
+        Ok(routes)
+    }
+}
+
 pub(super) struct SwapTrx<'a> {
     conn: &'a str,
+    protocol: &'a DexProtocol,
     ica_account: &'a HostAccount,
+    relaying: &'a RelayingParams,
     trx: Transaction,
     oracle: &'a OracleRef,
+    max_slippage: Percent,
+    routes: RoutesIter<'a>,
     querier: &'a QuerierWrapper<'a>,
 }
 
 impl<'a> SwapTrx<'a> {
     fn new(
-        conn: &'a str,
+        dex: &'a ConnectionParams,
         ica_account: &'a HostAccount,
         oracle: &'a OracleRef,
+        max_slippage: Percent,
+        routes: RoutesIter<'a>,
         querier: &'a QuerierWrapper<'a>,
     ) -> Self {
         let trx = Transaction::default();
         Self {
-            conn,
+            conn: &dex.connection_id,
+            protocol: &dex.protocol,
             ica_account,
+            relaying: &dex.relaying,
             trx,
             oracle,
+            max_slippage,
+            routes,
             querier,
         }
     }
@@ -167,12 +291,138 @@ impl<'a> SwapTrx<'a> {
     where
         G: Group,
     {
-        let swap_path =
-            self.oracle
-                .swap_path(amount.ticker().into(), currency_out.into(), self.querier)?;
-        trx::exact_amount_in(&mut self.trx, self.ica_account.clone(), amount, &swap_path)?;
+        let swap_routes = self
+            .routes
+            .next(self.oracle, amount, currency_out, self.querier)?;
+        let min_out = self.min_out(amount, currency_out)?;
+        self.protocol.split_exact_amount_in(
+            &mut self.trx,
+            self.ica_account.clone(),
+            amount,
+            min_out,
+            &swap_routes,
+        )?;
+        Ok(())
+    }
+
+    /// Swap into exactly `token_out` taking up to `token_in_max`
+    ///
+    /// The amount taken in is further limited by the market value of `token_out`
+    /// increased by the maximum slippage.
+    pub fn swap_exact_out<GIn, GOut>(
+        &mut self,
+        token_in_max: &CoinDTO<GIn>,
+        token_out: &CoinDTO<GOut>,
+    ) -> ContractResult<()>
+    where
+        GIn: Group,
+        GOut: Group,
+    {
+        let swap_routes =
+            self.routes
+                .next(self.oracle, token_in_max, token_out.ticker(), self.querier)?;
+        // an exact amount out is swapped along the main path only
+        let swap_path = swap_routes.first().ok_or_else(|| {
+            ContractError::InvalidParameters(format!(
+                "No swap path from {} to {}",
+                token_in_max.ticker(),
+                token_out.ticker()
+            ))
+        })?;
+        let max_in = self
+            .max_in(token_in_max.ticker(), token_out)?
+            .min(token_in_max.amount());
+        self.protocol.exact_amount_out(
+            &mut self.trx,
+            self.ica_account.clone(),
+            &coin::from_amount_ticker::<GIn>(max_in, token_in_max.ticker())?,
+            token_out,
+            swap_path,
+        )?;
         Ok(())
     }
+
+    /// The maximum amount of `currency_in` to swap into exactly `token_out`
+    pub fn max_in<G>(
+        &self,
+        currency_in: Symbol<'_>,
+        token_out: &CoinDTO<G>,
+    ) -> ContractResult<Amount>
+    where
+        G: Group,
+    {
+        let price_in = self.oracle.spot_price(currency_in.into(), self.querier)?;
+        let price_out = self
+            .oracle
+            .spot_price(token_out.ticker().into(), self.querier)?;
+        Ok(max_in_amount(
+            token_out.amount(),
+            &price_in,
+            &price_out,
+            self.max_slippage,
+        ))
+    }
+
+    fn min_out<G>(&self, amount: &CoinDTO<G>, currency_out: Symbol<'_>) -> ContractResult<Amount>
+    where
+        G: Group,
+    {
+        let price_in = self
+            .oracle
+            .spot_price(amount.ticker().into(), self.querier)?;
+        let price_out = self.oracle.spot_price(currency_out.into(), self.querier)?;
+        min_out_amount(amount.amount(), &price_in, &price_out, self.max_slippage)
+    }
+}
+
+/// Calculate the minimum amount expected out of a swap
+///
+/// The oracle prices of both currencies are against the same base currency.
+/// The market value of the input amount is reduced by the maximum slippage.
+fn min_out_amount(
+    amount_in: Amount,
+    price_in: &SpotPrice,
+    price_out: &SpotPrice,
+    max_slippage: Percent,
+) -> ContractResult<Amount> {
+    let max_slippage = max_slippage.min(Percent::HUNDRED);
+    let nominator = Uint512::from(amount_in)
+        * Uint512::from(price_in.quote().amount())
+        * Uint512::from(price_out.base().amount())
+        * Uint512::from((Percent::HUNDRED - max_slippage).units());
+    let denominator = Uint512::from(price_in.base().amount())
+        * Uint512::from(price_out.quote().amount())
+        * Uint512::from(Percent::HUNDRED.units());
+    Uint128::try_from(nominator / denominator)
+        .map(Into::into)
+        .map_err(|err| StdError::from(err).into())
+}
+
+/// Calculate the maximum amount to pay in a swap into an exact amount out
+///
+/// The oracle prices of both currencies are against the same base currency.
+/// The market value of the output amount is increased by the maximum slippage.
+/// The result is rounded up, and saturates on overflow or on a hundred percent slippage.
+fn max_in_amount(
+    amount_out: Amount,
+    price_in: &SpotPrice,
+    price_out: &SpotPrice,
+    max_slippage: Percent,
+) -> Amount {
+    let max_slippage = max_slippage.min(Percent::HUNDRED);
+    let nominator = Uint512::from(amount_out)
+        * Uint512::from(price_out.quote().amount())
+        * Uint512::from(price_in.base().amount())
+        * Uint512::from(Percent::HUNDRED.units());
+    let denominator = Uint512::from(price_out.base().amount())
+        * Uint512::from(price_in.quote().amount())
+        * Uint512::from((Percent::HUNDRED - max_slippage).units());
+    if denominator.is_zero() {
+        Amount::MAX
+    } else {
+        Uint128::try_from((nominator + denominator - Uint512::one()) / denominator)
+            .map_or(Amount::MAX, Into::into)
+    }
 }
 
 impl From<SwapTrx<'_>> for LocalBatch {
@@ -181,15 +431,47 @@ impl From<SwapTrx<'_>> for LocalBatch {
             value.conn,
             value.trx,
             "memo",
-            IBC_TIMEOUT,
-            ICA_SWAP_ACK_TIP,
-            ICA_SWAP_TIMEOUT_TIP,
+            value.relaying.timeout,
+            value.relaying.ica_ack_tip,
+            value.relaying.ica_timeout_tip,
         )
     }
 }
 
+/// Decode the responses to the swaps requested with a [`SwapTrx`]
+pub(super) struct SwapResp<'a> {
+    protocol: &'a DexProtocol,
+    oracle: &'a OracleRef,
+    routes: RoutesIter<'a>,
+    querier: &'a QuerierWrapper<'a>,
+}
+
+impl<'a> SwapResp<'a> {
+    /// Decode the amount out of the next responses to a swap of `amount` into `currency_out`
+    ///
+    /// The swaps should be decoded in the order they have been requested.
+    pub fn exact_amount_in<I, G>(
+        &mut self,
+        trx_resps: &mut I,
+        amount: &CoinDTO<G>,
+        currency_out: Symbol<'_>,
+    ) -> ContractResult<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+        G: Group,
+    {
+        let swap_routes = self
+            .routes
+            .next(self.oracle, amount, currency_out, self.querier)?;
+        self.protocol
+            .split_exact_amount_in_resp(trx_resps, amount.amount(), swap_routes.len())
+            .map_err(Into::into)
+    }
+}
+
 pub(super) struct TransferInTrx<'a> {
     conn: &'a str,
+    relaying: &'a RelayingParams,
     sender: RemoteSender<'a>,
 }
 
@@ -199,11 +481,20 @@ impl<'a> TransferInTrx<'a> {
         channel: &'a str,
         sender: &HostAccount,
         receiver: &Addr,
+        relaying: &'a RelayingParams,
         now: Timestamp,
     ) -> Self {
-        let sender =
-            RemoteSender::new(channel, sender.clone(), receiver.clone(), now + IBC_TIMEOUT);
-        TransferInTrx { conn, sender }
+        let sender = RemoteSender::new(
+            channel,
+            sender.clone(),
+            receiver.clone(),
+            now + relaying.timeout,
+        );
+        TransferInTrx {
+            conn,
+            relaying,
+            sender,
+        }
     }
 
     pub fn send<G>(&mut self, amount: &CoinDTO<G>) -> ContractResult<()>
@@ -220,9 +511,121 @@ impl<'r> From<TransferInTrx<'r>> for LocalBatch {
             value.conn,
             value.sender.into(),
             "memo",
-            IBC_TIMEOUT,
-            ICA_SWAP_ACK_TIP,
-            ICA_SWAP_TIMEOUT_TIP,
+            value.relaying.timeout,
+            value.relaying.ica_ack_tip,
+            value.relaying.ica_timeout_tip,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currency::{
+        lease::{Atom, Cro},
+        lpn::Usdc,
+    };
+    use finance::{coin::Coin, currency::Currency, percent::Percent};
+    use marketprice::SpotPrice;
+
+    fn price<C, QuoteC>(amount: u128, amount_quote: u128) -> SpotPrice
+    where
+        C: Currency,
+        QuoteC: Currency,
+    {
+        SpotPrice::new(
+            Coin::<C>::new(amount).into(),
+            Coin::<QuoteC>::new(amount_quote).into(),
         )
     }
+
+    #[test]
+    fn min_out_no_slippage() {
+        assert_eq!(
+            Ok(600),
+            super::min_out_amount(
+                100,
+                &price::<Cro, Usdc>(1, 3),
+                &price::<Atom, Usdc>(2, 1),
+                Percent::ZERO
+            )
+        );
+    }
+
+    #[test]
+    fn min_out_with_slippage() {
+        assert_eq!(
+            Ok(576),
+            super::min_out_amount(
+                100,
+                &price::<Cro, Usdc>(1, 3),
+                &price::<Atom, Usdc>(2, 1),
+                Percent::from_percent(4)
+            )
+        );
+    }
+
+    #[test]
+    fn min_out_to_base() {
+        assert_eq!(
+            Ok(297),
+            super::min_out_amount(
+                100,
+                &price::<Cro, Usdc>(1, 3),
+                &price::<Usdc, Usdc>(1, 1),
+                Percent::from_percent(1)
+            )
+        );
+    }
+
+    #[test]
+    fn min_out_full_slippage() {
+        assert_eq!(
+            Ok(0),
+            super::min_out_amount(
+                100,
+                &price::<Cro, Usdc>(1, 3),
+                &price::<Atom, Usdc>(2, 1),
+                Percent::HUNDRED
+            )
+        );
+    }
+
+    #[test]
+    fn max_in_no_slippage() {
+        assert_eq!(
+            100,
+            super::max_in_amount(
+                600,
+                &price::<Cro, Usdc>(1, 3),
+                &price::<Atom, Usdc>(2, 1),
+                Percent::ZERO
+            )
+        );
+    }
+
+    #[test]
+    fn max_in_with_slippage() {
+        assert_eq!(
+            105,
+            super::max_in_amount(
+                600,
+                &price::<Cro, Usdc>(1, 3),
+                &price::<Atom, Usdc>(2, 1),
+                Percent::from_percent(4)
+            )
+        );
+    }
+
+    #[test]
+    fn max_in_full_slippage() {
+        assert_eq!(
+            u128::MAX,
+            super::max_in_amount(
+                600,
+                &price::<Cro, Usdc>(1, 3),
+                &price::<Atom, Usdc>(2, 1),
+                Percent::HUNDRED
+            )
+        );
+    }
 }
@@ -1,11 +1,15 @@
 use enum_dispatch::enum_dispatch;
 use serde::{Deserialize, Serialize};
 
+use finance::percent::Percent;
 use sdk::cosmwasm_std::{QuerierWrapper, Timestamp};
 
 use crate::{
-    api::{dex::ConnectionParams, StateResponse},
-    error::ContractResult,
+    api::{
+        dex::{ConnectionParams, RelayingParams},
+        LeasePrice, LiquidationQuoteResponse, LpnCoin, RepayQuoteResponse, StateResponse,
+    },
+    error::{ContractError, ContractResult},
     lease::LeaseDTO,
 };
 
@@ -23,12 +27,43 @@ mod state;
 #[enum_dispatch]
 trait Contract {
     fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse>;
+
+    fn repay_quote(
+        self,
+        _payment: LpnCoin,
+        _now: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<RepayQuoteResponse> {
+        Err(ContractError::unsupported_operation("repay quote"))
+    }
+
+    fn liquidation_quote(
+        self,
+        _price: LeasePrice,
+        _at: Timestamp,
+        _now: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<LiquidationQuoteResponse> {
+        Err(ContractError::unsupported_operation("liquidation quote"))
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub(crate) struct Lease {
     lease: LeaseDTO,
     dex: Account,
+    /// The maximum tolerated deviation of a swap output from the oracle price
+    #[serde(default = "crate::api::default_max_slippage")]
+    max_slippage: Percent,
+    /// The fee charged on the payments that are not in LPN
+    #[serde(default)]
+    swap_fee: Percent,
+}
+
+impl Lease {
+    fn update_dex_relaying(&mut self, relaying: RelayingParams) {
+        self.dex.update_relaying(relaying)
+    }
 }
 
 impl DexConnectable for Lease {
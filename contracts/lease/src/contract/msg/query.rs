@@ -1,8 +1,9 @@
 use finance::currency::Currency;
 
 use crate::{
-    api::{opened, paid, StateResponse},
-    lease::State,
+    api::{opened, paid, LiquidationCause, RepayReceipt, StateResponse},
+    lease::{Cause, LiquidationPrices, State},
+    loan::RepayReceipt as LoanRepayReceipt,
 };
 
 impl StateResponse {
@@ -21,8 +22,11 @@ impl StateResponse {
             principal_due,
             previous_margin_due,
             previous_interest_due,
+            late_payment_penalty_due,
             current_margin_due,
             current_interest_due,
+            ltv,
+            liquidation_prices,
             validity,
         } = lease_state
         {
@@ -33,10 +37,14 @@ impl StateResponse {
                 principal_due: principal_due.into(),
                 previous_margin_due: previous_margin_due.into(),
                 previous_interest_due: previous_interest_due.into(),
+                late_payment_penalty_due: late_payment_penalty_due.into(),
                 current_margin_due: current_margin_due.into(),
                 current_interest_due: current_interest_due.into(),
+                ltv,
+                liquidation_prices: liquidation_prices.into(),
                 validity,
                 in_progress,
+                last_error: None,
             }
         } else {
             unreachable!();
@@ -55,9 +63,52 @@ impl StateResponse {
             Self::Paid {
                 amount: amount.into(),
                 in_progress,
+                last_error: None,
             }
         } else {
             unreachable!();
         }
     }
 }
+
+impl<Asset, Lpn> From<LiquidationPrices<Asset, Lpn>> for opened::LiquidationPrices
+where
+    Asset: Currency,
+    Lpn: Currency,
+{
+    fn from(prices: LiquidationPrices<Asset, Lpn>) -> Self {
+        Self {
+            first_warning: prices.first_warning.into(),
+            second_warning: prices.second_warning.into(),
+            third_warning: prices.third_warning.into(),
+            liquidation: prices.liquidation.into(),
+        }
+    }
+}
+
+impl<Lpn> From<LoanRepayReceipt<Lpn>> for RepayReceipt
+where
+    Lpn: Currency,
+{
+    fn from(receipt: LoanRepayReceipt<Lpn>) -> Self {
+        Self {
+            late_payment_penalty: receipt.late_payment_penalty_paid().into(),
+            previous_margin_interest: receipt.previous_margin_paid().into(),
+            previous_loan_interest: receipt.previous_interest_paid().into(),
+            current_margin_interest: receipt.current_margin_paid().into(),
+            current_loan_interest: receipt.current_interest_paid().into(),
+            principal: receipt.principal_paid().into(),
+            change: receipt.change().into(),
+            paid: receipt.close(),
+        }
+    }
+}
+
+impl From<Cause> for LiquidationCause {
+    fn from(cause: Cause) -> Self {
+        match cause {
+            Cause::Overdue => Self::Overdue,
+            Cause::Liability => Self::Liability,
+        }
+    }
+}
@@ -2,13 +2,13 @@ use cosmwasm_std::{DepsMut, MessageInfo, Timestamp};
 use serde::{Deserialize, Serialize};
 
 use platform::{
-    bank,
+    bank::{self, BankAccount},
     batch::{Batch, Emit, Emitter},
 };
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
-    api::{ExecuteMsg, StateResponse},
+    api::{ExecuteMsg, LedgerRecord, StateResponse},
     contract::{cmd::Close, state, Contract},
     error::ContractResult,
     event::Type,
@@ -28,10 +28,32 @@ impl Closed {
     ) -> ContractResult<Batch> {
         let lease_addr = lease.addr.clone();
         let lease_account = bank::account(&lease_addr, querier);
+        Self::close(lease, Close::new(lease_account), querier)
+    }
+
+    /// Enter the state of a lease whose amount has already been delivered to the customer
+    pub(super) fn enter_state_delivered(
+        &self,
+        lease: LeaseDTO,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Batch> {
+        let lease_addr = lease.addr.clone();
+        let lease_account = bank::account(&lease_addr, querier);
+        Self::close(lease, Close::delivered(lease_account), querier)
+    }
+
+    fn close<B>(
+        lease: LeaseDTO,
+        cmd: Close<B>,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Batch>
+    where
+        B: BankAccount,
+    {
         let IntoDTOResult {
             lease: _abandon,
             batch,
-        } = with_lease::execute(lease, Close::new(lease_account), querier)?;
+        } = with_lease::execute(lease, cmd, querier)?;
         Ok(batch)
     }
 
@@ -40,6 +62,12 @@ impl Closed {
             .emit("id", lease.addr.clone())
             .emit_tx_info(env)
     }
+
+    pub(super) fn record(&self, lease: &LeaseDTO) -> LedgerRecord {
+        LedgerRecord::Close {
+            amount: lease.amount.clone(),
+        }
+    }
 }
 
 impl Controller for Closed {
@@ -52,7 +80,19 @@ impl Controller for Closed {
     ) -> ContractResult<Response> {
         match msg {
             ExecuteMsg::Repay() => controller::err("repay", deps.api),
-            ExecuteMsg::Close() => controller::err("close", deps.api),
+            ExecuteMsg::Close { .. } => controller::err("close", deps.api),
+            ExecuteMsg::AddCollateral() => controller::err("add collateral", deps.api),
+            ExecuteMsg::Cancel() => controller::err("cancel", deps.api),
+            ExecuteMsg::BorrowMore { .. } => controller::err("borrow more", deps.api),
+            ExecuteMsg::ClosePosition { .. } => controller::err("close position", deps.api),
+            ExecuteMsg::ChangeClosePolicy { .. } => {
+                controller::err("change close policy", deps.api)
+            }
+            ExecuteMsg::GrantOperator { .. } => controller::err("grant operator", deps.api),
+            ExecuteMsg::RevokeOperator { .. } => controller::err("revoke operator", deps.api),
+            ExecuteMsg::TransferOwnership { .. } => controller::err("transfer ownership", deps.api),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
             ExecuteMsg::PriceAlarm() => state::ignore_msg(self),
             ExecuteMsg::TimeAlarm {} => state::ignore_msg(self),
         }
@@ -6,18 +6,24 @@ use finance::currency;
 use sdk::cosmwasm_std::entry_point;
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
-    cosmwasm_std::{to_binary, Api, Binary, Deps, DepsMut, Env, MessageInfo, Reply},
+    cosmwasm_std::{
+        to_binary, Api, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Storage, Timestamp,
+    },
     neutron_sdk::sudo::msg::SudoMsg,
 };
 use versioning::{version, VersionSegment};
 
 use crate::{
-    api::{ExecuteMsg, MigrateMsg, NewLeaseContract, StateQuery},
+    api::{dex::RelayingParams, AnyQueryMsg, ExecuteMsg, MigrateMsg, NewLeaseContract, QueryMsg},
     contract::Contract,
     error::{ContractError, ContractResult},
 };
 
-use super::{opening::request_loan::RequestLoan, Response};
+use super::{
+    fee_reserve, ica_error, ledger,
+    opening::{self, request_loan::RequestLoan},
+    Response, State,
+};
 
 // const CONTRACT_STORAGE_VERSION_FROM: VersionSegment = 1;
 const CONTRACT_STORAGE_VERSION: VersionSegment = 2;
@@ -102,18 +108,10 @@ pub fn migrate(deps: DepsMut<'_>, _env: Env, _msg: MigrateMsg) -> ContractResult
 
 #[cfg_attr(feature = "contract-with-bindings", entry_point)]
 pub fn reply(mut deps: DepsMut<'_>, env: Env, msg: Reply) -> ContractResult<CwResponse> {
+    let now = env.block.time;
     super::load(deps.storage)?
         .reply(&mut deps, env, msg)
-        .and_then(
-            |Response {
-                 cw_response,
-                 next_state,
-             }| {
-                super::save(deps.storage, &next_state)?;
-
-                Ok(cw_response)
-            },
-        )
+        .and_then(|resp| save(deps.storage, now, resp))
 }
 
 #[cfg_attr(feature = "contract-with-bindings", entry_point)]
@@ -123,22 +121,22 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> ContractResult<CwResponse> {
-    super::load(deps.storage)?
-        .execute(&mut deps, env, info, msg)
-        .and_then(
-            |Response {
-                 cw_response,
-                 next_state,
-             }| {
-                super::save(deps.storage, &next_state)?;
-
-                Ok(cw_response)
-            },
-        )
+    let now = env.block.time;
+    let state = super::load(deps.storage)?;
+    match msg {
+        ExecuteMsg::Cancel() => opening::cancel::execute(state, &mut deps, env, info),
+        ExecuteMsg::UpdateDexRelaying(relaying) => {
+            update_dex_relaying(state, &mut deps, &env, &info, &relaying)
+        }
+        ExecuteMsg::TopUpFeeReserve() => fee_reserve::top_up(state, &env, info, &deps.querier),
+        _ => state.execute(&mut deps, env, info, msg),
+    }
+    .and_then(|resp| save(deps.storage, now, resp))
 }
 
 #[cfg_attr(feature = "contract-with-bindings", entry_point)]
 pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> ContractResult<CwResponse> {
+    let now = env.block.time;
     let state = super::load(deps.storage)?;
     match msg {
         SudoMsg::OpenAck {
@@ -147,28 +145,75 @@ pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> ContractResult<CwRespo
             counterparty_channel_id: _,
             counterparty_version,
         } => state.on_open_ica(counterparty_version, deps.as_ref(), env),
-        SudoMsg::Response { request: _, data } => state.on_response(data, deps.as_ref(), env),
+        SudoMsg::Response { request: _, data } => {
+            ica_error::clear(deps.storage);
+            state.on_response(data, deps.as_ref(), env)
+        }
         SudoMsg::Timeout { request: _ } => state.on_timeout(deps.as_ref(), env),
         SudoMsg::Error {
             request: _,
-            details: _,
-        } => state.on_error(deps.as_ref(), env),
+            details,
+        } => {
+            ica_error::record(deps.storage, details, env.block.time)?;
+            state.on_error(deps.as_ref(), env)
+        }
         _ => unreachable!(),
     }
-    .and_then(
-        |Response {
-             cw_response,
-             next_state,
-         }| {
-            super::save(deps.storage, &next_state)?;
-
-            Ok(cw_response)
-        },
-    )
+    .and_then(|resp| save(deps.storage, now, resp))
 }
 
 #[cfg_attr(feature = "contract-with-bindings", entry_point)]
-pub fn query(deps: Deps<'_>, env: Env, _msg: StateQuery) -> ContractResult<Binary> {
-    let resp = super::load(deps.storage)?.state(env.block.time, &deps.querier)?;
-    to_binary(&resp).map_err(ContractError::from)
+pub fn query(deps: Deps<'_>, env: Env, msg: AnyQueryMsg) -> ContractResult<Binary> {
+    let state = super::load(deps.storage)?;
+    match msg.into() {
+        QueryMsg::State {} => {
+            let resp = state.state(env.block.time, &deps.querier)?;
+            let resp = ica_error::attach(deps.storage, resp)?;
+            to_binary(&resp)
+        }
+        QueryMsg::RepayQuote { payment } => {
+            let resp = state.repay_quote(payment, env.block.time, &deps.querier)?;
+            to_binary(&resp)
+        }
+        QueryMsg::LiquidationQuote { price, at } => {
+            let resp = state.liquidation_quote(price, at, env.block.time, &deps.querier)?;
+            to_binary(&resp)
+        }
+        QueryMsg::Ledger { start_after, limit } => {
+            to_binary(&ledger::page(deps.storage, start_after, limit)?)
+        }
+    }
+    .map_err(ContractError::from)
+}
+
+fn update_dex_relaying(
+    state: State,
+    deps: &mut DepsMut<'_>,
+    env: &Env,
+    info: &MessageInfo,
+    relaying: &RelayingParams,
+) -> ContractResult<Response> {
+    if platform::contract::query_admin(&deps.querier, &env.contract.address)?.as_ref()
+        != Some(&info.sender)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    super::update_dex_relaying(deps.storage, relaying)?;
+    Ok(Response::from(CwResponse::default(), state))
+}
+
+fn save(
+    storage: &mut dyn Storage,
+    now: Timestamp,
+    Response {
+        cw_response,
+        next_state,
+        ledger,
+    }: Response,
+) -> ContractResult<CwResponse> {
+    super::save_applying_dex_relaying(storage, next_state)?;
+    ledger::append(storage, ledger, now)?;
+
+    Ok(cw_response)
 }
@@ -0,0 +1,112 @@
+use currency::native::Nls;
+use platform::{
+    bank,
+    batch::{Batch, Emit, Emitter},
+};
+use sdk::cosmwasm_std::{Addr, Env, MessageInfo, QuerierWrapper};
+
+use crate::{
+    api::dex::RelayingParams,
+    error::{ContractError, ContractResult},
+    event::Type,
+};
+
+use super::{Response, State};
+
+/// Check whether the fee reserve of the lease covers the least one required to start a DEX interaction
+///
+/// The reserve is the Nls balance of the lease.
+pub(super) fn check(
+    lease: &Addr,
+    relaying: &RelayingParams,
+    querier: &QuerierWrapper<'_>,
+) -> ContractResult<()> {
+    let reserve = bank::balance::<Nls>(lease, querier)?;
+    if reserve < relaying.fee_reserve {
+        Err(ContractError::InsufficientFeeReserve {
+            reserve,
+            required: relaying.fee_reserve,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Top up the fee reserve of the lease with the Nls sent along
+///
+/// The funds are kept in the lease balance, so the reserve is topped up in any state
+/// but the closed one.
+pub(super) fn top_up(
+    state: State,
+    env: &Env,
+    info: MessageInfo,
+    querier: &QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    if matches!(state, State::Closed(_)) {
+        return Err(ContractError::unsupported_operation("top up fee reserve"));
+    }
+
+    let amount = bank::received_one::<Nls>(info.funds)?;
+    let reserve = bank::balance::<Nls>(&env.contract.address, querier)?;
+    let emitter = Emitter::of_type(Type::FeeReserveTopUp)
+        .emit_tx_info(env)
+        .emit("id", &env.contract.address)
+        .emit_coin("amount", amount)
+        .emit_coin("reserve", reserve);
+    Ok(Response::from(
+        Batch::default().into_response(emitter),
+        state,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use currency::native::Nls;
+    use finance::{coin::Coin, currency::Currency as _};
+    use sdk::cosmwasm_std::{coin, testing::MockQuerier, Addr, QuerierWrapper};
+
+    use crate::{api::dex::RelayingParams, error::ContractError};
+
+    const LEASE: &str = "lease";
+
+    fn relaying(fee_reserve: Coin<Nls>) -> RelayingParams {
+        RelayingParams {
+            fee_reserve,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn check_covered() {
+        let querier = MockQuerier::new(&[(LEASE, &[coin(1000, Nls::BANK_SYMBOL)])]);
+        let querier = QuerierWrapper::new(&querier);
+        let lease = Addr::unchecked(LEASE);
+
+        assert_eq!(
+            Ok(()),
+            super::check(&lease, &relaying(Coin::new(1000)), &querier)
+        );
+        assert_eq!(
+            Ok(()),
+            super::check(&lease, &relaying(Coin::default()), &querier)
+        );
+    }
+
+    #[test]
+    fn check_short() {
+        let querier = MockQuerier::new(&[(LEASE, &[coin(999, Nls::BANK_SYMBOL)])]);
+        let querier = QuerierWrapper::new(&querier);
+
+        assert_eq!(
+            Err(ContractError::InsufficientFeeReserve {
+                reserve: Coin::new(999),
+                required: Coin::new(1000),
+            }),
+            super::check(
+                &Addr::unchecked(LEASE),
+                &relaying(Coin::new(1000)),
+                &querier
+            )
+        );
+    }
+}
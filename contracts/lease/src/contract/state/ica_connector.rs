@@ -61,6 +61,10 @@ where
         Account::register_request(self.connectee.dex())
     }
 
+    pub(super) fn into_connectee(self) -> Connectee {
+        self.connectee
+    }
+
     fn build_account(&self, counterparty_version: String, env: &Env) -> ContractResult<Account> {
         let contract = env.contract.address.clone();
         Account::from_register_response(
@@ -0,0 +1,125 @@
+use finance::duration::Duration;
+use sdk::{
+    cosmwasm_std::{StdResult, Storage, Timestamp},
+    cw_storage_plus::Item,
+};
+
+use crate::api::{IcaError, StateResponse};
+
+const LAST_ERROR_DB_ITEM: Item<'static, IcaError> = Item::new("last_ica_error");
+
+const RETRY_DELAY_MIN: Duration = Duration::from_secs(30);
+const RETRY_DELAY_MAX: Duration = Duration::HOUR;
+
+pub(super) fn record(storage: &mut dyn Storage, details: String, now: Timestamp) -> StdResult<()> {
+    let count = LAST_ERROR_DB_ITEM
+        .may_load(storage)?
+        .map_or(1, |last| last.count.saturating_add(1));
+    LAST_ERROR_DB_ITEM.save(
+        storage,
+        &IcaError {
+            details,
+            at: now,
+            count,
+        },
+    )
+}
+
+pub(super) fn clear(storage: &mut dyn Storage) {
+    LAST_ERROR_DB_ITEM.remove(storage)
+}
+
+/// The time to wait before retrying a failed transaction
+///
+/// It doubles with each consecutive error starting from `RETRY_DELAY_MIN`
+/// up to `RETRY_DELAY_MAX`.
+pub(super) fn retry_delay(storage: &dyn Storage) -> StdResult<Duration> {
+    let count = LAST_ERROR_DB_ITEM
+        .may_load(storage)?
+        .map_or(1, |last| last.count);
+    Ok(delay(count))
+}
+
+pub(super) fn attach(storage: &dyn Storage, state: StateResponse) -> StdResult<StateResponse> {
+    let error = LAST_ERROR_DB_ITEM.may_load(storage)?;
+    Ok(match state {
+        StateResponse::Opening {
+            downpayment,
+            loan,
+            loan_interest_rate,
+            in_progress,
+            last_error: _,
+        } => StateResponse::Opening {
+            downpayment,
+            loan,
+            loan_interest_rate,
+            in_progress,
+            last_error: error,
+        },
+        StateResponse::Opened {
+            amount,
+            loan_interest_rate,
+            margin_interest_rate,
+            principal_due,
+            previous_margin_due,
+            previous_interest_due,
+            late_payment_penalty_due,
+            current_margin_due,
+            current_interest_due,
+            ltv,
+            liquidation_prices,
+            validity,
+            in_progress,
+            last_error: _,
+        } => StateResponse::Opened {
+            amount,
+            loan_interest_rate,
+            margin_interest_rate,
+            principal_due,
+            previous_margin_due,
+            previous_interest_due,
+            late_payment_penalty_due,
+            current_margin_due,
+            current_interest_due,
+            ltv,
+            liquidation_prices,
+            validity,
+            in_progress,
+            last_error: error,
+        },
+        StateResponse::Paid {
+            amount,
+            in_progress,
+            last_error: _,
+        } => StateResponse::Paid {
+            amount,
+            in_progress,
+            last_error: error,
+        },
+        StateResponse::Closed() => StateResponse::Closed(),
+    })
+}
+
+fn delay(count: u16) -> Duration {
+    debug_assert!(count > 0);
+    1u16.checked_shl(count.saturating_sub(1).into())
+        .and_then(|factor| RETRY_DELAY_MIN.checked_mul(factor))
+        .map_or(RETRY_DELAY_MAX, |delay| delay.min(RETRY_DELAY_MAX))
+}
+
+#[cfg(test)]
+mod test {
+    use finance::duration::Duration;
+
+    use super::{RETRY_DELAY_MAX, RETRY_DELAY_MIN};
+
+    #[test]
+    fn delay() {
+        assert_eq!(RETRY_DELAY_MIN, super::delay(1));
+        assert_eq!(Duration::from_secs(60), super::delay(2));
+        assert_eq!(Duration::from_secs(240), super::delay(4));
+        assert_eq!(RETRY_DELAY_MAX, super::delay(8));
+        assert_eq!(RETRY_DELAY_MAX, super::delay(17));
+        assert_eq!(RETRY_DELAY_MAX, super::delay(u16::MAX));
+    }
+}
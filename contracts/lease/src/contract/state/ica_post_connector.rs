@@ -38,13 +38,25 @@ where
         }
     }
 
+    pub(super) fn into_connectee(self) -> Connectee {
+        self.connectee
+    }
+
     pub(super) fn enter(
         &self,
         now: Timestamp,
         querier: &QuerierWrapper<'_>,
     ) -> ContractResult<Batch> {
-        self.connectee
-            .setup_alarm(now + Self::RIGHT_AFTER_NOW, querier)
+        self.enter_after(Self::RIGHT_AFTER_NOW, now, querier)
+    }
+
+    pub(super) fn enter_after(
+        &self,
+        delay: Duration,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Batch> {
+        self.connectee.setup_alarm(now + delay, querier)
     }
 }
 
@@ -62,7 +74,19 @@ where
     ) -> ContractResult<Response> {
         match msg {
             ExecuteMsg::Repay() => controller::err("repay", deps.api),
-            ExecuteMsg::Close() => controller::err("close", deps.api),
+            ExecuteMsg::Close { .. } => controller::err("close", deps.api),
+            ExecuteMsg::AddCollateral() => controller::err("add collateral", deps.api),
+            ExecuteMsg::Cancel() => controller::err("cancel", deps.api),
+            ExecuteMsg::BorrowMore { .. } => controller::err("borrow more", deps.api),
+            ExecuteMsg::ClosePosition { .. } => controller::err("close position", deps.api),
+            ExecuteMsg::ChangeClosePolicy { .. } => {
+                controller::err("change close policy", deps.api)
+            }
+            ExecuteMsg::GrantOperator { .. } => controller::err("grant operator", deps.api),
+            ExecuteMsg::RevokeOperator { .. } => controller::err("revoke operator", deps.api),
+            ExecuteMsg::TransferOwnership { .. } => controller::err("transfer ownership", deps.api),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
             ExecuteMsg::PriceAlarm() => super::ignore_msg(self),
             ExecuteMsg::TimeAlarm {} => {
                 let next_state = self.connectee.connected(self.ica_account);
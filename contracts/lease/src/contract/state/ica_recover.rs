@@ -26,6 +26,10 @@ impl<S> InRecovery<S> {
     pub(super) fn new(state: S) -> Self {
         Self { state }
     }
+
+    pub(super) fn into_state(self) -> S {
+        self.state
+    }
 }
 
 impl<S> IcaConnectee for InRecovery<S>
@@ -0,0 +1,106 @@
+use sdk::{
+    cosmwasm_std::{Order, StdError, StdResult, Storage, Timestamp},
+    cw_storage_plus::{Bound, Map},
+};
+
+use crate::api::{LedgerEntry, LedgerRecord};
+
+const LEDGER_DB: Map<'static, u32, LedgerEntry> = Map::new("ledger");
+
+const DEFAULT_PAGE_LIMIT: u32 = 10;
+const MAX_PAGE_LIMIT: u32 = 30;
+
+/// Append the records to the ledger in the given order
+pub(super) fn append<Records>(
+    storage: &mut dyn Storage,
+    records: Records,
+    now: Timestamp,
+) -> StdResult<()>
+where
+    Records: IntoIterator<Item = LedgerRecord>,
+{
+    let mut next_id = LEDGER_DB
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last_id| last_id + 1);
+
+    records.into_iter().try_for_each(|record| {
+        let id = next_id;
+        next_id += 1;
+        LEDGER_DB.save(
+            storage,
+            id,
+            &LedgerEntry {
+                id,
+                at: now,
+                record,
+            },
+        )
+    })
+}
+
+/// Load a page of entries in ascending order of their ids
+pub(super) fn page(
+    storage: &dyn Storage,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<Vec<LedgerEntry>> {
+    let limit = usize::try_from(limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT))
+        .map_err(|err| StdError::generic_err(format!("Invalid page limit: {}", err)))?;
+
+    LEDGER_DB
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|entry| entry.map(|(_id, entry)| entry))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use currency::lease::Atom;
+    use finance::coin::Coin;
+    use sdk::cosmwasm_std::{testing::MockStorage, Timestamp};
+
+    use crate::api::LedgerRecord;
+
+    fn close(amount: u128) -> LedgerRecord {
+        LedgerRecord::Close {
+            amount: Coin::<Atom>::new(amount).into(),
+        }
+    }
+
+    #[test]
+    fn append_and_page() {
+        let mut storage = MockStorage::new();
+        assert_eq!(Ok(vec![]), super::page(&storage, None, None));
+
+        super::append(
+            &mut storage,
+            [close(1), close(2)],
+            Timestamp::from_seconds(10),
+        )
+        .unwrap();
+        super::append(&mut storage, [close(3)], Timestamp::from_seconds(20)).unwrap();
+
+        let all = super::page(&storage, None, None).unwrap();
+        assert_eq!(
+            vec![0, 1, 2],
+            all.iter().map(|entry| entry.id).collect::<Vec<_>>()
+        );
+        assert_eq!(Timestamp::from_seconds(20), all[2].at);
+        assert_eq!(close(3), all[2].record);
+
+        let page = super::page(&storage, Some(0), Some(1)).unwrap();
+        assert_eq!(1, page.len());
+        assert_eq!(1, page[0].id);
+        assert_eq!(close(2), page[0].record);
+
+        assert_eq!(Ok(vec![]), super::page(&storage, Some(2), None));
+    }
+}
@@ -10,27 +10,36 @@ use sdk::{
     cw_storage_plus::Item,
 };
 
-use crate::{api::ExecuteMsg, error::ContractResult};
+use crate::{
+    api::{dex::RelayingParams, ExecuteMsg, LedgerRecord},
+    error::ContractResult,
+};
 
 use self::{
     closed::Closed,
     controller::Controller,
     ica_connector::{Enterable, IcaConnectee, IcaConnector},
+    ica_post_connector::{PostConnector, Postpone},
     ica_recover::InRecovery,
-    opened::repay::buy_lpn::BuyLpn,
+    opened::{borrow_more::BorrowMore, repay::buy_lpn::BuyLpn},
     opening::request_loan::RequestLoan,
 };
 pub use controller::{execute, instantiate, migrate, query, reply, sudo};
 
-use super::dex::DexConnectable;
+use super::dex::{Account, DexConnectable};
 
 mod closed;
 mod controller;
+mod fee_reserve;
 mod ica_connector;
+mod ica_error;
 mod ica_post_connector;
 mod ica_recover;
+mod ledger;
 mod opened;
 mod opening;
+mod operators;
+mod ownership;
 mod paid;
 mod transfer_in;
 
@@ -47,6 +56,19 @@ type BuyAssetRecoverIca = ica_connector::IcaConnector<
 >;
 type BuyAssetPostRecoverIca = ica_post_connector::PostConnector<ica_recover::InRecovery<BuyAsset>>;
 
+type CancelAwaitTransferOut = opening::cancel::in_flight::AwaitTransferOut;
+type CancelAwaitSwap = opening::cancel::in_flight::AwaitSwap;
+
+type CancelTransferInInit = opening::cancel::transfer_in_init::TransferInInit;
+type CancelTransferInInitRecoverIca = ica_connector::IcaConnector<
+    { ica_recover::InRecovery::<CancelTransferInInit>::PRECONNECTABLE },
+    ica_recover::InRecovery<CancelTransferInInit>,
+>;
+type CancelTransferInInitPostRecoverIca =
+    ica_post_connector::PostConnector<ica_recover::InRecovery<CancelTransferInInit>>;
+
+type CancelTransferInFinish = opening::cancel::transfer_in_finish::TransferInFinish;
+
 type OpenedActive = opened::active::Active;
 
 type RepaymentTransferOut = opened::repay::transfer_out::TransferOut;
@@ -67,6 +89,34 @@ type RepaymentTransferInInitPostRecoverIca =
 
 type RepaymentTransferInFinish = opened::repay::transfer_in_finish::TransferInFinish;
 
+type AddCollateralTransferOut = opened::add_collateral::Transfer;
+
+type AddCollateralSwap = opened::add_collateral::Swap;
+type AddCollateralSwapRecoverIca = ica_connector::IcaConnector<
+    { ica_recover::InRecovery::<AddCollateralSwap>::PRECONNECTABLE },
+    ica_recover::InRecovery<AddCollateralSwap>,
+>;
+type AddCollateralSwapPostRecoverIca =
+    ica_post_connector::PostConnector<ica_recover::InRecovery<AddCollateralSwap>>;
+
+type ClosePositionSellAsset = opened::close::sell_asset::SellAsset;
+type ClosePositionSellAssetRecoverIca = ica_connector::IcaConnector<
+    { ica_recover::InRecovery::<ClosePositionSellAsset>::PRECONNECTABLE },
+    ica_recover::InRecovery<ClosePositionSellAsset>,
+>;
+type ClosePositionSellAssetPostRecoverIca =
+    ica_post_connector::PostConnector<ica_recover::InRecovery<ClosePositionSellAsset>>;
+
+type ClosePositionTransferInInit = opened::close::transfer_in_init::TransferInInit;
+type ClosePositionTransferInInitRecoverIca = ica_connector::IcaConnector<
+    { ica_recover::InRecovery::<ClosePositionTransferInInit>::PRECONNECTABLE },
+    ica_recover::InRecovery<ClosePositionTransferInInit>,
+>;
+type ClosePositionTransferInInitPostRecoverIca =
+    ica_post_connector::PostConnector<ica_recover::InRecovery<ClosePositionTransferInInit>>;
+
+type ClosePositionTransferInFinish = opened::close::transfer_in_finish::TransferInFinish;
+
 type PaidActive = paid::Active;
 
 type ClosingTransferInInit = paid::transfer_in_init::TransferInInit;
@@ -79,6 +129,14 @@ type ClosingTransferInInitPostRecoverIca =
 
 type ClosingTransferInFinish = paid::transfer_in_finish::TransferInFinish;
 
+type ClosingDexSend = paid::dex_send::DexSend;
+type ClosingDexSendRecoverIca = ica_connector::IcaConnector<
+    { ica_recover::InRecovery::<ClosingDexSend>::PRECONNECTABLE },
+    ica_recover::InRecovery<ClosingDexSend>,
+>;
+type ClosingDexSendPostRecoverIca =
+    ica_post_connector::PostConnector<ica_recover::InRecovery<ClosingDexSend>>;
+
 #[enum_dispatch(Controller, Contract)]
 #[derive(Serialize, Deserialize)]
 pub(crate) enum State {
@@ -88,6 +146,12 @@ pub(crate) enum State {
     BuyAsset,
     BuyAssetRecoverIca,
     BuyAssetPostRecoverIca,
+    CancelAwaitTransferOut,
+    CancelAwaitSwap,
+    CancelTransferInInit,
+    CancelTransferInInitRecoverIca,
+    CancelTransferInInitPostRecoverIca,
+    CancelTransferInFinish,
     OpenedActive,
     RepaymentTransferOut,
     BuyLpn,
@@ -97,15 +161,47 @@ pub(crate) enum State {
     RepaymentTransferInInitRecoverIca,
     RepaymentTransferInInitPostRecoverIca,
     RepaymentTransferInFinish,
+    BorrowMore,
+    AddCollateralTransferOut,
+    AddCollateralSwap,
+    AddCollateralSwapRecoverIca,
+    AddCollateralSwapPostRecoverIca,
+    ClosePositionSellAsset,
+    ClosePositionSellAssetRecoverIca,
+    ClosePositionSellAssetPostRecoverIca,
+    ClosePositionTransferInInit,
+    ClosePositionTransferInInitRecoverIca,
+    ClosePositionTransferInInitPostRecoverIca,
+    ClosePositionTransferInFinish,
     PaidActive,
     ClosingTransferInInit,
     ClosingTransferInInitRecoverIca,
     ClosingTransferInInitPostRecoverIca,
     ClosingTransferInFinish,
+    ClosingDexSend,
+    ClosingDexSendRecoverIca,
+    ClosingDexSendPostRecoverIca,
     Closed,
 }
 
+impl State {
+    /// Apply the DEX relaying parameters unless the lease is in the middle of a DEX interaction
+    ///
+    /// Return whether the lease is done with them.
+    fn try_update_dex_relaying(&mut self, relaying: &RelayingParams) -> bool {
+        match self {
+            Self::OpenedActive(active) => active.update_dex_relaying(relaying.clone()),
+            Self::PaidActive(active) => active.update_dex_relaying(relaying.clone()),
+            Self::Closed(_) => {}
+            _ => return false,
+        }
+        true
+    }
+}
+
 const STATE_DB_ITEM: Item<'static, State> = Item::new("state");
+const PENDING_DEX_RELAYING_DB_ITEM: Item<'static, RelayingParams> =
+    Item::new("pending_dex_relaying");
 
 pub(super) fn load(storage: &dyn Storage) -> StdResult<State> {
     STATE_DB_ITEM.load(storage)
@@ -115,9 +211,33 @@ pub(super) fn save(storage: &mut dyn Storage, next_state: &State) -> StdResult<(
     STATE_DB_ITEM.save(storage, next_state)
 }
 
+/// Update the DEX relaying parameters of the lease on saving its next state
+///
+/// A lease in the middle of a DEX interaction applies them once it gets back to an active state.
+pub(super) fn update_dex_relaying(
+    storage: &mut dyn Storage,
+    relaying: &RelayingParams,
+) -> StdResult<()> {
+    PENDING_DEX_RELAYING_DB_ITEM.save(storage, relaying)
+}
+
+/// Save the next state applying the pending DEX relaying parameters, if any and possible
+pub(super) fn save_applying_dex_relaying(
+    storage: &mut dyn Storage,
+    mut next_state: State,
+) -> StdResult<()> {
+    if let Some(relaying) = PENDING_DEX_RELAYING_DB_ITEM.may_load(storage)? {
+        if next_state.try_update_dex_relaying(&relaying) {
+            PENDING_DEX_RELAYING_DB_ITEM.remove(storage);
+        }
+    }
+    save(storage, &next_state)
+}
+
 pub(crate) struct Response {
     pub(super) cw_response: CwResponse,
     pub(super) next_state: State,
+    pub(super) ledger: Vec<LedgerRecord>,
 }
 
 impl Response {
@@ -129,8 +249,15 @@ impl Response {
         Self {
             cw_response: resp.into(),
             next_state: next_state.into(),
+            ledger: vec![],
         }
     }
+
+    /// Append a record to the lease ledger along with saving the next state
+    pub fn record(mut self, record: LedgerRecord) -> Self {
+        self.ledger.push(record);
+        self
+    }
 }
 
 fn on_timeout_retry<S, L>(
@@ -172,6 +299,53 @@ where
     Ok(Response::from(batch.into_response(emitter), recover_ica))
 }
 
+/// Re-enter the current state on an error response from the DEX
+///
+/// Intended for states whose remote transactions fail atomically leaving
+/// the funds intact, for example, swaps rejected due to a slippage.
+fn on_error_retry<S, L>(
+    current_state: S,
+    state_label: L,
+    deps: Deps<'_>,
+    env: Env,
+) -> ContractResult<Response>
+where
+    S: Enterable + Into<State>,
+    L: Into<String>,
+{
+    let emitter = Emitter::of_type(state_label)
+        .emit("id", env.contract.address.clone())
+        .emit("error", "Retry");
+    let batch = current_state.enter(deps, env)?;
+    Ok(Response::from(batch.into_response(emitter), current_state))
+}
+
+/// Re-enter the current state on an error response from the DEX after a back-off delay
+///
+/// Intended for states whose remote transactions fail atomically leaving the funds intact
+/// and which are likely to fail again if retried right away, for example, swaps rejected
+/// due to a slippage. The delay grows with the number of consecutive errors.
+fn on_error_retry_later<S, L>(
+    current_state: S,
+    dex_account: Account,
+    state_label: L,
+    deps: Deps<'_>,
+    env: Env,
+) -> ContractResult<Response>
+where
+    S: Enterable + Postpone + Into<State>,
+    PostConnector<InRecovery<S>>: Into<State>,
+    L: Into<String>,
+{
+    let delay = ica_error::retry_delay(deps.storage)?;
+    let emitter = Emitter::of_type(state_label)
+        .emit("id", env.contract.address.clone())
+        .emit("error", "RetryLater");
+    let retry = PostConnector::new(InRecovery::new(current_state), dex_account);
+    let batch = retry.enter_after(delay, env.block.time, &deps.querier)?;
+    Ok(Response::from(batch.into_response(emitter), retry))
+}
+
 #[derive(Debug)]
 enum TimeoutPolicy {
     Retry,
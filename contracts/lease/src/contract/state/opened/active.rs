@@ -1,31 +1,46 @@
 use cosmwasm_std::Timestamp;
 use serde::{Deserialize, Serialize};
 
-use currency::{lpn::Lpns, payment::PaymentGroup};
+use currency::payment::PaymentGroup;
 use finance::coin::IntoDTO;
 use platform::{
     bank::{self},
-    batch::{Emit, Emitter},
+    batch::{Batch, Emit, Emitter},
 };
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
-    cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, QuerierWrapper},
+    cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, QuerierWrapper},
 };
 
 use crate::{
-    api::{DownpaymentCoin, ExecuteMsg, LpnCoin, StateResponse},
+    api::{
+        dex::RelayingParams, DownpaymentCoin, ExecuteMsg, LeaseCoin, LeasePrice, LedgerRecord,
+        LiquidationQuoteResponse, LpnCoin, PaymentCoin, RepayQuoteResponse, StateResponse,
+    },
     contract::{
-        cmd::{AlarmResult, OpenLoanRespResult, PriceAlarm, Repay, RepayResult, TimeAlarm},
-        dex::Account,
-        state::{controller, paid, Controller, Response},
+        cmd::{
+            self, AlarmResult, BorrowMore as BorrowMoreCmd, BorrowMoreResult, ChangeClosePolicy,
+            ClosePolicyCheck, ClosePosition, FullCloseAmount, FullSaleRepays, LiquidationQuote,
+            OpenLoanRespResult, PriceAlarm, Repay, RepayQuote, RepayResult, TimeAlarm,
+        },
+        dex::DexConnectable,
+        state::{
+            closed::Closed, controller, fee_reserve, ica_connector::Enterable, operators,
+            ownership, paid, Controller, Response,
+        },
         Contract, Lease,
     },
     error::{ContractError, ContractResult},
     event::Type,
-    lease::{with_lease, LeaseDTO},
+    lease::{with_lease, ClosePolicy, IntoDTOResult, LeaseDTO},
 };
 
-use super::repay::transfer_out::TransferOut;
+use super::{
+    add_collateral::{self, AddCollateral},
+    borrow_more::BorrowMore,
+    close::sell_asset::SellAsset,
+    repay::{self, transfer_out::TransferOut},
+};
 
 #[derive(Serialize, Deserialize)]
 pub struct Active {
@@ -37,13 +52,18 @@ impl Active {
         Self { lease }
     }
 
+    pub(in crate::contract::state) fn update_dex_relaying(&mut self, relaying: RelayingParams) {
+        self.lease.update_dex_relaying(relaying)
+    }
+
     pub(in crate::contract::state) fn emit_ok(
         &self,
         env: &Env,
         downpayment: DownpaymentCoin,
+        swap_fee: LpnCoin,
         loan: OpenLoanRespResult,
     ) -> Emitter {
-        build_emitter(env, &self.lease.lease, loan, downpayment)
+        build_emitter(env, &self.lease.lease, loan, downpayment, swap_fee)
     }
 
     pub(in crate::contract::state::opened) fn try_repay_lpn(
@@ -51,6 +71,47 @@ impl Active {
         payment: LpnCoin,
         querier: &QuerierWrapper<'_>,
         env: &Env,
+    ) -> ContractResult<Response> {
+        Self::repay(lease, Repay::new(payment, env), Batch::default(), querier)
+    }
+
+    /// Repay with LPN obtained by swapping non-LPN payments, and with the LPN paid, if any
+    ///
+    /// The swap fee is charged on the swapped amount. The unswapped remainder
+    /// of a payment, if any, is returned to the customer.
+    pub(in crate::contract::state::opened) fn try_repay_swapped_lpn(
+        lease: Lease,
+        swapped: LpnCoin,
+        paid: Option<LpnCoin>,
+        unswapped: Option<&PaymentCoin>,
+        querier: &QuerierWrapper<'_>,
+        env: &Env,
+    ) -> ContractResult<Response> {
+        let refund = unswapped.map_or_else(
+            || Ok(Batch::default()),
+            |unswapped| {
+                cmd::send(
+                    unswapped,
+                    &env.contract.address,
+                    &lease.lease.customer,
+                    querier,
+                )
+            },
+        )?;
+        let swap_fee = lease.swap_fee;
+        Self::repay(
+            lease,
+            Repay::swapped(swapped, paid, swap_fee, env),
+            refund,
+            querier,
+        )
+    }
+
+    fn repay(
+        lease: Lease,
+        cmd: Repay<'_>,
+        refund: Batch,
+        querier: &QuerierWrapper<'_>,
     ) -> ContractResult<Response> {
         // TODO Move RepayResult into this layer, rename to, for example, ExecuteResult
         // and refactor try_* to return it
@@ -59,38 +120,262 @@ impl Active {
             paid,
             batch,
             emitter,
-        } = with_lease::execute(lease.lease, Repay::new(payment, env), querier)?;
+            record,
+        } = with_lease::execute(lease.lease, cmd, querier)?;
 
-        let cw_resp = batch.into_response(emitter);
-        let new_lease = Lease {
+        Ok(into_repaid(
+            Lease {
+                lease: lease_updated,
+                ..lease
+            },
+            paid,
+            batch.merge(refund).into_response(emitter),
+        )
+        .record(record))
+    }
+
+    pub(in crate::contract::state::opened) fn try_close_position_lpn(
+        lease: Lease,
+        close: LeaseCoin,
+        full_close: bool,
+        payment: LpnCoin,
+        querier: &QuerierWrapper<'_>,
+        env: &Env,
+    ) -> ContractResult<Response> {
+        let lease_account = bank::account(&env.contract.address, querier);
+        let RepayResult {
             lease: lease_updated,
-            dex: lease.dex,
+            paid,
+            batch,
+            emitter,
+            record,
+        } = with_lease::execute(
+            lease.lease,
+            ClosePosition::new(close, payment, env, lease_account),
+            querier,
+        )?;
+
+        let lease = Lease {
+            lease: lease_updated,
+            ..lease
         };
-        let resp = if paid {
-            Response::from(cw_resp, paid::Active::new(new_lease))
+        if paid && lease.lease.amount.is_zero() {
+            let closed = Closed::default();
+            let cw_resp = batch
+                .into_response(emitter)
+                .add_event(closed.emit_ok(env, &lease.lease).into());
+            let close_record = closed.record(&lease.lease);
+            Ok(Response::from(cw_resp, closed)
+                .record(record)
+                .record(close_record))
+        } else if paid && full_close {
+            let transfer_in = paid::transfer_in_init::TransferInInit::new(lease);
+            let batch = batch.merge(transfer_in.enter(env.block.time)?);
+            Ok(Response::from(batch.into_response(emitter), transfer_in).record(record))
+        } else if full_close {
+            // the proceeds have not covered the debt, for example, due to the interest
+            // accrued during the sale, so sell for the remainder rather than stay open
+            let close = full_close_amount(&lease, querier, env)?;
+            if close == lease.lease.amount && !full_sale_repays(&lease, querier, env)? {
+                // after a price drop, selling what is left would not repay the loan,
+                // so the lease stays open with the part sold so far repaid
+                let stop = Emitter::of_type(Type::PositionCloseStop)
+                    .emit_tx_info(env)
+                    .emit("id", &lease.lease.addr);
+                let cw_resp = batch.into_response(emitter).add_event(stop.into());
+                return Ok(into_repaid(lease, paid, cw_resp).record(record));
+            }
+            let retry = Emitter::of_type(Type::PositionCloseRetry)
+                .emit_tx_info(env)
+                .emit("id", &lease.lease.addr)
+                .emit_coin_dto("close", close.clone());
+
+            let sell_asset = SellAsset::new(lease, close, true, querier)?;
+            let batch = batch.merge(sell_asset.enter(querier)?);
+            let cw_resp = batch.into_response(emitter).add_event(retry.into());
+            Ok(Response::from(cw_resp, sell_asset).record(record))
         } else {
-            Response::from(cw_resp, Active::new(new_lease))
-        };
-        Ok(resp)
+            Ok(into_repaid(lease, paid, batch.into_response(emitter)).record(record))
+        }
+    }
+
+    fn check_fee_reserve(&self, querier: &QuerierWrapper<'_>) -> ContractResult<()> {
+        fee_reserve::check(&self.lease.lease.addr, &self.lease.dex().relaying, querier)
     }
 
     fn try_repay(self, deps: Deps<'_>, env: Env, info: MessageInfo) -> ContractResult<Response> {
-        let payment = bank::may_received::<PaymentGroup, _>(
-            info.funds.clone(),
-            IntoDTO::<PaymentGroup>::new(),
-        )
-        .ok_or_else(ContractError::NoPaymentError)??;
-        if payment.ticker() == self.lease.lease.loan.lpp().currency() {
-            // TODO once refacture CoinDTO and Group convert to LpnCoin instead
-            let payment_lpn = bank::may_received::<Lpns, _>(info.funds, IntoDTO::<Lpns>::new())
+        let payments =
+            bank::received_all::<PaymentGroup, _>(info.funds, IntoDTO::<PaymentGroup>::new())?
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()?;
+        if payments.is_empty() {
+            return Err(ContractError::NoPaymentError());
+        }
+
+        let lpn = self.lease.lease.loan.lpp().currency();
+        match repay::paid_lpn(&payments, lpn)? {
+            Some(payment_lpn) if repay::swapped(&payments, lpn).next().is_none() => {
+                Self::try_repay_lpn(self.lease, payment_lpn, &deps.querier, &env)
+            }
+            _ => {
+                self.check_fee_reserve(&deps.querier)?;
+                let transfer_out = TransferOut::new(self.lease, payments);
+                let batch = transfer_out.enter(env.block.time)?;
+                Ok(Response::from(batch, transfer_out))
+            }
+        }
+    }
+
+    fn try_add_collateral(
+        self,
+        deps: Deps<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        let payment =
+            bank::may_received::<PaymentGroup, _>(info.funds, IntoDTO::<PaymentGroup>::new())
                 .ok_or_else(ContractError::NoPaymentError)??;
+        self.check_fee_reserve(&deps.querier)?;
+
+        let transfer_out = add_collateral::Transfer::new(AddCollateral::new(self.lease, payment));
+        let batch = transfer_out.enter(deps, env)?;
+        Ok(Response::from(batch, transfer_out))
+    }
+
+    fn try_borrow_more(
+        self,
+        deps: Deps<'_>,
+        env: &Env,
+        info: MessageInfo,
+        amount: LpnCoin,
+    ) -> ContractResult<Response> {
+        operators::check_access(deps.storage, &self.lease.lease.customer, &info.sender)?;
+        let querier = &deps.querier;
+        self.check_fee_reserve(querier)?;
+
+        let BorrowMoreResult {
+            lease: lease_updated,
+            batch,
+            collateral,
+        } = with_lease::execute(
+            self.lease.lease,
+            BorrowMoreCmd::new(amount, env.block.time),
+            querier,
+        )?;
+
+        let borrow_more = BorrowMore::new(
+            Lease {
+                lease: lease_updated,
+                ..self.lease
+            },
+            collateral,
+        );
+        Ok(Response::from(batch, borrow_more))
+    }
+
+    fn try_close_position(
+        self,
+        deps: &DepsMut<'_>,
+        env: &Env,
+        info: MessageInfo,
+        amount: Option<LeaseCoin>,
+        payout_lpn: bool,
+    ) -> ContractResult<Response> {
+        operators::check_access(deps.storage, &self.lease.lease.customer, &info.sender)?;
+
+        let lease_amount = &self.lease.lease.amount;
+        let (close, full_close) = match amount {
+            Some(close) => (close, false),
+            None if payout_lpn => (lease_amount.clone(), true),
+            None => (full_close_amount(&self.lease, &deps.querier, env)?, true),
+        };
+        if close.ticker() != lease_amount.ticker()
+            || close.is_zero()
+            || lease_amount.amount() < close.amount()
+        {
+            return Err(ContractError::InvalidPositionCloseAmount(close));
+        }
+        if close.amount() == lease_amount.amount()
+            && !full_sale_repays(&self.lease, &deps.querier, env)?
+        {
+            return Err(ContractError::InsufficientPositionValue());
+        }
+        self.check_fee_reserve(&deps.querier)?;
+
+        let sell_asset = SellAsset::new(self.lease, close, full_close, &deps.querier)?;
+        let batch = sell_asset.enter(&deps.querier)?;
+        Ok(Response::from(batch, sell_asset))
+    }
+
+    fn try_change_close_policy(
+        self,
+        deps: Deps<'_>,
+        env: &Env,
+        info: MessageInfo,
+        policy: ClosePolicy,
+    ) -> ContractResult<Response> {
+        operators::check_access(deps.storage, &self.lease.lease.customer, &info.sender)?;
 
-            Self::try_repay_lpn(self.lease, payment_lpn, &deps.querier, &env)
+        let IntoDTOResult {
+            lease: lease_updated,
+            batch,
+        } = with_lease::execute(
+            self.lease.lease,
+            ChangeClosePolicy::new(policy, env.block.time),
+            &deps.querier,
+        )?;
+
+        let emitter = Emitter::of_type(Type::ClosePolicyChange)
+            .emit_tx_info(env)
+            .emit("id", &lease_updated.addr);
+        Ok(into_updated_active(
+            Lease {
+                lease: lease_updated,
+                ..self.lease
+            },
+            batch.into_response(emitter),
+        ))
+    }
+
+    fn try_change_operator(
+        self,
+        deps: &mut DepsMut<'_>,
+        env: &Env,
+        info: MessageInfo,
+        operator: Addr,
+        grant: bool,
+    ) -> ContractResult<Response> {
+        if info.sender != self.lease.lease.customer {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let operator = deps.api.addr_validate(operator.as_str())?;
+        let emitter = if grant {
+            operators::grant(deps.storage, &operator)?;
+            Emitter::of_type(Type::OperatorGrant)
         } else {
-            let transfer_out = TransferOut::new(self.lease, payment);
-            let batch = transfer_out.enter(env.block.time)?;
-            Ok(Response::from(batch, transfer_out))
+            operators::revoke(deps.storage, &operator);
+            Emitter::of_type(Type::OperatorRevoke)
         }
+        .emit_tx_info(env)
+        .emit("id", &self.lease.lease.addr)
+        .emit("operator", operator);
+        Ok(into_updated_active(
+            self.lease,
+            Batch::default().into_response(emitter),
+        ))
+    }
+
+    fn try_transfer_ownership(
+        mut self,
+        deps: &mut DepsMut<'_>,
+        env: &Env,
+        info: MessageInfo,
+        new_customer: Addr,
+    ) -> ContractResult<Response> {
+        let resp =
+            ownership::transfer(&mut self.lease.lease, deps, env, &info.sender, new_customer)?;
+        Ok(Response::from(resp, self))
     }
 
     fn try_on_price_alarm(
@@ -99,15 +384,55 @@ impl Active {
         env: &Env,
         info: MessageInfo,
     ) -> ContractResult<Response> {
+        let trigger = with_lease::execute(
+            self.lease.lease.clone(),
+            ClosePolicyCheck::new(&info.sender),
+            querier,
+        )?;
+        let mut price_alarm = PriceAlarm::new(env, &info.sender, env.block.time);
+        let mut cancel = None;
+        if let Some(trigger) = trigger {
+            if full_sale_repays(&self.lease, querier, env)? {
+                let close = self.lease.lease.amount.clone();
+                let emitter = Emitter::of_type(Type::ClosePolicyTrigger)
+                    .emit_tx_info(env)
+                    .emit("id", &self.lease.lease.addr)
+                    .emit_to_string_value("trigger", trigger.to_uint())
+                    .emit_coin_dto("close", close.clone());
+
+                let sell_asset = SellAsset::new(self.lease, close, true, querier)?;
+                let batch = sell_asset.enter(querier)?;
+                return Ok(Response::from(batch.into_response(emitter), sell_asset));
+            }
+
+            // a lease whose sale would not repay the loan is left to the liquidation checks,
+            // and the crossed policy price is cancelled so that it is not set as an alarm again
+            price_alarm = price_alarm.cancel_trigger(trigger);
+            cancel = Some(
+                Emitter::of_type(Type::ClosePolicyCancel)
+                    .emit_tx_info(env)
+                    .emit("id", &self.lease.lease.addr)
+                    .emit_to_string_value("trigger", trigger.to_uint()),
+            );
+        }
+
         let AlarmResult {
             response,
             lease_dto: lease_updated,
-        } = with_lease::execute(
-            self.lease.lease,
-            PriceAlarm::new(env, &info.sender, env.block.time),
-            querier,
-        )?;
-        Ok(into_updated_active(lease_updated, self.lease.dex, response))
+            record,
+        } = with_lease::execute(self.lease.lease, price_alarm, querier)?;
+        let response = match cancel {
+            Some(cancel) => response.add_event(cancel.into()),
+            None => response,
+        };
+        Ok(into_liquidated(
+            Lease {
+                lease: lease_updated,
+                ..self.lease
+            },
+            response,
+            record,
+        ))
     }
 
     fn try_on_time_alarm(
@@ -119,12 +444,20 @@ impl Active {
         let AlarmResult {
             response,
             lease_dto: lease_updated,
+            record,
         } = with_lease::execute(
             self.lease.lease,
             TimeAlarm::new(env, &info.sender, env.block.time),
             querier,
         )?;
-        Ok(into_updated_active(lease_updated, self.lease.dex, response))
+        Ok(into_liquidated(
+            Lease {
+                lease: lease_updated,
+                ..self.lease
+            },
+            response,
+            record,
+        ))
     }
 }
 
@@ -138,9 +471,37 @@ impl Controller for Active {
     ) -> ContractResult<Response> {
         match msg {
             ExecuteMsg::Repay() => self.try_repay(deps.as_ref(), env, info),
-            ExecuteMsg::Close() => controller::err("close", deps.api),
+            ExecuteMsg::AddCollateral() => self.try_add_collateral(deps.as_ref(), env, info),
+            ExecuteMsg::BorrowMore { amount } => {
+                self.try_borrow_more(deps.as_ref(), &env, info, amount)
+            }
+            ExecuteMsg::Close { .. } => controller::err("close", deps.api),
+            ExecuteMsg::Cancel() => controller::err("cancel", deps.api),
+            ExecuteMsg::ClosePosition { amount, payout_lpn } => {
+                self.try_close_position(deps, &env, info, amount, payout_lpn)
+            }
+            ExecuteMsg::ChangeClosePolicy {
+                stop_loss,
+                take_profit,
+            } => self.try_change_close_policy(
+                deps.as_ref(),
+                &env,
+                info,
+                ClosePolicy::new(stop_loss, take_profit),
+            ),
+            ExecuteMsg::GrantOperator { operator } => {
+                self.try_change_operator(deps, &env, info, operator, true)
+            }
+            ExecuteMsg::RevokeOperator { operator } => {
+                self.try_change_operator(deps, &env, info, operator, false)
+            }
+            ExecuteMsg::TransferOwnership { new_customer } => {
+                self.try_transfer_ownership(deps, &env, info, new_customer)
+            }
             ExecuteMsg::PriceAlarm() => self.try_on_price_alarm(&deps.querier, &env, info),
             ExecuteMsg::TimeAlarm {} => self.try_on_time_alarm(&deps.querier, &env, info),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
         }
     }
 }
@@ -149,6 +510,31 @@ impl Contract for Active {
     fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse> {
         super::lease_state(self.lease.lease, None, now, querier)
     }
+
+    fn repay_quote(
+        self,
+        payment: LpnCoin,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<RepayQuoteResponse> {
+        with_lease::execute(self.lease.lease, RepayQuote::new(payment, now), querier)
+    }
+
+    fn liquidation_quote(
+        self,
+        price: LeasePrice,
+        at: Timestamp,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<LiquidationQuoteResponse> {
+        if at < now {
+            return Err(ContractError::InvalidParameters(format!(
+                "The liquidation quote time {} is in the past",
+                at
+            )));
+        }
+        with_lease::execute(self.lease.lease, LiquidationQuote::new(price, at), querier)
+    }
 }
 
 fn build_emitter(
@@ -156,6 +542,7 @@ fn build_emitter(
     lease: &LeaseDTO,
     loan: OpenLoanRespResult,
     downpayment: DownpaymentCoin,
+    swap_fee: LpnCoin,
 ) -> Emitter {
     Emitter::of_type(Type::OpenedActive)
         .emit_tx_info(env)
@@ -169,15 +556,66 @@ fn build_emitter(
         .emit("loan-pool-id", lease.loan.lpp().addr())
         .emit_coin_dto("loan", loan.principal)
         .emit_coin_dto("downpayment", downpayment)
+        .emit_coin_dto("swap-fee", swap_fee)
 }
 
-fn into_updated_active<R>(updated_dto: LeaseDTO, dex: Account, resp: R) -> Response
+/// The amount of the lease asset whose sale covers the debt, capped at the lease amount
+fn full_close_amount(
+    lease: &Lease,
+    querier: &QuerierWrapper<'_>,
+    env: &Env,
+) -> ContractResult<LeaseCoin> {
+    with_lease::execute(
+        lease.lease.clone(),
+        FullCloseAmount::new(lease.max_slippage, env.block.time),
+        querier,
+    )
+}
+
+/// Check whether the proceeds of selling the whole lease amount would repay the loan
+///
+/// The lease cannot stay open once its whole amount is sold. The loan is taken as of
+/// the time the proceeds get relayed back, so that the interest accrued meanwhile is covered.
+fn full_sale_repays(
+    lease: &Lease,
+    querier: &QuerierWrapper<'_>,
+    env: &Env,
+) -> ContractResult<bool> {
+    with_lease::execute(
+        lease.lease.clone(),
+        FullSaleRepays::new(
+            lease.max_slippage,
+            super::relayed_by(lease.dex(), env.block.time)?,
+        ),
+        querier,
+    )
+}
+
+fn into_repaid<R>(lease: Lease, paid: bool, resp: R) -> Response
+where
+    R: Into<CwResponse>,
+{
+    if paid {
+        Response::from(resp, paid::Active::new(lease))
+    } else {
+        Response::from(resp, Active::new(lease))
+    }
+}
+
+fn into_updated_active<R>(lease: Lease, resp: R) -> Response
 where
     R: Into<CwResponse>,
 {
-    let lease = Lease {
-        lease: updated_dto,
-        dex,
-    };
     Response::from(resp, Active { lease })
 }
+
+fn into_liquidated<R>(lease: Lease, resp: R, record: Option<LedgerRecord>) -> Response
+where
+    R: Into<CwResponse>,
+{
+    let resp = into_updated_active(lease, resp);
+    match record {
+        Some(record) => resp.record(record),
+        None => resp,
+    }
+}
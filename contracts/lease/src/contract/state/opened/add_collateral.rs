@@ -0,0 +1,154 @@
+use cosmwasm_std::{Env, QuerierWrapper, Timestamp};
+use serde::{Deserialize, Serialize};
+
+use currency::lease::LeaseGroup;
+use finance::{coin::CoinDTO, currency::Symbol, percent::Percent};
+use oracle::stub::OracleRef;
+use platform::batch::{Emit, Emitter};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{
+        opened::{CollateralTrx, OngoingTrx},
+        PaymentCoin, StateResponse,
+    },
+    contract::{
+        cmd::AddCollateral as AddCollateralCmd,
+        dex::Account,
+        state::{
+            opened::active::Active,
+            opening::{
+                swap_coins,
+                swap_exact_in::SwapExactIn,
+                swap_state::{ContractInSwap, SwapState, TransferOutState},
+                swap_task::{
+                    CoinVisitor, IterNext, IterState, OutChain, SwapTask as SwapTaskT,
+                    REMOTE_OUT_CHAIN,
+                },
+                transfer_out::TransferOut,
+            },
+            Response,
+        },
+        Lease,
+    },
+    error::ContractError,
+    event::Type,
+    lease::{with_lease, IntoDTOResult},
+};
+
+const OUT_CHAIN: OutChain = REMOTE_OUT_CHAIN;
+type AssetGroup = LeaseGroup;
+pub(crate) type Transfer = TransferOut<AssetGroup, AddCollateral, OUT_CHAIN>;
+pub(crate) type Swap = SwapExactIn<AssetGroup, AddCollateral, OUT_CHAIN>;
+
+/// Top up the lease with a payment swapped into the lease asset
+#[derive(Serialize, Deserialize)]
+pub(crate) struct AddCollateral {
+    lease: Lease,
+    payment: PaymentCoin,
+}
+
+impl AddCollateral {
+    pub(super) fn new(lease: Lease, payment: PaymentCoin) -> Self {
+        Self { lease, payment }
+    }
+
+    fn state(
+        self,
+        in_progress: CollateralTrx,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> Result<StateResponse, ContractError> {
+        let in_progress = OngoingTrx::CollateralAddition {
+            payment: self.payment,
+            in_progress,
+        };
+
+        super::lease_state(self.lease.lease, Some(in_progress), now, querier)
+    }
+}
+
+impl SwapTaskT<AssetGroup> for AddCollateral {
+    type Result = Response;
+    type Error = ContractError;
+    type Label = Type;
+
+    fn label(&self) -> Self::Label {
+        Type::AddCollateral
+    }
+
+    fn dex_account(&self) -> &Account {
+        &self.lease.dex
+    }
+
+    fn oracle(&self) -> &OracleRef {
+        &self.lease.lease.oracle
+    }
+
+    fn max_slippage(&self) -> Percent {
+        self.lease.max_slippage
+    }
+
+    fn time_alarm(&self, _querier: &QuerierWrapper<'_>) -> Result<TimeAlarmsRef, Self::Error> {
+        Ok(self.lease.lease.time_alarms.clone())
+    }
+
+    fn out_currency(&self) -> Symbol<'_> {
+        self.lease.lease.amount.ticker()
+    }
+
+    fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
+    where
+        Visitor: CoinVisitor<Result = IterNext>,
+    {
+        swap_coins::on_coin(&self.payment, visitor)
+    }
+
+    fn finish(
+        self,
+        amount: CoinDTO<AssetGroup>,
+        querier: &QuerierWrapper<'_>,
+        env: Env,
+    ) -> Result<Self::Result, Self::Error> {
+        let emitter = Emitter::of_type(Type::AddCollateral)
+            .emit_tx_info(&env)
+            .emit("id", self.lease.lease.addr.clone())
+            .emit_coin_dto("payment", self.payment)
+            .emit_coin_dto("amount", amount.clone());
+
+        let IntoDTOResult {
+            lease: lease_updated,
+            batch,
+        } = with_lease::execute(
+            self.lease.lease,
+            AddCollateralCmd::new(amount, env.block.time),
+            querier,
+        )?;
+
+        let active = Active::new(Lease {
+            lease: lease_updated,
+            ..self.lease
+        });
+        Ok(Response::from(batch.into_response(emitter), active))
+    }
+}
+
+impl ContractInSwap<TransferOutState> for AddCollateral {
+    fn state(
+        self,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> Result<StateResponse, ContractError> {
+        self.state(CollateralTrx::TransferOut, now, querier)
+    }
+}
+
+impl ContractInSwap<SwapState> for AddCollateral {
+    fn state(
+        self,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> Result<StateResponse, ContractError> {
+        self.state(CollateralTrx::Swap, now, querier)
+    }
+}
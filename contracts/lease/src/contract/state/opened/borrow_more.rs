@@ -0,0 +1,82 @@
+use cosmwasm_std::{QuerierWrapper, Timestamp};
+use serde::{Deserialize, Serialize};
+
+use currency::payment::PaymentGroup;
+use finance::coin;
+use platform::batch::{Emit, Emitter};
+use sdk::cosmwasm_std::{Deps, DepsMut, Env, Reply};
+
+use crate::{
+    api::{LpnCoin, StateResponse},
+    contract::{
+        cmd::{OpenLoanResp, OpenLoanRespResult},
+        state::{ica_connector::Enterable, Controller, Response},
+        Contract, Lease,
+    },
+    error::{ContractError, ContractResult},
+    event::Type,
+    reply_id::ReplyId,
+};
+
+use super::add_collateral::{self, AddCollateral};
+
+/// Wait for the additional loan amount and then add the collateral
+#[derive(Serialize, Deserialize)]
+pub struct BorrowMore {
+    lease: Lease,
+    collateral: LpnCoin,
+}
+
+impl BorrowMore {
+    pub(super) fn new(lease: Lease, collateral: LpnCoin) -> Self {
+        Self { lease, collateral }
+    }
+
+    fn on_response(self, deps: Deps<'_>, env: Env, msg: Reply) -> ContractResult<Response> {
+        let id = ReplyId::try_from(msg.id)
+            .map_err(|_| ContractError::InvalidParameters("Invalid reply ID passed!".into()))?;
+
+        match id {
+            ReplyId::OpenLoanReq => {
+                let OpenLoanRespResult {
+                    principal,
+                    annual_interest_rate,
+                } = self
+                    .lease
+                    .lease
+                    .loan
+                    .lpp()
+                    .clone()
+                    .execute(OpenLoanResp::new(msg), &deps.querier)?;
+
+                let emitter = Emitter::of_type(Type::BorrowMore)
+                    .emit_tx_info(&env)
+                    .emit("id", &self.lease.lease.addr)
+                    .emit_coin_dto("loan", principal)
+                    .emit_percent_amount("loan-interest", annual_interest_rate)
+                    .emit_coin_dto("collateral", self.collateral.clone());
+
+                let payment = coin::from_amount_ticker::<PaymentGroup>(
+                    self.collateral.amount(),
+                    self.collateral.ticker(),
+                )?;
+                let transfer_out =
+                    add_collateral::Transfer::new(AddCollateral::new(self.lease, payment));
+                let batch = transfer_out.enter(deps, env)?;
+                Ok(Response::from(batch.into_response(emitter), transfer_out))
+            }
+        }
+    }
+}
+
+impl Controller for BorrowMore {
+    fn reply(self, deps: &mut DepsMut<'_>, env: Env, msg: Reply) -> ContractResult<Response> {
+        self.on_response(deps.as_ref(), env, msg)
+    }
+}
+
+impl Contract for BorrowMore {
+    fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse> {
+        super::lease_state(self.lease.lease, None, now, querier)
+    }
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::{QuerierWrapper, Timestamp};
+
+use crate::{
+    api::{
+        opened::{OngoingTrx, PositionCloseTrx},
+        LeaseCoin, StateResponse,
+    },
+    error::ContractResult,
+    lease::LeaseDTO,
+};
+
+pub mod sell_asset;
+pub mod transfer_in_finish;
+pub mod transfer_in_init;
+
+fn query(
+    lease: LeaseDTO,
+    close: LeaseCoin,
+    in_progress: PositionCloseTrx,
+    now: Timestamp,
+    querier: &QuerierWrapper<'_>,
+) -> ContractResult<StateResponse> {
+    let in_progress = OngoingTrx::PositionClose { close, in_progress };
+
+    super::lease_state(lease, Some(in_progress), now, querier)
+}
@@ -0,0 +1,160 @@
+use cosmwasm_std::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use finance::{
+    coin::{self},
+    currency::Symbol,
+};
+use platform::{
+    batch::{Batch as LocalBatch, Emit, Emitter},
+    trx,
+};
+use sdk::cosmwasm_std::{Binary, Deps, Env, QuerierWrapper};
+
+use crate::{
+    api::{dex::ConnectionParams, opened::PositionCloseTrx, LeaseCoin, LpnCoin, StateResponse},
+    contract::{
+        dex::{DexConnectable, SwapRoutes},
+        state::{
+            self, ica_connector::Enterable, ica_post_connector::Postpone, opened::close,
+            Controller, Response,
+        },
+        Contract, Lease,
+    },
+    error::ContractResult,
+    event::Type,
+};
+
+use super::transfer_in_init::TransferInInit;
+
+#[derive(Serialize, Deserialize)]
+pub struct SellAsset {
+    lease: Lease,
+    close: LeaseCoin,
+    full_close: bool,
+    #[serde(default)]
+    routes: SwapRoutes,
+}
+
+impl SellAsset {
+    pub(in crate::contract::state) fn new(
+        lease: Lease,
+        close: LeaseCoin,
+        full_close: bool,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Self> {
+        let mut routes = SwapRoutes::default();
+        routes.resolve(
+            &lease.lease.oracle,
+            &close,
+            lease.lease.loan.lpp().currency(),
+            querier,
+        )?;
+        Ok(Self {
+            lease,
+            close,
+            full_close,
+            routes,
+        })
+    }
+
+    pub(in crate::contract::state::opened) fn enter(
+        &self,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<LocalBatch> {
+        let mut swap_trx = self.lease.dex.swap(
+            &self.lease.lease.oracle,
+            self.lease.max_slippage,
+            &self.routes,
+            querier,
+        );
+        swap_trx.swap_exact_in(&self.close, self.target_currency())?;
+        Ok(swap_trx.into())
+    }
+
+    fn on_response(self, resp: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let emitter = self.emit_ok();
+        let payment_lpn = self.decode_response(resp.as_slice(), &deps.querier)?;
+
+        let transfer_in = TransferInInit::new(self.lease, self.close, self.full_close, payment_lpn);
+        let batch = transfer_in.enter(env.block.time)?;
+
+        Ok(Response::from(batch.into_response(emitter), transfer_in))
+    }
+
+    fn decode_response(
+        &self,
+        resp: &[u8],
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<LpnCoin> {
+        let mut resp_msgs = trx::decode_msg_responses(resp)?;
+        let payment_amount = self
+            .lease
+            .dex
+            .swap_resp(&self.lease.lease.oracle, &self.routes, querier)
+            .exact_amount_in(&mut resp_msgs, &self.close, self.target_currency())?;
+
+        coin::from_amount_ticker(payment_amount, self.target_currency()).map_err(Into::into)
+    }
+
+    fn target_currency(&self) -> Symbol<'_> {
+        self.lease.lease.loan.lpp().currency()
+    }
+
+    fn emit_ok(&self) -> Emitter {
+        Emitter::of_type(Type::PositionCloseSwap)
+            .emit("id", self.lease.lease.addr.clone())
+            .emit_coin_dto("close", self.close.clone())
+            .emit_to_string_value("full-close", self.full_close)
+    }
+}
+
+impl DexConnectable for SellAsset {
+    fn dex(&self) -> &ConnectionParams {
+        self.lease.dex()
+    }
+}
+
+impl Enterable for SellAsset {
+    fn enter(&self, deps: Deps<'_>, _env: Env) -> ContractResult<LocalBatch> {
+        self.enter(&deps.querier)
+    }
+}
+
+impl Controller for SellAsset {
+    fn on_response(self, data: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        self.on_response(data, deps, env)
+    }
+
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let dex_account = self.lease.dex.clone();
+        state::on_error_retry_later(self, dex_account, Type::PositionCloseSwap, deps, env)
+    }
+
+    fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        state::on_timeout_repair_channel(self, Type::PositionCloseSwap, env)
+    }
+}
+
+impl Contract for SellAsset {
+    fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse> {
+        close::query(
+            self.lease.lease,
+            self.close,
+            PositionCloseTrx::Swap,
+            now,
+            querier,
+        )
+    }
+}
+
+impl Postpone for SellAsset {
+    fn setup_alarm(
+        &self,
+        when: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<LocalBatch> {
+        let time_alarms = self.lease.lease.time_alarms.clone();
+        time_alarms.setup_alarm(when).map_err(Into::into)
+    }
+}
@@ -1,13 +1,16 @@
 use cosmwasm_std::{QuerierWrapper, Timestamp};
 
 use crate::{
-    api::{opened::OngoingTrx, StateResponse},
+    api::{dex::ConnectionParams, opened::OngoingTrx, StateResponse},
     contract::cmd::LeaseState,
-    error::ContractResult,
+    error::{ContractError, ContractResult},
     lease::{with_lease, LeaseDTO},
 };
 
 pub mod active;
+pub mod add_collateral;
+pub mod borrow_more;
+pub mod close;
 pub mod repay;
 
 fn lease_state(
@@ -18,3 +21,15 @@ fn lease_state(
 ) -> ContractResult<StateResponse> {
     with_lease::execute(lease, LeaseState::new(now, in_progress), querier)
 }
+
+/// The latest time a swap started `now` and the transfer-in of its output may get relayed
+///
+/// Used to cover the interest accrued until the swap proceeds reach the lease.
+fn relayed_by(dex: &ConnectionParams, now: Timestamp) -> ContractResult<Timestamp> {
+    // one timeout for the swap and one for the transfer-in
+    dex.relaying
+        .timeout
+        .checked_mul(2)
+        .map(|timeout| now + timeout)
+        .ok_or_else(|| ContractError::InvalidParameters("The relaying timeout is too long".into()))
+}
@@ -1,65 +1,154 @@
+use std::any::type_name;
+
 use cosmwasm_std::Timestamp;
 use serde::{Deserialize, Serialize};
 
 use finance::{
-    coin::{self},
+    coin::{self, Amount},
     currency::Symbol,
+    fraction::Fraction,
+    percent::{Percent, Units},
+    ratio::Rational,
 };
 use platform::{
     batch::{Batch as LocalBatch, Emit, Emitter},
     trx,
 };
 use sdk::cosmwasm_std::{Binary, Deps, Env, QuerierWrapper};
-use swap::trx as swap_trx;
+use swap::trx::Dex;
 
 use crate::{
     api::{dex::ConnectionParams, opened::RepayTrx, LpnCoin, PaymentCoin, StateResponse},
     contract::{
-        dex::DexConnectable,
+        cmd::FullRepayAmount,
+        dex::{DexConnectable, SwapRoutes},
         state::{
-            self, ica_connector::Enterable, ica_post_connector::Postpone, opened::repay,
+            self,
+            ica_connector::Enterable,
+            ica_post_connector::Postpone,
+            opened::{self, repay},
             Controller, Response,
         },
         Contract, Lease,
     },
-    error::ContractResult,
+    error::{ContractError, ContractResult},
     event::Type,
+    lease::with_lease,
 };
 
 use super::transfer_in_init::TransferInInit;
 
+/// Swap the non-LPN payments into LPN
+///
+/// A single payment worth more than the debt is swapped into the exact amount of LPN
+/// that pays off the lease, provided the DEX supports it. The unswapped remainder
+/// is transferred back in its original currency and returned to the customer.
 #[derive(Serialize, Deserialize)]
 pub struct BuyLpn {
     lease: Lease,
-    payment: PaymentCoin,
+    payments: Vec<PaymentCoin>,
+    #[serde(default)]
+    exact_out: Option<LpnCoin>,
+    #[serde(default)]
+    routes: SwapRoutes,
 }
 
 impl BuyLpn {
-    pub(in crate::contract::state) fn new(lease: Lease, payment: PaymentCoin) -> Self {
-        Self { lease, payment }
+    pub(in crate::contract::state) fn new(
+        lease: Lease,
+        payments: Vec<PaymentCoin>,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Self> {
+        let mut routes = SwapRoutes::default();
+        let lpn = lease.lease.loan.lpp().currency();
+        repay::swapped(&payments, lpn)
+            .try_for_each(|payment| routes.resolve(&lease.lease.oracle, payment, lpn, querier))?;
+        let exact_out = full_repay_out(&lease, &payments, &routes, now, querier)?;
+        Ok(Self {
+            lease,
+            payments,
+            exact_out,
+            routes,
+        })
     }
 
     pub(super) fn enter(&self, querier: &QuerierWrapper<'_>) -> ContractResult<LocalBatch> {
-        let mut swap_trx = self.lease.dex.swap(&self.lease.lease.oracle, querier);
-        swap_trx.swap_exact_in(&self.payment, self.target_currency())?;
+        let mut swap_trx = self.lease.dex.swap(
+            &self.lease.lease.oracle,
+            self.lease.max_slippage,
+            &self.routes,
+            querier,
+        );
+        repay::swapped(&self.payments, self.target_currency()).try_for_each(|payment| {
+            if let Some(token_out) = &self.exact_out {
+                swap_trx.swap_exact_out(payment, token_out)
+            } else {
+                swap_trx.swap_exact_in(payment, self.target_currency())
+            }
+        })?;
         Ok(swap_trx.into())
     }
 
-    fn on_response(self, resp: Binary, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+    fn on_response(self, resp: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         let emitter = self.emit_ok();
-        let payment_lpn = self.decode_response(resp.as_slice())?;
+        let (payment_lpn, unswapped) = self.decode_response(resp.as_slice(), &deps.querier)?;
 
-        let transfer_in = TransferInInit::new(self.lease, self.payment, payment_lpn);
+        let emitter = if let Some(unswapped) = &unswapped {
+            emitter.emit_coin_dto("unswapped", unswapped.clone())
+        } else {
+            emitter
+        };
+        let transfer_in = TransferInInit::new(self.lease, self.payments, payment_lpn, unswapped);
         let batch = transfer_in.enter(env.block.time)?;
 
         Ok(Response::from(batch.into_response(emitter), transfer_in))
     }
 
-    fn decode_response(&self, resp: &[u8]) -> ContractResult<LpnCoin> {
+    /// Decode the LPN bought, and the remainder of the payment left unswapped, if any
+    fn decode_response(
+        &self,
+        resp: &[u8],
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<(LpnCoin, Option<PaymentCoin>)> {
         let mut resp_msgs = trx::decode_msg_responses(resp)?;
-        let payment_amount = swap_trx::exact_amount_in_resp(&mut resp_msgs)?;
+        if let Some(token_out) = &self.exact_out {
+            let payment = repay::swapped(&self.payments, self.target_currency())
+                .next()
+                .ok_or_else(|| {
+                    ContractError::BrokenInvariant(
+                        type_name::<Self>().into(),
+                        "no payment is swapped into an exact amount".into(),
+                    )
+                })?;
+            let amount_in = self
+                .lease
+                .dex()
+                .protocol
+                .exact_amount_out_resp(&mut resp_msgs)?;
+            let unswapped = payment.amount().saturating_sub(amount_in);
+            let unswapped = (unswapped != Amount::default())
+                .then(|| coin::from_amount_ticker(unswapped, payment.ticker()))
+                .transpose()?;
+            Ok((token_out.clone(), unswapped))
+        } else {
+            let mut swap_resp =
+                self.lease
+                    .dex
+                    .swap_resp(&self.lease.lease.oracle, &self.routes, querier);
+            let payment_amount = repay::swapped(&self.payments, self.target_currency()).try_fold(
+                Amount::default(),
+                |total, payment| {
+                    swap_resp
+                        .exact_amount_in(&mut resp_msgs, payment, self.target_currency())
+                        .map(|amount| total + amount)
+                },
+            )?;
 
-        coin::from_amount_ticker(payment_amount, self.target_currency()).map_err(Into::into)
+            coin::from_amount_ticker(payment_amount, self.target_currency())
+                .map(|payment_lpn| (payment_lpn, None))
+                .map_err(Into::into)
+        }
     }
 
     fn target_currency(&self) -> Symbol<'_> {
@@ -67,12 +156,73 @@ impl BuyLpn {
     }
 
     fn emit_ok(&self) -> Emitter {
-        Emitter::of_type(Type::BuyLpn)
-            .emit("id", self.lease.lease.addr.clone())
-            .emit_coin_dto("payment", self.payment.clone())
+        repay::swapped(&self.payments, self.target_currency()).fold(
+            Emitter::of_type(Type::BuyLpn).emit("id", self.lease.lease.addr.clone()),
+            |emitter, payment| emitter.emit_coin_dto("payment", payment.clone()),
+        )
     }
 }
 
+/// The amount of LPN to buy with a payment worth more than the debt
+///
+/// Applies only if there is a single payment to be swapped and the DEX
+/// is able to swap into an exact amount. The debt is taken as of the latest time
+/// the swap and the transfer-in may get relayed, to cover the interest accrued meanwhile.
+/// The amount is grossed up with the swap fee charged on it so that, along with
+/// the payment in LPN, if any, it pays off the lease. The excess is returned as a change.
+fn full_repay_out(
+    lease: &Lease,
+    payments: &[PaymentCoin],
+    routes: &SwapRoutes,
+    now: Timestamp,
+    querier: &QuerierWrapper<'_>,
+) -> ContractResult<Option<LpnCoin>> {
+    let lpn = lease.lease.loan.lpp().currency();
+    let mut swapped = repay::swapped(payments, lpn);
+    let payment = match (swapped.next(), swapped.next()) {
+        (Some(payment), None) if lease.dex().protocol.supports_exact_amount_out() => payment,
+        _ => return Ok(None),
+    };
+
+    let debt = with_lease::execute(
+        lease.lease.clone(),
+        FullRepayAmount::new(opened::relayed_by(lease.dex(), now)?),
+        querier,
+    )?;
+    let paid = repay::paid_lpn(payments, lpn)?.map_or(Amount::default(), |paid| paid.amount());
+    let due = debt.amount().saturating_sub(paid);
+    if due == Amount::default() {
+        return Ok(None);
+    }
+    let token_out = match gross_of_fee(due, lease.swap_fee) {
+        Some(amount) => coin::from_amount_ticker(amount, lpn)?,
+        None => return Ok(None),
+    };
+
+    let max_in = lease
+        .dex
+        .swap(&lease.lease.oracle, lease.max_slippage, routes, querier)
+        .max_in(payment.ticker(), &token_out)?;
+    Ok((max_in < payment.amount()).then_some(token_out))
+}
+
+/// The amount that is at least `net` after `swap_fee` is charged on it
+///
+/// From 'gross - swap_fee% of gross = net' follows 'gross = 100% / (100% - swap_fee%) of net'.
+/// The result is rounded up. A fee of a hundred percent leaves nothing.
+fn gross_of_fee(net: Amount, swap_fee: Percent) -> Option<Amount> {
+    if swap_fee == Percent::ZERO {
+        return Some(net);
+    }
+    Percent::HUNDRED
+        .checked_sub(swap_fee)
+        .ok()
+        .filter(|rest| rest != &Percent::ZERO)
+        .and_then(|rest| {
+            Fraction::<Units>::of(&Rational::new(Percent::HUNDRED, rest), net).checked_add(1)
+        })
+}
+
 impl DexConnectable for BuyLpn {
     fn dex(&self) -> &ConnectionParams {
         self.lease.dex()
@@ -90,6 +240,11 @@ impl Controller for BuyLpn {
         self.on_response(data, deps, env)
     }
 
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let dex_account = self.lease.dex.clone();
+        state::on_error_retry_later(self, dex_account, Type::BuyLpn, deps, env)
+    }
+
     fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         state::on_timeout_repair_channel(self, Type::BuyLpn, env)
     }
@@ -97,7 +252,13 @@ impl Controller for BuyLpn {
 
 impl Contract for BuyLpn {
     fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse> {
-        repay::query(self.lease.lease, self.payment, RepayTrx::Swap, now, querier)
+        repay::query(
+            self.lease.lease,
+            self.payments,
+            RepayTrx::Swap,
+            now,
+            querier,
+        )
     }
 }
 
@@ -111,3 +272,29 @@ impl Postpone for BuyLpn {
         time_alarms.setup_alarm(when).map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use finance::{fraction::Fraction, percent::Percent};
+
+    #[test]
+    fn gross_of_no_fee() {
+        assert_eq!(Some(1000), super::gross_of_fee(1000, Percent::ZERO));
+    }
+
+    #[test]
+    fn gross_of_fee_covers_net() {
+        let fee = Percent::from_permille(3);
+        let gross = super::gross_of_fee(1000, fee).unwrap();
+        assert_eq!(1004, gross);
+        assert!(gross - fee.of(gross) >= 1000);
+
+        let gross = super::gross_of_fee(997, fee).unwrap();
+        assert!(gross - fee.of(gross) >= 997);
+    }
+
+    #[test]
+    fn gross_of_full_fee() {
+        assert_eq!(None, super::gross_of_fee(1000, Percent::HUNDRED));
+    }
+}
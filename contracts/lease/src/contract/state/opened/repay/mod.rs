@@ -1,9 +1,11 @@
 use cosmwasm_std::{QuerierWrapper, Timestamp};
 
+use finance::{coin, currency::Symbol};
+
 use crate::{
     api::{
         opened::{OngoingTrx, RepayTrx},
-        PaymentCoin, StateResponse,
+        LpnCoin, PaymentCoin, StateResponse,
     },
     error::ContractResult,
     lease::LeaseDTO,
@@ -14,15 +16,38 @@ pub mod transfer_in_finish;
 pub mod transfer_in_init;
 pub mod transfer_out;
 
+/// The payments to be swapped into LPN
+pub(in crate::contract::state::opened) fn swapped<'p>(
+    payments: &'p [PaymentCoin],
+    lpn: Symbol<'p>,
+) -> impl Iterator<Item = &'p PaymentCoin> {
+    payments
+        .iter()
+        .filter(move |payment| payment.ticker() != lpn)
+}
+
+/// The payment made in LPN, if any
+pub(in crate::contract::state::opened) fn paid_lpn(
+    payments: &[PaymentCoin],
+    lpn: Symbol<'_>,
+) -> ContractResult<Option<LpnCoin>> {
+    payments
+        .iter()
+        .find(|payment| payment.ticker() == lpn)
+        .map(|payment| coin::from_amount_ticker(payment.amount(), payment.ticker()))
+        .transpose()
+        .map_err(Into::into)
+}
+
 fn query(
     lease: LeaseDTO,
-    payment: PaymentCoin,
+    payments: Vec<PaymentCoin>,
     in_progress: RepayTrx,
     now: Timestamp,
     querier: &QuerierWrapper<'_>,
 ) -> ContractResult<StateResponse> {
     let in_progress = OngoingTrx::Repayment {
-        payment,
+        payments,
         in_progress,
     };
 
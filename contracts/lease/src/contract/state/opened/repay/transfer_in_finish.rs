@@ -1,6 +1,7 @@
 use cosmwasm_std::{Deps, Timestamp};
 use serde::{Deserialize, Serialize};
 
+use finance::coin;
 use platform::batch::{Emit, Emitter};
 use sdk::cosmwasm_std::{DepsMut, Env, MessageInfo, QuerierWrapper};
 
@@ -23,37 +24,65 @@ use super::transfer_in_init::TransferInInit;
 #[derive(Serialize, Deserialize)]
 pub struct TransferInFinish {
     lease: Lease,
-    payment: PaymentCoin,
+    payments: Vec<PaymentCoin>,
     payment_lpn: LpnCoin,
+    #[serde(default)]
+    unswapped: Option<PaymentCoin>,
     timeout: Timestamp,
 }
 
 impl TransferInFinish {
     pub(super) fn new(
         lease: Lease,
-        payment: PaymentCoin,
+        payments: Vec<PaymentCoin>,
         payment_lpn: LpnCoin,
+        unswapped: Option<PaymentCoin>,
         timeout: Timestamp,
     ) -> Self {
         Self {
             lease,
-            payment,
+            payments,
             payment_lpn,
+            unswapped,
             timeout,
         }
     }
 
     pub(super) fn try_complete(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         let querier = &deps.querier;
-        let received =
-            transfer_in::check_received(&self.payment_lpn, &env.contract.address, querier)?;
+        let paid_lpn = repay::paid_lpn(&self.payments, self.payment_lpn.ticker())?;
+        let expected = paid_lpn.as_ref().map_or_else(
+            || Ok(self.payment_lpn.clone()),
+            |paid| {
+                coin::from_amount_ticker(
+                    self.payment_lpn.amount() + paid.amount(),
+                    self.payment_lpn.ticker(),
+                )
+            },
+        )?;
+        let received = transfer_in::check_received(&expected, &env.contract.address, querier)?
+            && self.unswapped.as_ref().map_or(Ok(true), |unswapped| {
+                transfer_in::check_received(unswapped, &env.contract.address, querier)
+            })?;
 
         if received {
-            Active::try_repay_lpn(self.lease, self.payment_lpn, querier, &env)
+            Active::try_repay_swapped_lpn(
+                self.lease,
+                self.payment_lpn,
+                paid_lpn,
+                self.unswapped.as_ref(),
+                querier,
+                &env,
+            )
         } else {
             let emitter = self.emit_ok();
             if env.block.time >= self.timeout {
-                let transfer_in = TransferInInit::new(self.lease, self.payment, self.payment_lpn);
+                let transfer_in = TransferInInit::new(
+                    self.lease,
+                    self.payments,
+                    self.payment_lpn,
+                    self.unswapped,
+                );
                 Ok(Response::from(
                     transfer_in.enter(env.block.time)?.into_response(emitter),
                     transfer_in,
@@ -71,9 +100,13 @@ impl TransferInFinish {
     }
 
     fn emit_ok(&self) -> Emitter {
-        Emitter::of_type(Type::RepaymentTransferIn)
-            .emit("id", self.lease.lease.addr.clone())
-            .emit_coin_dto("payment", self.payment.clone())
+        self.payments
+            .iter()
+            .fold(
+                Emitter::of_type(Type::RepaymentTransferIn)
+                    .emit("id", self.lease.lease.addr.clone()),
+                |emitter, payment| emitter.emit_coin_dto("payment", payment.clone()),
+            )
             .emit_coin_dto("payment-stable", self.payment_lpn.clone())
     }
 }
@@ -98,7 +131,7 @@ impl Contract for TransferInFinish {
     fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse> {
         repay::query(
             self.lease.lease,
-            self.payment,
+            self.payments,
             RepayTrx::TransferInFinish,
             now,
             querier,
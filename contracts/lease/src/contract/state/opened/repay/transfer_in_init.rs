@@ -7,7 +7,7 @@ use sdk::cosmwasm_std::{Deps, Env, QuerierWrapper, Timestamp};
 use crate::{
     api::{dex::ConnectionParams, opened::RepayTrx, LpnCoin, PaymentCoin, StateResponse},
     contract::{
-        dex::{self, DexConnectable},
+        dex::DexConnectable,
         state::{
             self, ica_connector::Enterable, ica_post_connector::Postpone, opened::repay,
             Controller, Response,
@@ -20,38 +20,48 @@ use crate::{
 
 use super::transfer_in_finish::TransferInFinish;
 
+/// Transfer back the bought LPN along with the unswapped remainder of a payment, if any
 #[derive(Serialize, Deserialize)]
 pub struct TransferInInit {
     lease: Lease,
-    payment: PaymentCoin,
+    payments: Vec<PaymentCoin>,
     payment_lpn: LpnCoin,
+    #[serde(default)]
+    unswapped: Option<PaymentCoin>,
 }
 
 impl TransferInInit {
     pub(in crate::contract::state) fn new(
         lease: Lease,
-        payment: PaymentCoin,
+        payments: Vec<PaymentCoin>,
         payment_lpn: LpnCoin,
+        unswapped: Option<PaymentCoin>,
     ) -> Self {
         Self {
             lease,
-            payment,
+            payments,
             payment_lpn,
+            unswapped,
         }
     }
 
     pub(super) fn enter(&self, now: Timestamp) -> ContractResult<Batch> {
         let mut sender = self.lease.dex.transfer_from(now);
         sender.send(&self.payment_lpn)?;
+        if let Some(unswapped) = &self.unswapped {
+            sender.send(unswapped)?;
+        }
         Ok(sender.into())
     }
 
     fn on_response(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let timeout = env.block.time + self.lease.dex().relaying.timeout;
         let finish = TransferInFinish::new(
             self.lease,
-            self.payment,
+            self.payments,
             self.payment_lpn,
-            env.block.time + dex::IBC_TIMEOUT,
+            self.unswapped,
+            timeout,
         );
         finish.try_complete(deps, env)
     }
@@ -74,6 +84,11 @@ impl Controller for TransferInInit {
         self.on_response(deps, env)
     }
 
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let dex_account = self.lease.dex.clone();
+        state::on_error_retry_later(self, dex_account, Type::RepaymentTransferIn, deps, env)
+    }
+
     fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         state::on_timeout_repair_channel(self, Type::RepaymentTransferIn, env)
     }
@@ -83,7 +98,7 @@ impl Contract for TransferInInit {
     fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse> {
         repay::query(
             self.lease.lease,
-            self.payment,
+            self.payments,
             RepayTrx::TransferInInit,
             now,
             querier,
@@ -7,7 +7,13 @@ use sdk::cosmwasm_std::{Deps, Env, Timestamp};
 use crate::{
     api::{opened::RepayTrx, PaymentCoin, StateResponse},
     contract::{
-        state::{self, ica_connector::Enterable, opened::repay, Controller, Response},
+        cmd,
+        state::{
+            self,
+            ica_connector::Enterable,
+            opened::{active::Active, repay},
+            Controller, Response,
+        },
         Contract, Lease,
     },
     error::ContractResult,
@@ -16,15 +22,29 @@ use crate::{
 
 use super::buy_lpn::BuyLpn;
 
+/// Transfer out to the DEX, one at a time, the payments to be swapped into LPN
+///
+/// A payment in LPN, if any, stays in the lease account.
 #[derive(Serialize, Deserialize)]
 pub struct TransferOut {
     lease: Lease,
-    payment: PaymentCoin,
+    payments: Vec<PaymentCoin>,
+    coin_index: usize,
 }
 
 impl TransferOut {
-    pub(in crate::contract::state) fn new(lease: Lease, payment: PaymentCoin) -> Self {
-        Self { lease, payment }
+    pub(in crate::contract::state) fn new(lease: Lease, payments: Vec<PaymentCoin>) -> Self {
+        Self::new_with_index(lease, payments, 0)
+    }
+
+    fn new_with_index(lease: Lease, payments: Vec<PaymentCoin>, coin_index: usize) -> Self {
+        let res = Self {
+            lease,
+            payments,
+            coin_index,
+        };
+        debug_assert!(res.coin().is_some(), "the coin index is invalid");
+        res
     }
 
     pub(in crate::contract::state::opened) fn enter(
@@ -32,23 +52,78 @@ impl TransferOut {
         now: Timestamp,
     ) -> ContractResult<Batch> {
         let mut sender = self.lease.dex.transfer_to(now);
-        // TODO apply nls_swap_fee on the payment!
-        sender.send(&self.payment)?;
+        if let Some(coin) = self.coin() {
+            sender.send(coin)?;
+        }
         Ok(sender.into())
     }
 
-    fn on_response(self, deps: Deps<'_>, _env: Env) -> ContractResult<Response> {
+    fn on_response(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         let emitter = self.emit_ok();
-        let buy_lpn = BuyLpn::new(self.lease, self.payment);
-        let batch = buy_lpn.enter(&deps.querier)?;
+        if self.last_coin() {
+            let buy_lpn = BuyLpn::new(self.lease, self.payments, env.block.time, &deps.querier)?;
+            let batch = buy_lpn.enter(&deps.querier)?;
+
+            Ok(Response::from(batch.into_response(emitter), buy_lpn))
+        } else {
+            let next_transfer =
+                Self::new_with_index(self.lease, self.payments, self.coin_index + 1);
+            let batch = next_transfer.enter(env.block.time)?;
+
+            Ok(Response::from(batch.into_response(emitter), next_transfer))
+        }
+    }
+
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        if self.coin_index == 0 {
+            self.roll_back(&deps.querier, env)
+        } else {
+            // some payments are already on the DEX, and the failed one is refunded
+            // so it is safe to send it again
+            state::on_error_retry(self, Type::RepaymentTransferOut, deps, env)
+        }
+    }
 
-        Ok(Response::from(batch.into_response(emitter), buy_lpn))
+    /// Return the payments to the customer and get back to the active lease
+    ///
+    /// The payments are refunded to the lease account on a failed first transfer.
+    fn roll_back(self, querier: &QuerierWrapper<'_>, env: Env) -> ContractResult<Response> {
+        let emitter = self.emit_ok().emit("error", "RollBack");
+        let batch = self
+            .payments
+            .iter()
+            .try_fold(Batch::default(), |batch, payment| {
+                cmd::send(
+                    payment,
+                    &env.contract.address,
+                    &self.lease.lease.customer,
+                    querier,
+                )
+                .map(|payment_batch| batch.merge(payment_batch))
+            })?;
+        Ok(Response::from(
+            batch.into_response(emitter),
+            Active::new(self.lease),
+        ))
+    }
+
+    fn coin(&self) -> Option<&PaymentCoin> {
+        repay::swapped(&self.payments, self.lease.lease.loan.lpp().currency()).nth(self.coin_index)
+    }
+
+    fn last_coin(&self) -> bool {
+        repay::swapped(&self.payments, self.lease.lease.loan.lpp().currency()).count()
+            == self.coin_index + 1
     }
 
     fn emit_ok(&self) -> Emitter {
-        Emitter::of_type(Type::RepaymentTransferOut)
-            .emit("id", self.lease.lease.addr.clone())
-            .emit_coin_dto("payment", self.payment.clone())
+        let emitter =
+            Emitter::of_type(Type::RepaymentTransferOut).emit("id", self.lease.lease.addr.clone());
+        if let Some(coin) = self.coin() {
+            emitter.emit_coin_dto("payment", coin.clone())
+        } else {
+            emitter
+        }
     }
 }
 
@@ -63,6 +138,10 @@ impl Controller for TransferOut {
         self.on_response(deps, env)
     }
 
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        self.on_error(deps, env)
+    }
+
     fn on_timeout(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         state::on_timeout_retry(self, Type::RepaymentTransferOut, deps, env)
     }
@@ -72,7 +151,7 @@ impl Contract for TransferOut {
     fn state(self, now: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<StateResponse> {
         repay::query(
             self.lease.lease,
-            self.payment,
+            self.payments,
             RepayTrx::TransferOut,
             now,
             querier,
@@ -1,16 +1,22 @@
-use cosmwasm_std::{Env, QuerierWrapper, Timestamp};
-use currency::lease::LeaseGroup;
-use finance::{coin::CoinDTO, currency::Symbol};
+use cosmwasm_std::{Addr, Env, QuerierWrapper, Timestamp};
+use currency::{lease::LeaseGroup, lpn::Lpns};
+use finance::{
+    coin::{self, Amount, CoinDTO},
+    currency::Symbol,
+    percent::Percent,
+};
 use lpp::stub::lender::LppLenderRef;
 use oracle::stub::OracleRef;
-use platform::ica::HostAccount;
+use platform::{batch::Batch, ica::HostAccount};
 use serde::{Deserialize, Serialize};
 use timealarms::stub::TimeAlarmsRef;
 
 use crate::{
-    api::{opening::OngoingTrx, DownpaymentCoin, NewLeaseForm, StateResponse},
+    api::{
+        opening::OngoingTrx, DownpaymentCoin, LedgerRecord, LpnCoin, NewLeaseForm, StateResponse,
+    },
     contract::{
-        cmd::OpenLoanRespResult,
+        cmd::{self, OpenLoanRespResult},
         dex::Account,
         state::{opened::active::Active, Response},
         Lease,
@@ -21,6 +27,7 @@ use crate::{
 };
 
 use super::{
+    cancel::Refund,
     swap_coins,
     swap_exact_in::SwapExactIn,
     swap_state::{ContractInSwap, SwapState, TransferOutState},
@@ -40,6 +47,12 @@ pub(crate) struct BuyAsset {
     form: NewLeaseForm,
     dex_account: Account,
     downpayment: DownpaymentCoin,
+    /// The swap fee on the downpayment
+    ///
+    /// It is withheld from the loan, stays at the lease account and is paid once
+    /// the asset is bought. Leases requested before the fee was introduced have none.
+    #[serde(default)]
+    swap_fee: Option<LpnCoin>,
     loan: OpenLoanRespResult,
     deps: (LppLenderRef, OracleRef),
 }
@@ -49,6 +62,7 @@ impl BuyAsset {
         form: NewLeaseForm,
         dex_account: Account,
         downpayment: DownpaymentCoin,
+        swap_fee: Option<LpnCoin>,
         loan: OpenLoanRespResult,
         deps: (LppLenderRef, OracleRef),
     ) -> Self {
@@ -56,11 +70,30 @@ impl BuyAsset {
             form,
             dex_account,
             downpayment,
+            swap_fee,
             loan,
             deps,
         }
     }
 
+    pub(super) fn customer(&self) -> &Addr {
+        &self.form.customer
+    }
+
+    pub(super) fn into_refund(self) -> (Refund, Account) {
+        (
+            Refund::new(
+                self.form.customer,
+                self.downpayment,
+                self.swap_fee,
+                self.loan,
+                self.deps.0,
+                self.form.time_alarms,
+            ),
+            self.dex_account,
+        )
+    }
+
     fn state<InP>(self, in_progress_fn: InP) -> StateResponse
     where
         InP: FnOnce(String) -> OngoingTrx,
@@ -70,12 +103,9 @@ impl BuyAsset {
             loan: self.loan.principal,
             loan_interest_rate: self.loan.annual_interest_rate,
             in_progress: in_progress_fn(HostAccount::from(self.dex_account).into()),
+            last_error: None,
         }
     }
-
-    // fn emit_ok(&self) -> Emitter {
-    //     Emitter::of_type(Type::OpeningTransferOut)
-    // }
 }
 
 impl SwapTaskT<AssetGroup> for BuyAsset {
@@ -95,6 +125,10 @@ impl SwapTaskT<AssetGroup> for BuyAsset {
         &self.deps.1
     }
 
+    fn max_slippage(&self) -> Percent {
+        self.form.max_slippage
+    }
+
     fn time_alarm(&self, querier: &QuerierWrapper<'_>) -> Result<TimeAlarmsRef, Self::Error> {
         TimeAlarmsRef::new(self.form.time_alarms.clone(), querier).map_err(Into::into)
     }
@@ -107,7 +141,11 @@ impl SwapTaskT<AssetGroup> for BuyAsset {
     where
         Visitor: CoinVisitor<Result = IterNext>,
     {
-        swap_coins::on_coins(&self.downpayment, &self.loan.principal, visitor)
+        swap_coins::on_coins(
+            &self.downpayment,
+            &loan_out(&self.loan.principal, self.swap_fee.as_ref()),
+            visitor,
+        )
     }
 
     fn finish(
@@ -116,6 +154,19 @@ impl SwapTaskT<AssetGroup> for BuyAsset {
         querier: &QuerierWrapper<'_>,
         env: Env,
     ) -> Result<Self::Result, Self::Error> {
+        let fee_batch = match &self.swap_fee {
+            Some(fee) if !fee.is_zero() => {
+                cmd::send(fee, &env.contract.address, &self.form.loan.profit, querier)?
+            }
+            _ => Batch::default(),
+        };
+        let swap_fee_paid = self.swap_fee.clone().map_or_else(
+            || coin::from_amount_ticker(Amount::default(), self.loan.principal.ticker()),
+            Ok,
+        )?;
+
+        let max_slippage = self.form.max_slippage;
+        let swap_fee = self.form.swap_fee;
         let IntoDTOResult { lease, batch } = self.form.into_lease(
             env.contract.address.clone(),
             env.block.time,
@@ -126,9 +177,17 @@ impl SwapTaskT<AssetGroup> for BuyAsset {
         let active = Active::new(Lease {
             lease,
             dex: self.dex_account,
+            max_slippage,
+            swap_fee,
         });
-        let emitter = active.emit_ok(&env, self.downpayment, self.loan);
-        Ok(Response::from(batch.into_response(emitter), active))
+        let record = LedgerRecord::Open {
+            amount,
+            downpayment: self.downpayment.clone(),
+            swap_fee: swap_fee_paid.clone(),
+            loan: self.loan.principal.clone(),
+        };
+        let emitter = active.emit_ok(&env, self.downpayment, swap_fee_paid, self.loan);
+        Ok(Response::from(batch.merge(fee_batch).into_response(emitter), active).record(record))
     }
 }
 
@@ -153,3 +212,16 @@ impl ContractInSwap<SwapState> for BuyAsset {
         Ok(self.state(in_progress_fn))
     }
 }
+
+/// The part of the loan taken to the DEX
+///
+/// The swap fee, if any, stays at the lease account.
+pub(super) fn loan_out(loan: &LpnCoin, swap_fee: Option<&LpnCoin>) -> LpnCoin {
+    swap_fee.map_or_else(
+        || loan.clone(),
+        |fee| {
+            coin::from_amount_ticker::<Lpns>(loan.amount() - fee.amount(), loan.ticker())
+                .expect("the swap fee is in the loan currency")
+        },
+    )
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use platform::ica::HostAccount;
+use sdk::cosmwasm_std::{Binary, Deps, Env, QuerierWrapper, Timestamp};
+
+use crate::{
+    api::StateResponse,
+    contract::{
+        dex::Account,
+        state::{self, opening::buy_asset::Swap, Controller, Response},
+        Contract,
+    },
+    error::ContractResult,
+    event::Type,
+};
+
+use super::{
+    super::swap_task::CoinsNb, transfer_in_finish::TransferInFinish,
+    transfer_in_init::TransferInInit, Refund, ALL_COINS,
+};
+
+/// Wait for the outcome of a transfer to the DEX before taking the funds back
+#[derive(Serialize, Deserialize)]
+pub struct AwaitTransferOut {
+    refund: Refund,
+    dex_account: Account,
+    coin_index: CoinsNb,
+}
+
+impl AwaitTransferOut {
+    pub(super) fn new(refund: Refund, dex_account: Account, coin_index: CoinsNb) -> Self {
+        Self {
+            refund,
+            dex_account,
+            coin_index,
+        }
+    }
+
+    fn transfer_in(
+        self,
+        remote_coins: CoinsNb,
+        env: Env,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
+        if remote_coins == 0 {
+            let finish =
+                TransferInFinish::new(self.refund, self.dex_account, remote_coins, env.block.time);
+            let batch = finish.enter(env.block.time, querier)?;
+            Ok(Response::from(batch, finish))
+        } else {
+            let transfer_in = TransferInInit::new(self.refund, self.dex_account, remote_coins);
+            let batch = transfer_in.enter(env.block.time)?;
+            Ok(Response::from(batch, transfer_in))
+        }
+    }
+}
+
+impl Controller for AwaitTransferOut {
+    fn on_response(self, _data: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let remote_coins = self.coin_index + 1;
+        self.transfer_in(remote_coins, env, &deps.querier)
+    }
+
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        // the coin has been refunded
+        let remote_coins = self.coin_index;
+        self.transfer_in(remote_coins, env, &deps.querier)
+    }
+
+    fn on_timeout(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        // the coin has been refunded
+        let remote_coins = self.coin_index;
+        self.transfer_in(remote_coins, env, &deps.querier)
+    }
+}
+
+impl Contract for AwaitTransferOut {
+    fn state(
+        self,
+        _now: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<StateResponse> {
+        Ok(self
+            .refund
+            .state(HostAccount::from(self.dex_account).into()))
+    }
+}
+
+/// Wait for the outcome of a swap before taking the funds back
+///
+/// If the swap has gone through, it is too late to cancel and the lease gets opened.
+#[derive(Serialize, Deserialize)]
+pub struct AwaitSwap {
+    swap: Swap,
+}
+
+impl AwaitSwap {
+    pub(super) fn new(swap: Swap) -> Self {
+        Self { swap }
+    }
+}
+
+impl Controller for AwaitSwap {
+    fn on_response(self, data: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        Controller::on_response(self.swap, data, deps, env)
+    }
+
+    fn on_error(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let (refund, dex_account) = self.swap.into_spec().into_refund();
+        let transfer_in = TransferInInit::new(refund, dex_account, ALL_COINS);
+        let batch = transfer_in.enter(env.block.time)?;
+        Ok(Response::from(batch, transfer_in))
+    }
+
+    fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let (refund, dex_account) = self.swap.into_spec().into_refund();
+        let transfer_in = TransferInInit::new(refund, dex_account, ALL_COINS);
+        state::on_timeout_repair_channel(transfer_in, Type::OpeningCancelTransferIn, env)
+    }
+}
+
+impl Contract for AwaitSwap {
+    fn state(
+        self,
+        _now: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<StateResponse> {
+        let (refund, dex_account) = self.swap.into_spec().into_refund();
+        Ok(refund.state(HostAccount::from(dex_account).into()))
+    }
+}
@@ -0,0 +1,273 @@
+use serde::{Deserialize, Serialize};
+
+use currency::{lpn::Lpns, payment::PaymentGroup};
+use finance::coin::{self, Amount, IntoDTO};
+use lpp::stub::lender::LppLenderRef;
+use platform::{
+    bank,
+    batch::{Batch, Emit, Emitter},
+};
+use sdk::{
+    cosmwasm_ext::Response as CwResponse,
+    cosmwasm_std::{Addr, Coin as CwCoin, DepsMut, Env, MessageInfo, QuerierWrapper},
+};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{opening::OngoingTrx, DownpaymentCoin, ExecuteMsg, LpnCoin, StateResponse},
+    contract::{
+        cmd::{CancelOpen, OpenLoanRespResult},
+        state::{
+            closed::Closed, ica_connector::IcaConnector, ica_recover::InRecovery, transfer_in,
+            Controller, Response, State,
+        },
+    },
+    error::{ContractError, ContractResult},
+    event::Type,
+};
+
+use self::{
+    in_flight::{AwaitSwap, AwaitTransferOut},
+    transfer_in_init::TransferInInit,
+};
+
+use super::{buy_asset, swap_task::CoinsNb};
+
+pub mod in_flight;
+pub mod transfer_in_finish;
+pub mod transfer_in_init;
+
+/// The number of coins a lease takes to the DEX while opening, the downpayment and the loan
+const ALL_COINS: CoinsNb = 2;
+
+/// Cancel the opening of the lease
+///
+/// Only the states that do not handle the message on their own are dispatched here.
+pub(in crate::contract::state) fn execute(
+    state: State,
+    deps: &mut DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+) -> ContractResult<Response> {
+    match state {
+        State::OpenIcaAccount(open_ica) => {
+            let mut refund = open_ica.into_connectee().into_refund();
+            refund.check_access(&info.sender, &env, &deps.querier)?;
+            refund.add_payment(info.funds)?;
+            refund.finish(&env, &deps.querier)
+        }
+        State::OpeningTransferOut(transfer) => {
+            let (buy_asset, coin_index) = transfer.into_parts();
+            let (mut refund, dex_account) = buy_asset.into_refund();
+            refund.check_access(&info.sender, &env, &deps.querier)?;
+            refund.add_payment(info.funds)?;
+            Ok(Response::from(
+                CwResponse::new(),
+                AwaitTransferOut::new(refund, dex_account, coin_index),
+            ))
+        }
+        State::BuyAsset(swap) => {
+            check_access(swap.spec().customer(), &info.sender, &env, &deps.querier)?;
+            // the lease may still get opened, so the interest is to be paid once the funds are back
+            if !info.funds.is_empty() {
+                return Err(ContractError::InvalidParameters(
+                    "No funds are accepted while the swap is in flight".into(),
+                ));
+            }
+            Ok(Response::from(CwResponse::new(), AwaitSwap::new(swap)))
+        }
+        State::BuyAssetRecoverIca(recover_ica) => {
+            // the swap has not been delivered, the channel is being recovered
+            let (mut refund, dex_account) = recover_ica
+                .into_connectee()
+                .into_state()
+                .into_spec()
+                .into_refund();
+            refund.check_access(&info.sender, &env, &deps.querier)?;
+            refund.add_payment(info.funds)?;
+            let transfer_in = TransferInInit::new(refund, dex_account, ALL_COINS);
+            Ok(Response::from(
+                CwResponse::new(),
+                IcaConnector::new(InRecovery::new(transfer_in)),
+            ))
+        }
+        State::BuyAssetPostRecoverIca(post_recover_ica) => {
+            let (mut refund, dex_account) = post_recover_ica
+                .into_connectee()
+                .into_state()
+                .into_spec()
+                .into_refund();
+            refund.check_access(&info.sender, &env, &deps.querier)?;
+            refund.add_payment(info.funds)?;
+            let transfer_in = TransferInInit::new(refund, dex_account, ALL_COINS);
+            let batch = transfer_in.enter(env.block.time)?;
+            Ok(Response::from(batch, transfer_in))
+        }
+        _ => state.execute(deps, env, info, ExecuteMsg::Cancel()),
+    }
+}
+
+/// The funds of a lease whose opening is being cancelled
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Refund {
+    customer: Addr,
+    downpayment: DownpaymentCoin,
+    /// The swap fee withheld from the loan, it is repaid along with the loan
+    #[serde(default)]
+    swap_fee: Option<LpnCoin>,
+    loan: OpenLoanRespResult,
+    lpp: LppLenderRef,
+    time_alarms: Addr,
+    /// The LPN sent along with the cancel messages to cover the loan interest
+    #[serde(default)]
+    payment: Option<LpnCoin>,
+}
+
+impl Refund {
+    pub(super) fn new(
+        customer: Addr,
+        downpayment: DownpaymentCoin,
+        swap_fee: Option<LpnCoin>,
+        loan: OpenLoanRespResult,
+        lpp: LppLenderRef,
+        time_alarms: Addr,
+    ) -> Self {
+        Self {
+            customer,
+            downpayment,
+            swap_fee,
+            loan,
+            lpp,
+            time_alarms,
+            payment: None,
+        }
+    }
+
+    /// Add the funds sent along with a cancel message to the payment of the loan interest
+    ///
+    /// Only the loan currency is accepted.
+    fn add_payment(&mut self, funds: Vec<CwCoin>) -> ContractResult<()> {
+        let loan_ticker = self.loan.principal.ticker();
+        let only_loan_currency = || {
+            ContractError::InvalidParameters(format!(
+                "Only {} is accepted along with a cancel",
+                loan_ticker
+            ))
+        };
+        let payments = bank::received_all::<Lpns, _>(funds, IntoDTO::<Lpns>::new())
+            .map_err(|_| only_loan_currency())?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        if payments
+            .iter()
+            .any(|payment: &LpnCoin| payment.ticker() != loan_ticker)
+        {
+            return Err(only_loan_currency());
+        }
+
+        let paid = payments.iter().map(LpnCoin::amount).sum::<Amount>();
+        if paid != Amount::default() {
+            self.payment = Some(coin::from_amount_ticker::<Lpns>(
+                self.paid_amount() + paid,
+                loan_ticker,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// The part of the loan taken to the DEX
+    fn loan_out(&self) -> LpnCoin {
+        buy_asset::loan_out(&self.loan.principal, self.swap_fee.as_ref())
+    }
+
+    fn paid_amount(&self) -> Amount {
+        self.payment
+            .as_ref()
+            .map_or_else(Amount::default, LpnCoin::amount)
+    }
+
+    fn check_access(
+        &self,
+        sender: &Addr,
+        env: &Env,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<()> {
+        check_access(&self.customer, sender, env, querier)
+    }
+
+    fn time_alarms(&self, querier: &QuerierWrapper<'_>) -> ContractResult<TimeAlarmsRef> {
+        TimeAlarmsRef::new(self.time_alarms.clone(), querier).map_err(Into::into)
+    }
+
+    /// Check whether all funds are back at the lease account
+    ///
+    /// The LPN paid along with the cancel messages is at the lease account too.
+    fn received(&self, lease: &Addr, querier: &QuerierWrapper<'_>) -> ContractResult<bool> {
+        let loan = &self.loan.principal;
+        let loan_paid = loan.amount() + self.paid_amount();
+        if self.downpayment.ticker() == loan.ticker() {
+            let total = coin::from_amount_ticker::<PaymentGroup>(
+                self.downpayment.amount() + loan_paid,
+                loan.ticker(),
+            )?;
+            transfer_in::check_received(&total, lease, querier)
+        } else {
+            let total_lpn = coin::from_amount_ticker::<Lpns>(loan_paid, loan.ticker())?;
+            Ok(
+                transfer_in::check_received(&self.downpayment, lease, querier)?
+                    && transfer_in::check_received(&total_lpn, lease, querier)?,
+            )
+        }
+    }
+
+    /// Repay the loan and send the rest of the funds to the customer
+    fn finish(&self, env: &Env, querier: &QuerierWrapper<'_>) -> ContractResult<Response> {
+        let lease = &env.contract.address;
+        let batch: Batch = self.lpp.clone().execute(
+            CancelOpen::new(
+                lease,
+                &self.customer,
+                &self.downpayment,
+                env.block.time,
+                querier,
+            ),
+            querier,
+        )?;
+
+        let emitter = Emitter::of_type(Type::OpeningCancel)
+            .emit_tx_info(env)
+            .emit("id", lease)
+            .emit("customer", self.customer.clone())
+            .emit_coin_dto("downpayment", self.downpayment.clone())
+            .emit_coin_dto("loan", self.loan.principal.clone());
+        Ok(Response::from(
+            batch.into_response(emitter),
+            Closed::default(),
+        ))
+    }
+
+    fn state(self, ica_account: String) -> StateResponse {
+        StateResponse::Opening {
+            downpayment: self.downpayment,
+            loan: self.loan.principal,
+            loan_interest_rate: self.loan.annual_interest_rate,
+            in_progress: OngoingTrx::Cancel { ica_account },
+            last_error: None,
+        }
+    }
+}
+
+fn check_access(
+    customer: &Addr,
+    sender: &Addr,
+    env: &Env,
+    querier: &QuerierWrapper<'_>,
+) -> ContractResult<()> {
+    if sender == customer
+        || platform::contract::query_admin(querier, &env.contract.address)?.as_ref() == Some(sender)
+    {
+        Ok(())
+    } else {
+        Err(ContractError::Unauthorized {})
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use platform::{
+    batch::{Batch, Emit, Emitter},
+    ica::HostAccount,
+};
+use sdk::cosmwasm_std::{DepsMut, Env, MessageInfo, QuerierWrapper, Timestamp};
+
+use crate::{
+    api::{ExecuteMsg, StateResponse},
+    contract::{
+        dex::Account,
+        state::{self, controller, transfer_in, Controller, Response},
+        Contract,
+    },
+    error::{ContractError, ContractResult},
+    event::Type,
+};
+
+use super::{super::swap_task::CoinsNb, transfer_in_init::TransferInInit, Refund};
+
+/// Wait for the funds of a cancelled lease to arrive back from the DEX
+///
+/// Once all funds are at the lease account, the loan is repaid and the rest goes to the customer.
+/// If the LPN there does not cover the loan interest, the lease waits for a cancel message
+/// with the missing amount.
+#[derive(Serialize, Deserialize)]
+pub struct TransferInFinish {
+    refund: Refund,
+    dex_account: Account,
+    remote: CoinsNb,
+    timeout: Timestamp,
+}
+
+impl TransferInFinish {
+    pub(super) fn new(
+        refund: Refund,
+        dex_account: Account,
+        remote: CoinsNb,
+        timeout: Timestamp,
+    ) -> Self {
+        Self {
+            refund,
+            dex_account,
+            remote,
+            timeout,
+        }
+    }
+
+    pub(super) fn enter(
+        &self,
+        now: Timestamp,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<Batch> {
+        transfer_in::setup_alarm(self.refund.time_alarms(querier)?, now)
+    }
+
+    fn try_complete(self, querier: &QuerierWrapper<'_>, env: &Env) -> ContractResult<Response> {
+        if self.refund.received(&env.contract.address, querier)? {
+            self.refund.finish(env, querier)
+        } else {
+            self.await_funds(querier, env)
+        }
+    }
+
+    /// Complete the cancel, or wait for the missing interest without scheduling more alarms
+    fn on_alarm(self, querier: &QuerierWrapper<'_>, env: &Env) -> ContractResult<Response> {
+        if self.refund.received(&env.contract.address, querier)? {
+            match self.refund.finish(env, querier) {
+                Err(ContractError::InsufficientPayment(shortfall)) => {
+                    let emitter = Emitter::of_type(Type::OpeningCancelTransferIn)
+                        .emit("id", env.contract.address.clone())
+                        .emit_coin_dto("shortfall", shortfall);
+                    Ok(Response::from(
+                        Batch::default().into_response(emitter),
+                        self,
+                    ))
+                }
+                res => res,
+            }
+        } else {
+            self.await_funds(querier, env)
+        }
+    }
+
+    fn await_funds(self, querier: &QuerierWrapper<'_>, env: &Env) -> ContractResult<Response> {
+        let emitter = Emitter::of_type(Type::OpeningCancelTransferIn)
+            .emit("id", env.contract.address.clone());
+        if env.block.time >= self.timeout {
+            let back_to_init = TransferInInit::new(self.refund, self.dex_account, self.remote);
+            let batch = back_to_init.enter(env.block.time)?;
+            Ok(Response::from(batch.into_response(emitter), back_to_init))
+        } else {
+            let batch = self.enter(env.block.time, querier)?;
+            Ok(Response::from(batch.into_response(emitter), self))
+        }
+    }
+}
+
+impl Controller for TransferInFinish {
+    fn execute(
+        mut self,
+        deps: &mut DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> ContractResult<Response> {
+        match msg {
+            ExecuteMsg::Repay() => controller::err("repay", deps.api),
+            ExecuteMsg::Close { .. } => controller::err("close", deps.api),
+            ExecuteMsg::AddCollateral() => controller::err("add collateral", deps.api),
+            ExecuteMsg::Cancel() => {
+                self.refund
+                    .check_access(&info.sender, &env, &deps.querier)?;
+                self.refund.add_payment(info.funds)?;
+                self.try_complete(&deps.querier, &env)
+            }
+            ExecuteMsg::BorrowMore { .. } => controller::err("borrow more", deps.api),
+            ExecuteMsg::ClosePosition { .. } => controller::err("close position", deps.api),
+            ExecuteMsg::ChangeClosePolicy { .. } => {
+                controller::err("change close policy", deps.api)
+            }
+            ExecuteMsg::GrantOperator { .. } => controller::err("grant operator", deps.api),
+            ExecuteMsg::RevokeOperator { .. } => controller::err("revoke operator", deps.api),
+            ExecuteMsg::TransferOwnership { .. } => controller::err("transfer ownership", deps.api),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
+            ExecuteMsg::PriceAlarm() => state::ignore_msg(self),
+            ExecuteMsg::TimeAlarm {} => self.on_alarm(&deps.querier, &env),
+        }
+    }
+}
+
+impl Contract for TransferInFinish {
+    fn state(
+        self,
+        _now: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<StateResponse> {
+        Ok(self
+            .refund
+            .state(HostAccount::from(self.dex_account).into()))
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use platform::{
+    batch::{Batch, Emit, Emitter},
+    ica::HostAccount,
+};
+use sdk::cosmwasm_std::{Binary, Deps, Env, QuerierWrapper, Timestamp};
+
+use crate::{
+    api::{dex::ConnectionParams, StateResponse},
+    contract::{
+        dex::{Account, DexConnectable},
+        state::{
+            self, ica_connector::Enterable, ica_post_connector::Postpone, Controller, Response,
+        },
+        Contract,
+    },
+    error::ContractResult,
+    event::Type,
+};
+
+use super::{super::swap_task::CoinsNb, transfer_in_finish::TransferInFinish, Refund};
+
+/// Transfer the funds of a cancelled lease back from the DEX
+///
+/// Only the first `remote` coins, the downpayment followed by the loan, have reached the DEX.
+#[derive(Serialize, Deserialize)]
+pub struct TransferInInit {
+    refund: Refund,
+    dex_account: Account,
+    remote: CoinsNb,
+}
+
+impl TransferInInit {
+    pub(super) fn new(refund: Refund, dex_account: Account, remote: CoinsNb) -> Self {
+        debug_assert!(remote > 0);
+        Self {
+            refund,
+            dex_account,
+            remote,
+        }
+    }
+
+    pub(super) fn enter(&self, now: Timestamp) -> ContractResult<Batch> {
+        let mut sender = self.dex_account.transfer_from(now);
+        sender.send(&self.refund.downpayment)?;
+        if self.remote > 1 {
+            sender.send(&self.refund.loan_out())?;
+        }
+        Ok(sender.into())
+    }
+
+    fn on_response(self, env: &Env, querier: &QuerierWrapper<'_>) -> ContractResult<Response> {
+        let timeout = env.block.time + self.dex().relaying.timeout;
+        let emitter = Emitter::of_type(Type::OpeningCancelTransferIn)
+            .emit("id", env.contract.address.clone());
+        let finish = TransferInFinish::new(self.refund, self.dex_account, self.remote, timeout);
+        let batch = finish.enter(env.block.time, querier)?;
+        Ok(Response::from(batch.into_response(emitter), finish))
+    }
+}
+
+impl DexConnectable for TransferInInit {
+    fn dex(&self) -> &ConnectionParams {
+        self.dex_account.dex()
+    }
+}
+
+impl Enterable for TransferInInit {
+    fn enter(&self, _deps: Deps<'_>, env: Env) -> ContractResult<Batch> {
+        self.enter(env.block.time)
+    }
+}
+
+impl Controller for TransferInInit {
+    fn on_response(self, _data: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        self.on_response(&env, &deps.querier)
+    }
+
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let dex_account = self.dex_account.clone();
+        state::on_error_retry_later(self, dex_account, Type::OpeningCancelTransferIn, deps, env)
+    }
+
+    fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        state::on_timeout_repair_channel(self, Type::OpeningCancelTransferIn, env)
+    }
+}
+
+impl Contract for TransferInInit {
+    fn state(
+        self,
+        _now: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<StateResponse> {
+        Ok(self
+            .refund
+            .state(HostAccount::from(self.dex_account).into()))
+    }
+}
+
+impl Postpone for TransferInInit {
+    fn setup_alarm(&self, when: Timestamp, querier: &QuerierWrapper<'_>) -> ContractResult<Batch> {
+        let time_alarms = self.refund.time_alarms(querier)?;
+        time_alarms.setup_alarm(when).map_err(Into::into)
+    }
+}
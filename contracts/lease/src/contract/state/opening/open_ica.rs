@@ -6,7 +6,7 @@ use oracle::stub::OracleRef;
 
 use crate::{
     api::{
-        dex::ConnectionParams, opening::OngoingTrx, DownpaymentCoin, NewLeaseContract,
+        dex::ConnectionParams, opening::OngoingTrx, DownpaymentCoin, LpnCoin, NewLeaseContract,
         StateResponse,
     },
     contract::{
@@ -18,12 +18,17 @@ use crate::{
     error::ContractResult,
 };
 
-use super::buy_asset::{BuyAsset, Transfer};
+use super::{
+    buy_asset::{BuyAsset, Transfer},
+    cancel::Refund,
+};
 
 #[derive(Serialize, Deserialize)]
 pub struct OpenIcaAccount {
     new_lease: NewLeaseContract,
     downpayment: DownpaymentCoin,
+    #[serde(default)]
+    swap_fee: Option<LpnCoin>,
     loan: OpenLoanRespResult,
     deps: (LppLenderRef, OracleRef),
 }
@@ -32,16 +37,29 @@ impl OpenIcaAccount {
     pub(super) fn new(
         new_lease: NewLeaseContract,
         downpayment: DownpaymentCoin,
+        swap_fee: Option<LpnCoin>,
         loan: OpenLoanRespResult,
         deps: (LppLenderRef, OracleRef),
     ) -> Self {
         Self {
             new_lease,
             downpayment,
+            swap_fee,
             loan,
             deps,
         }
     }
+
+    pub(super) fn into_refund(self) -> Refund {
+        Refund::new(
+            self.new_lease.form.customer,
+            self.downpayment,
+            self.swap_fee,
+            self.loan,
+            self.deps.0,
+            self.new_lease.form.time_alarms,
+        )
+    }
 }
 
 impl IcaConnectee for OpenIcaAccount {
@@ -54,6 +72,7 @@ impl IcaConnectee for OpenIcaAccount {
             self.new_lease.form,
             dex_account,
             self.downpayment,
+            self.swap_fee,
             self.loan,
             self.deps,
         ))
@@ -77,6 +96,7 @@ impl Contract for OpenIcaAccount {
             loan: self.loan.principal,
             loan_interest_rate: self.loan.annual_interest_rate,
             in_progress: OngoingTrx::OpenIcaAccount {},
+            last_error: None,
         })
     }
 }
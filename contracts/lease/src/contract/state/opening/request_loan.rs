@@ -7,7 +7,7 @@ use platform::batch::{Batch, Emit, Emitter};
 use sdk::cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Reply};
 
 use crate::{
-    api::{DownpaymentCoin, NewLeaseContract},
+    api::{DownpaymentCoin, LpnCoin, NewLeaseContract},
     contract::{
         cmd::{OpenLoanReq, OpenLoanReqResult, OpenLoanResp},
         state::{ica_connector::IcaConnector, Controller, Response},
@@ -24,6 +24,8 @@ use super::open_ica::OpenIcaAccount;
 pub struct RequestLoan {
     new_lease: NewLeaseContract,
     downpayment: DownpaymentCoin,
+    #[serde(default)]
+    swap_fee: Option<LpnCoin>,
     deps: (LppLenderRef, OracleRef),
 }
 
@@ -42,11 +44,16 @@ impl RequestLoan {
         let oracle = OracleRef::try_from(new_lease.form.market_price_oracle.clone(), &deps.querier)
             .expect("Market Price Oracle is not deployed, or wrong address is passed!");
 
-        let OpenLoanReqResult { batch, downpayment } = lpp.clone().execute(
+        let OpenLoanReqResult {
+            batch,
+            downpayment,
+            swap_fee,
+        } = lpp.clone().execute(
             OpenLoanReq::new(
                 &new_lease.form.liability,
                 info.funds,
                 new_lease.form.max_ltv,
+                new_lease.form.swap_fee,
                 oracle.clone(),
                 &deps.querier,
             ),
@@ -57,6 +64,7 @@ impl RequestLoan {
             RequestLoan {
                 new_lease,
                 downpayment,
+                swap_fee: Some(swap_fee),
                 deps: (lpp, oracle),
             },
         ))
@@ -78,6 +86,7 @@ impl RequestLoan {
                 let open_ica = IcaConnector::new(OpenIcaAccount::new(
                     self.new_lease,
                     self.downpayment,
+                    self.swap_fee,
                     loan,
                     self.deps,
                 ));
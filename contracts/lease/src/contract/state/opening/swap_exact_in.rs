@@ -8,21 +8,21 @@ use finance::{
     currency::{Group, Symbol},
     zero::Zero,
 };
+use oracle::stub::OracleRef;
 use platform::{batch::Batch, trx};
 use sdk::{cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData, cosmwasm_std::Binary};
-use swap::trx as swap_trx;
 
 #[cfg(debug_assertions)]
 use crate::contract::state::opening::swap_task::IterState;
 use crate::{
     api::{dex::ConnectionParams, StateResponse},
     contract::{
-        dex::{DexConnectable, SwapTrx},
+        dex::{DexConnectable, SwapResp, SwapRoutes, SwapTrx},
         state::{
             self,
             controller::Controller,
             ica_connector::{Enterable, IcaConnector},
-            ica_post_connector::Postpone,
+            ica_post_connector::{PostConnector, Postpone},
             ica_recover::InRecovery,
             opening::swap_task::{CoinVisitor, IterNext, SwapTask as SwapTaskT},
             Response, State,
@@ -40,15 +40,18 @@ use super::{
 #[derive(Serialize, Deserialize)]
 pub(crate) struct SwapExactIn<OutG, SwapTask, const SWAP_OUT_CHAIN: OutChain> {
     spec: SwapTask,
+    #[serde(default)]
+    routes: SwapRoutes,
     _out_g: PhantomData<OutG>,
 }
 
 impl<OutG, SwapTask, const SWAP_OUT_CHAIN: OutChain> SwapExactIn<OutG, SwapTask, SWAP_OUT_CHAIN> {
-    pub(super) fn new(spec: SwapTask) -> Self {
-        Self {
-            spec,
-            _out_g: PhantomData,
-        }
+    pub(super) fn spec(&self) -> &SwapTask {
+        &self.spec
+    }
+
+    pub(super) fn into_spec(self) -> SwapTask {
+        self.spec
     }
 }
 
@@ -57,13 +60,33 @@ where
     OutG: Group,
     SwapTask: SwapTaskT<OutG>,
 {
+    pub(super) fn new(spec: SwapTask, querier: &QuerierWrapper<'_>) -> ContractResult<Self> {
+        let mut resolver = RoutesResolver(
+            SwapRoutes::default(),
+            spec.oracle(),
+            spec.out_currency(),
+            querier,
+        );
+        spec.on_coins(&mut resolver)?;
+        let routes = resolver.0;
+        Ok(Self {
+            spec,
+            routes,
+            _out_g: PhantomData,
+        })
+    }
+
     pub(super) fn enter_state(
         &self,
         _now: Timestamp,
         querier: &QuerierWrapper<'_>,
     ) -> ContractResult<Batch> {
-        let swap_trx = self.spec.dex_account().swap(self.spec.oracle(), querier);
-        // TODO apply nls_swap_fee on the downpayment only!
+        let swap_trx = self.spec.dex_account().swap(
+            self.spec.oracle(),
+            self.spec.max_slippage(),
+            &self.routes,
+            querier,
+        );
 
         let mut builder = TrxBuilder(swap_trx, self.spec.out_currency(), false);
         let _res = self.spec.on_coins(&mut builder)?;
@@ -75,9 +98,19 @@ where
         Ok(builder.0.into())
     }
 
-    fn decode_response(&self, resp: &[u8], spec: &SwapTask) -> ContractResult<CoinDTO<OutG>> {
-        let mut parser =
-            ResponseParser::new(trx::decode_msg_responses(resp)?, self.spec.out_currency());
+    fn decode_response(
+        &self,
+        resp: &[u8],
+        spec: &SwapTask,
+        querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<CoinDTO<OutG>> {
+        let mut parser = ResponseParser::new(
+            trx::decode_msg_responses(resp)?,
+            self.spec.out_currency(),
+            self.spec
+                .dex_account()
+                .swap_resp(self.spec.oracle(), &self.routes, querier),
+        );
         let _res = self.spec.on_coins(&mut parser)?;
         #[cfg(debug_assertions)]
         {
@@ -140,13 +173,19 @@ where
     SwapTask: SwapTaskT<OutG, Result = Response, Error = ContractError>,
     Self: Into<State>,
     IcaConnector<false, InRecovery<Self>>: Into<State>,
+    PostConnector<InRecovery<Self>>: Into<State>,
 {
     fn on_response(self, resp: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
-        // TODO transfer (downpayment - transferred_and_swapped), i.e. the nls_swap_fee to the profit
-        let amount = self.decode_response(resp.as_slice(), &self.spec)?;
+        let amount = self.decode_response(resp.as_slice(), &self.spec, &deps.querier)?;
         self.spec.finish(amount, &deps.querier, env)
     }
 
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let state_label = self.spec.label();
+        let dex_account = self.spec.dex_account().clone();
+        state::on_error_retry_later(self, dex_account, state_label, deps, env)
+    }
+
     fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         let state_label = self.spec.label();
         state::on_timeout_repair_channel(self, state_label, env)
@@ -175,6 +214,27 @@ where
     }
 }
 
+struct RoutesResolver<'a>(
+    SwapRoutes,
+    &'a OracleRef,
+    Symbol<'a>,
+    &'a QuerierWrapper<'a>,
+);
+impl<'a> CoinVisitor for RoutesResolver<'a> {
+    type Result = IterNext;
+    type Error = ContractError;
+
+    fn visit<G>(&mut self, coin: &CoinDTO<G>) -> Result<Self::Result, Self::Error>
+    where
+        G: Group,
+    {
+        if coin.ticker() != self.2 {
+            self.0.resolve(self.1, coin, self.2, self.3)?;
+        }
+        Ok(IterNext::Continue)
+    }
+}
+
 struct TrxBuilder<'a>(SwapTrx<'a>, Symbol<'a>, bool);
 impl<'a> TrxBuilder<'a> {
     #[cfg(debug_assertions)]
@@ -198,10 +258,10 @@ impl<'a> CoinVisitor for TrxBuilder<'a> {
     }
 }
 
-struct ResponseParser<'a, I>(I, Symbol<'a>, Amount, bool);
+struct ResponseParser<'a, I>(I, Symbol<'a>, Amount, bool, SwapResp<'a>);
 impl<'a, I> ResponseParser<'a, I> {
-    fn new(msgs: I, out: Symbol<'a>) -> Self {
-        Self(msgs, out, Amount::ZERO, false)
+    fn new(msgs: I, out: Symbol<'a>, swap_resp: SwapResp<'a>) -> Self {
+        Self(msgs, out, Amount::ZERO, false, swap_resp)
     }
     fn total_amount(&self) -> Amount {
         self.2
@@ -216,7 +276,7 @@ where
     I: Iterator<Item = MsgData>,
 {
     type Result = IterNext;
-    type Error = swap::error::Error;
+    type Error = ContractError;
 
     fn visit<G>(&mut self, coin: &CoinDTO<G>) -> Result<Self::Result, Self::Error>
     where
@@ -225,7 +285,7 @@ where
         if coin.ticker() == self.1 {
             self.2 += coin.amount();
         } else {
-            self.2 += swap_trx::exact_amount_in_resp(&mut self.0)?;
+            self.2 += self.4.exact_amount_in(&mut self.0, coin, self.1)?;
             self.3 = true;
         }
         Ok(IterNext::Continue)
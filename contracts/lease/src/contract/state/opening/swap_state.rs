@@ -2,10 +2,10 @@ use cosmwasm_std::{QuerierWrapper, Timestamp};
 
 use crate::{api::StateResponse, error::ContractError};
 
-pub(super) struct TransferOutState {}
-pub(super) struct SwapState {}
+pub(in crate::contract::state) struct TransferOutState {}
+pub(in crate::contract::state) struct SwapState {}
 
-pub(super) trait ContractInSwap<State>
+pub(in crate::contract::state) trait ContractInSwap<State>
 where
     Self: Sized,
 {
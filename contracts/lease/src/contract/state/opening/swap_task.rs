@@ -2,6 +2,7 @@ use cosmwasm_std::{Env, QuerierWrapper};
 use finance::{
     coin::CoinDTO,
     currency::{Group, Symbol},
+    percent::Percent,
 };
 use oracle::stub::OracleRef;
 use timealarms::stub::TimeAlarmsRef;
@@ -9,9 +10,9 @@ use timealarms::stub::TimeAlarmsRef;
 use crate::contract::dex::Account;
 
 pub(super) type CoinsNb = u8;
-pub(super) type OutChain = bool;
+pub(in crate::contract::state) type OutChain = bool;
 // pub(super) const LOCAL_OUT_CHAIN: OutChain = true;
-pub(super) const REMOTE_OUT_CHAIN: OutChain = false;
+pub(in crate::contract::state) const REMOTE_OUT_CHAIN: OutChain = false;
 
 /// Specification of a swap process
 ///
@@ -24,6 +25,7 @@ pub(crate) trait SwapTask<OutG> {
     fn label(&self) -> Self::Label;
     fn dex_account(&self) -> &Account;
     fn oracle(&self) -> &OracleRef;
+    fn max_slippage(&self) -> Percent;
     fn time_alarm(&self, querier: &QuerierWrapper<'_>) -> Result<TimeAlarmsRef, Self::Error>;
     fn out_currency(&self) -> Symbol<'_>;
 
@@ -4,7 +4,11 @@ use cosmwasm_std::{Binary, Deps, Env, QuerierWrapper, Timestamp};
 use platform::batch::{Batch, Emitter};
 use serde::{Deserialize, Serialize};
 
-use finance::{coin::CoinDTO, currency::Group, zero::Zero};
+use finance::{
+    coin::{self, Amount, CoinDTO},
+    currency::{Group, Symbol},
+    zero::Zero,
+};
 
 use crate::{
     api::StateResponse,
@@ -48,12 +52,17 @@ where
     OutG: Group,
     SwapTask: SwapTaskT<OutG>,
 {
-    pub(super) fn new(spec: SwapTask) -> Self {
+    pub(in crate::contract::state) fn new(spec: SwapTask) -> Self {
         let first_index = Default::default();
         let last_coin_index = Self::last_coin_index(&spec);
         Self::new_with_index(spec, first_index, last_coin_index)
     }
 
+    /// Break into the swap task and the index of the coin being transferred
+    pub(super) fn into_parts(self) -> (SwapTask, CoinsNb) {
+        (self.spec, self.coin_index)
+    }
+
     fn next(self) -> Self {
         debug_assert!(!self.last_coin());
 
@@ -109,6 +118,13 @@ where
         debug_assert_eq!(iter_state == IterState::Complete, self.last_coin());
         Ok(sender.0.into())
     }
+
+    /// The total amount of the coins provided none of them should be swapped
+    fn total_if_no_swap(&self) -> Option<Amount> {
+        let mut total = OutCoinsTotal(self.spec.out_currency(), Some(Amount::ZERO));
+        let _res = never::safe_unwrap(self.spec.on_coins(&mut total));
+        total.1
+    }
 }
 
 impl<OutG, SwapTask, const SWAP_OUT_CHAIN: OutChain> Enterable
@@ -126,14 +142,29 @@ impl<OutG, SwapTask, const SWAP_OUT_CHAIN: OutChain> Controller
     for TransferOut<OutG, SwapTask, SWAP_OUT_CHAIN>
 where
     OutG: Group,
-    SwapTask: SwapTaskT<OutG>,
+    SwapTask: SwapTaskT<OutG, Result = Response, Error = ContractError>,
     Self: Into<State>,
     SwapExactIn<OutG, SwapTask, SWAP_OUT_CHAIN>: Into<State>,
 {
     fn on_response(self, _resp: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         let emitter = Emitter::of_type(self.spec.label());
         if self.last_coin() {
-            let swap = SwapExactIn::new(self.spec);
+            if let Some(total) = self.total_if_no_swap() {
+                let amount = coin::from_amount_ticker(total, self.spec.out_currency())?;
+                let Response {
+                    cw_response,
+                    next_state,
+                    ledger,
+                } = self.spec.finish(amount, &deps.querier, env)?;
+
+                return Ok(Response {
+                    cw_response: cw_response.add_event(emitter.into()),
+                    next_state,
+                    ledger,
+                });
+            }
+
+            let swap = SwapExactIn::new(self.spec, &deps.querier)?;
             let batch = swap.enter_state(env.block.time, &deps.querier)?;
             let resp = batch.into_response(emitter);
 
@@ -146,6 +177,12 @@ where
         }
     }
 
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        // the coin is refunded on a failed transfer so it is safe to send it again
+        let state_label = self.spec.label();
+        state::on_error_retry(self, state_label, deps, env)
+    }
+
     fn on_timeout(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         let state_label = self.spec.label();
         state::on_timeout_retry(self, state_label, deps, env)
@@ -162,6 +199,25 @@ where
     }
 }
 
+struct OutCoinsTotal<'a>(Symbol<'a>, Option<Amount>);
+impl<'a> CoinVisitor for OutCoinsTotal<'a> {
+    type Result = IterNext;
+    type Error = Never;
+
+    fn visit<G>(&mut self, coin: &CoinDTO<G>) -> Result<Self::Result, Self::Error>
+    where
+        G: Group,
+    {
+        if coin.ticker() == self.0 {
+            self.1 = self.1.map(|total| total + coin.amount());
+            Ok(IterNext::Continue)
+        } else {
+            self.1 = None;
+            Ok(IterNext::Stop)
+        }
+    }
+}
+
 #[derive(Default)]
 struct Counter(Option<CoinsNb>);
 impl Counter {
@@ -0,0 +1,86 @@
+use sdk::{
+    cosmwasm_std::{Addr, StdResult, Storage},
+    cw_storage_plus::Map,
+};
+
+use crate::error::{ContractError, ContractResult};
+
+const OPERATORS_DB: Map<'static, Addr, ()> = Map::new("operators");
+
+pub(super) fn grant(storage: &mut dyn Storage, operator: &Addr) -> StdResult<()> {
+    OPERATORS_DB.save(storage, operator.clone(), &())
+}
+
+pub(super) fn revoke(storage: &mut dyn Storage, operator: &Addr) {
+    OPERATORS_DB.remove(storage, operator.clone())
+}
+
+pub(super) fn revoke_all(storage: &mut dyn Storage) {
+    OPERATORS_DB.clear(storage)
+}
+
+/// Check whether the sender is the customer, or an operator acting on their behalf
+pub(super) fn check_access(
+    storage: &dyn Storage,
+    customer: &Addr,
+    sender: &Addr,
+) -> ContractResult<()> {
+    if sender == customer || OPERATORS_DB.has(storage, sender.clone()) {
+        Ok(())
+    } else {
+        Err(ContractError::Unauthorized {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::{testing::MockStorage, Addr};
+
+    use crate::error::ContractError;
+
+    const CUSTOMER: &str = "customer";
+    const OPERATOR: &str = "operator";
+
+    #[test]
+    fn customer_only() {
+        let storage = MockStorage::new();
+        let customer = Addr::unchecked(CUSTOMER);
+
+        assert_eq!(Ok(()), super::check_access(&storage, &customer, &customer));
+        assert_eq!(
+            Err(ContractError::Unauthorized {}),
+            super::check_access(&storage, &customer, &Addr::unchecked(OPERATOR))
+        );
+    }
+
+    #[test]
+    fn grant_revoke() {
+        let mut storage = MockStorage::new();
+        let customer = Addr::unchecked(CUSTOMER);
+        let operator = Addr::unchecked(OPERATOR);
+
+        super::grant(&mut storage, &operator).unwrap();
+        assert_eq!(Ok(()), super::check_access(&storage, &customer, &operator));
+        assert_eq!(Ok(()), super::check_access(&storage, &customer, &customer));
+
+        super::revoke(&mut storage, &operator);
+        assert_eq!(
+            Err(ContractError::Unauthorized {}),
+            super::check_access(&storage, &customer, &operator)
+        );
+    }
+
+    #[test]
+    fn revoke_all() {
+        let mut storage = MockStorage::new();
+        let customer = Addr::unchecked(CUSTOMER);
+        let operator = Addr::unchecked(OPERATOR);
+
+        super::grant(&mut storage, &operator).unwrap();
+        super::revoke_all(&mut storage);
+        assert_eq!(
+            Err(ContractError::Unauthorized {}),
+            super::check_access(&storage, &customer, &operator)
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use currency::native::Nls;
+use platform::batch::{Batch, Emit, Emitter};
+use sdk::{
+    cosmwasm_ext::Response as CwResponse,
+    cosmwasm_std::{Addr, DepsMut, Env},
+};
+
+use crate::{
+    api::LeaserExecuteMsg,
+    error::{ContractError, ContractResult},
+    event::Type,
+    lease::LeaseDTO,
+};
+
+use super::operators;
+
+/// Transfer the ownership of the lease to a new customer
+///
+/// Only the customer may transfer the ownership. The operators are revoked, and the leaser,
+/// that is the administrator of the lease, is notified to update its index of leases.
+pub(super) fn transfer(
+    lease: &mut LeaseDTO,
+    deps: &mut DepsMut<'_>,
+    env: &Env,
+    sender: &Addr,
+    new_customer: Addr,
+) -> ContractResult<CwResponse> {
+    if sender != lease.customer {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_customer = deps.api.addr_validate(new_customer.as_str())?;
+    let leaser = platform::contract::query_admin(&deps.querier, &env.contract.address)?
+        .ok_or_else(|| ContractError::unsupported_operation("transfer ownership"))?;
+
+    operators::revoke_all(deps.storage);
+
+    let mut batch = Batch::default();
+    batch.schedule_execute_wasm_no_reply::<_, Nls>(
+        &leaser,
+        LeaserExecuteMsg::ChangeLeaseCustomer {
+            customer: lease.customer.clone(),
+            new_customer: new_customer.clone(),
+        },
+        None,
+    )?;
+
+    let emitter = Emitter::of_type(Type::OwnershipTransfer)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", lease.customer.clone())
+        .emit("new-customer", new_customer.clone());
+    lease.customer = new_customer;
+
+    Ok(batch.into_response(emitter))
+}
@@ -0,0 +1,137 @@
+use cosmwasm_std::{Binary, DepsMut, MessageInfo};
+use serde::{Deserialize, Serialize};
+
+use platform::{
+    batch::{Batch, Emitter},
+    ica::HostAccount,
+};
+use sdk::cosmwasm_std::{Deps, Env, QuerierWrapper, Timestamp};
+
+use crate::{
+    api::{dex::ConnectionParams, paid::ClosingTrx, ExecuteMsg, StateResponse},
+    contract::{
+        dex::DexConnectable,
+        state::{
+            self, closed::Closed, controller, ica_connector::Enterable,
+            ica_post_connector::Postpone, Controller, Response,
+        },
+        Contract, Lease,
+    },
+    error::ContractResult,
+    event::Type,
+};
+
+use super::Active;
+
+/// Send the lease amount from the DEX account to an address of the customer at the DEX network
+///
+/// The lease is closed as soon as the send is acknowledged. If the send fails, the lease
+/// gets back to paid, so the customer may close it again, with or without a DEX recipient.
+#[derive(Serialize, Deserialize)]
+pub struct DexSend {
+    lease: Lease,
+    recipient: HostAccount,
+}
+
+impl DexSend {
+    pub(in crate::contract::state) fn new(lease: Lease, recipient: HostAccount) -> Self {
+        Self { lease, recipient }
+    }
+
+    pub(in crate::contract::state) fn enter(&self) -> ContractResult<Batch> {
+        self.lease
+            .dex
+            .send_on_dex(self.recipient.clone(), &self.lease.lease.amount)
+    }
+
+    fn on_response(self, env: &Env, querier: &QuerierWrapper<'_>) -> ContractResult<Response> {
+        let closed = Closed::default();
+        let emitter = closed
+            .emit_ok(env, &self.lease.lease)
+            .emit("dex-recipient", self.recipient);
+        let record = closed.record(&self.lease.lease);
+        let batch = closed.enter_state_delivered(self.lease.lease, querier)?;
+        Ok(Response::from(batch.into_response(emitter), closed).record(record))
+    }
+}
+
+impl DexConnectable for DexSend {
+    fn dex(&self) -> &ConnectionParams {
+        self.lease.dex()
+    }
+}
+
+impl Enterable for DexSend {
+    fn enter(&self, _deps: Deps<'_>, _env: Env) -> ContractResult<Batch> {
+        self.enter()
+    }
+}
+
+impl Controller for DexSend {
+    fn execute(
+        self,
+        deps: &mut DepsMut<'_>,
+        _env: Env,
+        _info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> ContractResult<Response> {
+        match msg {
+            ExecuteMsg::Repay() => controller::err("repay", deps.api),
+            ExecuteMsg::Close { .. } => controller::err("close", deps.api),
+            ExecuteMsg::AddCollateral() => controller::err("add collateral", deps.api),
+            ExecuteMsg::Cancel() => controller::err("cancel", deps.api),
+            ExecuteMsg::BorrowMore { .. } => controller::err("borrow more", deps.api),
+            ExecuteMsg::ClosePosition { .. } => controller::err("close position", deps.api),
+            ExecuteMsg::ChangeClosePolicy { .. } => {
+                controller::err("change close policy", deps.api)
+            }
+            ExecuteMsg::GrantOperator { .. } => controller::err("grant operator", deps.api),
+            ExecuteMsg::RevokeOperator { .. } => controller::err("revoke operator", deps.api),
+            ExecuteMsg::TransferOwnership { .. } => controller::err("transfer ownership", deps.api),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
+            ExecuteMsg::PriceAlarm() => state::ignore_msg(self),
+            ExecuteMsg::TimeAlarm {} => state::ignore_msg(self),
+        }
+    }
+
+    fn on_response(self, _data: Binary, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        self.on_response(&env, &deps.querier)
+    }
+
+    fn on_error(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let emitter = Emitter::of_type(Type::ClosingDexSend)
+            .emit("id", env.contract.address)
+            .emit("error", "BackToPaid")
+            .emit("dex-recipient", self.recipient);
+        Ok(Response::from(
+            Batch::default().into_response(emitter),
+            Active::new(self.lease),
+        ))
+    }
+
+    fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        state::on_timeout_repair_channel(self, Type::ClosingDexSend, env)
+    }
+}
+
+impl Contract for DexSend {
+    fn state(
+        self,
+        _now: Timestamp,
+        _querier: &QuerierWrapper<'_>,
+    ) -> ContractResult<StateResponse> {
+        Ok(StateResponse::Paid {
+            amount: self.lease.lease.amount,
+            in_progress: Some(ClosingTrx::DexSend),
+            last_error: None,
+        })
+    }
+}
+
+impl Postpone for DexSend {
+    fn setup_alarm(&self, when: Timestamp, _querier: &QuerierWrapper<'_>) -> ContractResult<Batch> {
+        let time_alarms = self.lease.lease.time_alarms.clone();
+        time_alarms.setup_alarm(when).map_err(Into::into)
+    }
+}
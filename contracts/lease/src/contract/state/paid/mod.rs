@@ -1,18 +1,20 @@
 use cosmwasm_std::{QuerierWrapper, Timestamp};
 use serde::{Deserialize, Serialize};
 
-use sdk::cosmwasm_std::{DepsMut, Env, MessageInfo};
+use platform::ica::HostAccount;
+use sdk::cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Storage};
 
 use crate::{
-    api::{ExecuteMsg, StateResponse},
-    contract::{state, Contract, Lease},
+    api::{dex::RelayingParams, ExecuteMsg, StateResponse},
+    contract::{dex::DexConnectable, state, Contract, Lease},
     error::ContractResult,
 };
 
-use super::{controller, Controller, Response};
+use super::{controller, fee_reserve, operators, ownership, Controller, Response};
 
-use self::transfer_in_init::TransferInInit;
+use self::{dex_send::DexSend, transfer_in_init::TransferInInit};
 
+pub mod dex_send;
 pub mod transfer_in_finish;
 pub mod transfer_in_init;
 
@@ -25,25 +27,72 @@ impl Active {
     pub(in crate::contract::state) fn new(lease: Lease) -> Self {
         Self { lease }
     }
+
+    pub(in crate::contract::state) fn update_dex_relaying(&mut self, relaying: RelayingParams) {
+        self.lease.update_dex_relaying(relaying)
+    }
+
+    fn send_on_dex(
+        self,
+        storage: &dyn Storage,
+        sender: &Addr,
+        dex_recipient: String,
+    ) -> ContractResult<Response> {
+        operators::check_access(storage, &self.lease.lease.customer, sender)?;
+
+        let dex_send = DexSend::new(self.lease, HostAccount::try_from(dex_recipient)?);
+        let batch = dex_send.enter()?;
+        Ok(Response::from(batch, dex_send))
+    }
 }
 
 impl Controller for Active {
     fn execute(
-        self,
+        mut self,
         deps: &mut DepsMut<'_>,
         env: Env,
-        _info: MessageInfo,
+        info: MessageInfo,
         msg: ExecuteMsg,
     ) -> ContractResult<Response> {
         match msg {
             ExecuteMsg::Repay() => controller::err("repay", deps.api),
-            ExecuteMsg::Close() => {
-                let transfer_in = TransferInInit::new(self.lease);
-                let batch = transfer_in.enter(env.block.time)?;
-                Ok(Response::from(batch, transfer_in))
+            ExecuteMsg::AddCollateral() => controller::err("add collateral", deps.api),
+            ExecuteMsg::Cancel() => controller::err("cancel", deps.api),
+            ExecuteMsg::BorrowMore { .. } => controller::err("borrow more", deps.api),
+            ExecuteMsg::ClosePosition { .. } => controller::err("close position", deps.api),
+            ExecuteMsg::ChangeClosePolicy { .. } => {
+                controller::err("change close policy", deps.api)
+            }
+            ExecuteMsg::GrantOperator { .. } => controller::err("grant operator", deps.api),
+            ExecuteMsg::RevokeOperator { .. } => controller::err("revoke operator", deps.api),
+            ExecuteMsg::TransferOwnership { new_customer } => {
+                let resp = ownership::transfer(
+                    &mut self.lease.lease,
+                    deps,
+                    &env,
+                    &info.sender,
+                    new_customer,
+                )?;
+                Ok(Response::from(resp, self))
+            }
+            ExecuteMsg::Close { dex_recipient } => {
+                fee_reserve::check(
+                    &self.lease.lease.addr,
+                    &self.lease.dex().relaying,
+                    &deps.querier,
+                )?;
+                if let Some(dex_recipient) = dex_recipient {
+                    self.send_on_dex(deps.storage, &info.sender, dex_recipient)
+                } else {
+                    let transfer_in = TransferInInit::new(self.lease);
+                    let batch = transfer_in.enter(env.block.time)?;
+                    Ok(Response::from(batch, transfer_in))
+                }
             }
             ExecuteMsg::PriceAlarm() => state::ignore_msg(self),
             ExecuteMsg::TimeAlarm {} => state::ignore_msg(self),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
         }
     }
 }
@@ -57,6 +106,7 @@ impl Contract for Active {
         Ok(StateResponse::Paid {
             amount: self.lease.lease.amount,
             in_progress: None,
+            last_error: None,
         })
     }
 }
@@ -35,22 +35,23 @@ impl TransferInFinish {
         let received =
             transfer_in::check_received(&self.lease.lease.amount, &env.contract.address, querier)?;
 
-        let (next_state, cw_resp): (State, _) = if received {
+        if received {
             let closed = Closed::default();
             let emitter = closed.emit_ok(env, &self.lease.lease);
+            let record = closed.record(&self.lease.lease);
             let batch = closed.enter_state(self.lease.lease, querier)?;
-            (closed.into(), batch.into_response(emitter))
+            return Ok(Response::from(batch.into_response(emitter), closed).record(record));
+        }
+
+        let emitter = self.emit_ok();
+        let (next_state, cw_resp): (State, _) = if env.block.time >= self.timeout {
+            let back_to_init = TransferInInit::new(self.lease);
+            let batch = back_to_init.enter(env.block.time)?;
+            (back_to_init.into(), batch.into_response(emitter))
         } else {
-            let emitter = self.emit_ok();
-            if env.block.time >= self.timeout {
-                let back_to_init = TransferInInit::new(self.lease);
-                let batch = back_to_init.enter(env.block.time)?;
-                (back_to_init.into(), batch.into_response(emitter))
-            } else {
-                let batch =
-                    transfer_in::setup_alarm(self.lease.lease.time_alarms.clone(), env.block.time)?;
-                (self.into(), batch.into_response(emitter))
-            }
+            let batch =
+                transfer_in::setup_alarm(self.lease.lease.time_alarms.clone(), env.block.time)?;
+            (self.into(), batch.into_response(emitter))
         };
         Ok(Response::from(cw_resp, next_state))
     }
@@ -76,7 +77,19 @@ impl Controller for TransferInFinish {
     ) -> ContractResult<Response> {
         match msg {
             ExecuteMsg::Repay() => controller::err("repay", deps.api),
-            ExecuteMsg::Close() => controller::err("close", deps.api),
+            ExecuteMsg::Close { .. } => controller::err("close", deps.api),
+            ExecuteMsg::AddCollateral() => controller::err("add collateral", deps.api),
+            ExecuteMsg::Cancel() => controller::err("cancel", deps.api),
+            ExecuteMsg::BorrowMore { .. } => controller::err("borrow more", deps.api),
+            ExecuteMsg::ClosePosition { .. } => controller::err("close position", deps.api),
+            ExecuteMsg::ChangeClosePolicy { .. } => {
+                controller::err("change close policy", deps.api)
+            }
+            ExecuteMsg::GrantOperator { .. } => controller::err("grant operator", deps.api),
+            ExecuteMsg::RevokeOperator { .. } => controller::err("revoke operator", deps.api),
+            ExecuteMsg::TransferOwnership { .. } => controller::err("transfer ownership", deps.api),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
             ExecuteMsg::PriceAlarm() => state::ignore_msg(self),
             ExecuteMsg::TimeAlarm {} => self.on_alarm(&deps.querier, &env),
         }
@@ -92,6 +105,7 @@ impl Contract for TransferInFinish {
         Ok(StateResponse::Paid {
             amount: self.lease.lease.amount,
             in_progress: Some(ClosingTrx::TransferInFinish),
+            last_error: None,
         })
     }
 }
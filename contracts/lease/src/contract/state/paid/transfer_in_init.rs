@@ -7,7 +7,7 @@ use sdk::cosmwasm_std::{Deps, Env, QuerierWrapper, Timestamp};
 use crate::{
     api::{dex::ConnectionParams, paid::ClosingTrx, ExecuteMsg, StateResponse},
     contract::{
-        dex::{self, DexConnectable},
+        dex::DexConnectable,
         state::{
             self, controller, ica_connector::Enterable, ica_post_connector::Postpone, Controller,
             Response,
@@ -30,14 +30,15 @@ impl TransferInInit {
         Self { lease }
     }
 
-    pub(super) fn enter(&self, now: Timestamp) -> ContractResult<Batch> {
+    pub(in crate::contract::state) fn enter(&self, now: Timestamp) -> ContractResult<Batch> {
         let mut sender = self.lease.dex.transfer_from(now);
         sender.send(&self.lease.lease.amount)?;
         Ok(sender.into())
     }
 
     fn on_response(self, env: &Env, querier: &QuerierWrapper<'_>) -> ContractResult<Response> {
-        let finish = TransferInFinish::new(self.lease, env.block.time + dex::IBC_TIMEOUT);
+        let timeout = env.block.time + self.lease.dex().relaying.timeout;
+        let finish = TransferInFinish::new(self.lease, timeout);
         finish.try_complete(querier, env)
     }
 }
@@ -64,7 +65,19 @@ impl Controller for TransferInInit {
     ) -> ContractResult<Response> {
         match msg {
             ExecuteMsg::Repay() => controller::err("repay", deps.api),
-            ExecuteMsg::Close() => controller::err("close", deps.api),
+            ExecuteMsg::Close { .. } => controller::err("close", deps.api),
+            ExecuteMsg::AddCollateral() => controller::err("add collateral", deps.api),
+            ExecuteMsg::Cancel() => controller::err("cancel", deps.api),
+            ExecuteMsg::BorrowMore { .. } => controller::err("borrow more", deps.api),
+            ExecuteMsg::ClosePosition { .. } => controller::err("close position", deps.api),
+            ExecuteMsg::ChangeClosePolicy { .. } => {
+                controller::err("change close policy", deps.api)
+            }
+            ExecuteMsg::GrantOperator { .. } => controller::err("grant operator", deps.api),
+            ExecuteMsg::RevokeOperator { .. } => controller::err("revoke operator", deps.api),
+            ExecuteMsg::TransferOwnership { .. } => controller::err("transfer ownership", deps.api),
+            ExecuteMsg::UpdateDexRelaying(_) => controller::err("update dex relaying", deps.api),
+            ExecuteMsg::TopUpFeeReserve() => controller::err("top up fee reserve", deps.api),
             ExecuteMsg::PriceAlarm() => state::ignore_msg(self),
             ExecuteMsg::TimeAlarm {} => state::ignore_msg(self),
         }
@@ -74,6 +87,11 @@ impl Controller for TransferInInit {
         self.on_response(&env, &deps.querier)
     }
 
+    fn on_error(self, deps: Deps<'_>, env: Env) -> ContractResult<Response> {
+        let dex_account = self.lease.dex.clone();
+        state::on_error_retry_later(self, dex_account, Type::ClosingTransferIn, deps, env)
+    }
+
     fn on_timeout(self, _deps: Deps<'_>, env: Env) -> ContractResult<Response> {
         state::on_timeout_repair_channel(self, Type::ClosingTransferIn, env)
     }
@@ -88,6 +106,7 @@ impl Contract for TransferInInit {
         Ok(StateResponse::Paid {
             amount: self.lease.lease.amount,
             in_progress: Some(ClosingTrx::TransferInInit),
+            last_error: None,
         })
     }
 }
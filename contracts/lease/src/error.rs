@@ -2,7 +2,8 @@ use std::any::type_name;
 
 use thiserror::Error;
 
-use finance::error::Error as FinanceError;
+use currency::native::Nls;
+use finance::{coin::Coin, error::Error as FinanceError};
 use lpp::error::ContractError as LppError;
 use oracle::error::ContractError as OracleError;
 use platform::error::Error as PlatformError;
@@ -11,7 +12,7 @@ use sdk::cosmwasm_std::StdError;
 use swap::error::Error as SwapError;
 use timealarms::error::ContractError as TimeAlarmsError;
 
-use crate::api::PaymentCoin;
+use crate::api::{LeaseCoin, LpnCoin, PaymentCoin};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -48,12 +49,39 @@ pub enum ContractError {
     #[error("[Lease] Insufficient payment amount {0}")]
     InsufficientPayment(PaymentCoin),
 
+    #[error("[Lease] Invalid position close amount {0}")]
+    InvalidPositionCloseAmount(LeaseCoin),
+
+    #[error("[Lease] Selling the whole lease amount would not repay the loan in full")]
+    InsufficientPositionValue(),
+
+    #[error("[Lease] The stop-loss price should be below, and the take-profit price above, the current price")]
+    InvalidClosePolicy(),
+
+    #[error("[Lease] The borrowed amount {0} does not exceed the due margin interest")]
+    InvalidBorrowAmount(LpnCoin),
+
+    #[error("[Lease] The underlying loan has overdue interest")]
+    LoanOverdue(),
+
+    #[error("[Lease] No liquidity in the LPP for the requested amount")]
+    NoLiquidity(),
+
+    #[error("[Lease] The loan increase would exceed the initial LTV")]
+    LoanIncreaseAboveInitialLtv(),
+
     #[error("[Lease] The underlying loan is not fully repaid")]
     LoanNotPaid(),
 
     #[error("[Lease] The underlying loan is closed")]
     LoanClosed(),
 
+    #[error("[Lease] The fee reserve {reserve} falls short of {required}, it should be topped up")]
+    InsufficientFeeReserve {
+        reserve: Coin<Nls>,
+        required: Coin<Nls>,
+    },
+
     #[error("[Lease] Invalid parameters: {0}")]
     InvalidParameters(String),
 
@@ -7,10 +7,27 @@ pub enum Type {
     BuyLpn,
     RepaymentTransferIn,
     PaidActive,
+    PositionCloseSwap,
+    PositionCloseTransferIn,
+    PositionClose,
+    PositionCloseRetry,
+    PositionCloseStop,
     ClosingTransferIn,
+    ClosingDexSend,
     Closed,
     LiquidationWarning,
     Liquidation,
+    ClosePolicyChange,
+    AddCollateral,
+    ClosePolicyTrigger,
+    ClosePolicyCancel,
+    BorrowMore,
+    OpeningCancelTransferIn,
+    OpeningCancel,
+    OperatorGrant,
+    OperatorRevoke,
+    OwnershipTransfer,
+    FeeReserveTopUp,
 }
 
 impl Type {
@@ -25,10 +42,27 @@ impl Type {
             Self::BuyLpn => "ls-repay-buy-lpn",
             Self::RepaymentTransferIn => "ls-repay-transfer-in",
             Self::PaidActive => "ls-repay",
+            Self::PositionCloseSwap => "ls-close-position-swap",
+            Self::PositionCloseTransferIn => "ls-close-position-transfer-in",
+            Self::PositionClose => "ls-close-position",
+            Self::PositionCloseRetry => "ls-close-position-retry",
+            Self::PositionCloseStop => "ls-close-position-stop",
             Self::ClosingTransferIn => "ls-close-transfer-in",
+            Self::ClosingDexSend => "ls-close-dex-send",
             Self::Closed => "ls-close",
             Self::LiquidationWarning => "ls-liquidation-warning",
             Self::Liquidation => "ls-liquidation",
+            Self::ClosePolicyChange => "ls-close-policy",
+            Self::AddCollateral => "ls-add-collateral",
+            Self::ClosePolicyTrigger => "ls-close-policy-trigger",
+            Self::ClosePolicyCancel => "ls-close-policy-cancel",
+            Self::BorrowMore => "ls-borrow-more",
+            Self::OpeningCancelTransferIn => "ls-open-cancel-transfer-in",
+            Self::OpeningCancel => "ls-open-cancel",
+            Self::OperatorGrant => "ls-operator-grant",
+            Self::OperatorRevoke => "ls-operator-revoke",
+            Self::OwnershipTransfer => "ls-transfer-ownership",
+            Self::FeeReserveTopUp => "ls-fee-reserve-top-up",
         }
     }
 }
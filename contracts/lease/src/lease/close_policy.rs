@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+
+use finance::{currency::Currency, price::Price};
+use lpp::stub::lender::LppLender as LppLenderTrait;
+use oracle::stub::Oracle as OracleTrait;
+use platform::generate_ids;
+use profit::stub::Profit as ProfitTrait;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
+
+use crate::{
+    api::LeasePrice,
+    error::{ContractError, ContractResult},
+    lease::Lease,
+};
+
+/// The prices of the lease asset, in LPN, at which the position gets fully closed
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub(crate) struct ClosePolicy {
+    stop_loss: Option<LeasePrice>,
+    take_profit: Option<LeasePrice>,
+}
+
+impl ClosePolicy {
+    pub fn new(stop_loss: Option<LeasePrice>, take_profit: Option<LeasePrice>) -> Self {
+        Self {
+            stop_loss,
+            take_profit,
+        }
+    }
+
+    /// Narrow the price alarm bounds so it fires on crossing any of the policy prices
+    ///
+    /// The upper bound is dropped if it would not remain above the lower one. That may
+    /// happen only if the price has already fallen to the stop-loss, which triggers
+    /// the alarm anyway.
+    pub(super) fn bound_alarm<Asset, Lpn>(
+        &self,
+        below: Price<Asset, Lpn>,
+        above: Option<Price<Asset, Lpn>>,
+    ) -> ContractResult<(Price<Asset, Lpn>, Option<Price<Asset, Lpn>>)>
+    where
+        Asset: Currency,
+        Lpn: Currency,
+    {
+        let below = match self.stop_loss()? {
+            Some(stop_loss) if below < stop_loss => stop_loss,
+            _ => below,
+        };
+        let above = match (above, self.take_profit()?) {
+            (Some(above), Some(take_profit)) if take_profit < above => Some(take_profit),
+            (above, take_profit) => above.or(take_profit),
+        };
+
+        Ok((below, above.filter(|above| &below < above)))
+    }
+
+    /// Drop the policy price crossed on the `trigger`
+    fn cancel(&mut self, trigger: ClosePolicyTrigger) {
+        match trigger {
+            ClosePolicyTrigger::StopLoss => self.stop_loss = None,
+            ClosePolicyTrigger::TakeProfit => self.take_profit = None,
+        }
+    }
+
+    pub(super) fn trigger<Asset, Lpn>(
+        &self,
+        price: Price<Asset, Lpn>,
+    ) -> ContractResult<Option<ClosePolicyTrigger>>
+    where
+        Asset: Currency,
+        Lpn: Currency,
+    {
+        if self
+            .stop_loss()?
+            .map_or(false, |stop_loss| price <= stop_loss)
+        {
+            Ok(Some(ClosePolicyTrigger::StopLoss))
+        } else if self
+            .take_profit()?
+            .map_or(false, |take_profit| take_profit <= price)
+        {
+            Ok(Some(ClosePolicyTrigger::TakeProfit))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn stop_loss<Asset, Lpn>(&self) -> ContractResult<Option<Price<Asset, Lpn>>>
+    where
+        Asset: Currency,
+        Lpn: Currency,
+    {
+        self.stop_loss
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn take_profit<Asset, Lpn>(&self) -> ContractResult<Option<Price<Asset, Lpn>>>
+    where
+        Asset: Currency,
+        Lpn: Currency,
+    {
+        self.take_profit
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+generate_ids! {
+    pub(crate) ClosePolicyTrigger as u8 {
+        StopLoss = 1,
+        TakeProfit = 2,
+    }
+}
+
+impl ClosePolicyTrigger {
+    pub fn to_uint(self) -> u8 {
+        self.into()
+    }
+}
+
+impl<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle> Lease<Lpn, Asset, Lpp, Profit, TimeAlarms, Oracle>
+where
+    Lpn: Currency + Serialize,
+    Lpp: LppLenderTrait<Lpn>,
+    TimeAlarms: TimeAlarmsTrait,
+    Oracle: OracleTrait<Lpn>,
+    Profit: ProfitTrait,
+    Asset: Currency + Serialize,
+{
+    pub(crate) fn change_close_policy(
+        &mut self,
+        policy: ClosePolicy,
+        now: &Timestamp,
+    ) -> ContractResult<()> {
+        if policy.trigger(self.price_of_lease_currency()?)?.is_some() {
+            return Err(ContractError::InvalidClosePolicy());
+        }
+
+        self.close_policy = policy;
+        self.reschedule_on_close_policy_change(now)
+    }
+
+    pub(crate) fn close_policy_trigger(&self) -> ContractResult<Option<ClosePolicyTrigger>> {
+        self.close_policy.trigger(self.price_of_lease_currency()?)
+    }
+
+    /// Cancel the policy price crossed on a `trigger` the position cannot be closed on
+    ///
+    /// Otherwise, the next price alarm would be set with an already crossed bound.
+    /// The alarms are not rescheduled here.
+    pub(crate) fn cancel_close_policy_trigger(&mut self, trigger: ClosePolicyTrigger) {
+        self.close_policy.cancel(trigger)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use currency::{lease::Atom, lpn::Usdc};
+    use finance::{
+        coin::Coin,
+        price::{total_of, Price},
+    };
+    use sdk::cosmwasm_std::Addr;
+
+    use crate::{
+        api::LeasePrice,
+        error::ContractError,
+        lease::tests::{loan, open_lease, LEASE_START},
+    };
+
+    use super::{ClosePolicy, ClosePolicyTrigger};
+
+    fn price(amount: u128, amount_quote: u128) -> Price<Atom, Usdc> {
+        total_of(Coin::new(amount)).is(Coin::new(amount_quote))
+    }
+
+    fn policy(stop_loss: Option<(u128, u128)>, take_profit: Option<(u128, u128)>) -> ClosePolicy {
+        let into_dto = |(amount, amount_quote)| LeasePrice::from(price(amount, amount_quote));
+        ClosePolicy::new(stop_loss.map(into_dto), take_profit.map(into_dto))
+    }
+
+    #[test]
+    fn trigger() {
+        let policy = policy(Some((2, 1)), Some((1, 2)));
+
+        assert_eq!(policy.trigger(price(1, 1)), Ok(None));
+        assert_eq!(
+            policy.trigger(price(2, 1)),
+            Ok(Some(ClosePolicyTrigger::StopLoss))
+        );
+        assert_eq!(
+            policy.trigger(price(3, 1)),
+            Ok(Some(ClosePolicyTrigger::StopLoss))
+        );
+        assert_eq!(
+            policy.trigger(price(1, 2)),
+            Ok(Some(ClosePolicyTrigger::TakeProfit))
+        );
+        assert_eq!(ClosePolicy::default().trigger(price(1, 100)), Ok(None));
+    }
+
+    #[test]
+    fn bound_alarm() {
+        let policy = policy(Some((2, 1)), Some((1, 2)));
+
+        assert_eq!(
+            policy.bound_alarm(price(4, 1), None),
+            Ok((price(2, 1), Some(price(1, 2))))
+        );
+        assert_eq!(
+            policy.bound_alarm(price(3, 2), Some(price(1, 3))),
+            Ok((price(3, 2), Some(price(1, 2))))
+        );
+        assert_eq!(
+            policy.bound_alarm(price(3, 2), Some(price(3, 4))),
+            Ok((price(3, 2), Some(price(3, 4))))
+        );
+        assert_eq!(
+            ClosePolicy::default().bound_alarm(price(4, 1), None),
+            Ok((price(4, 1), None))
+        );
+    }
+
+    #[test]
+    fn bound_alarm_crossed() {
+        let policy = policy(Some((1, 1)), None);
+
+        assert_eq!(
+            policy.bound_alarm(price(4, 1), Some(price(3, 2))),
+            Ok((price(1, 1), None))
+        );
+    }
+
+    #[test]
+    fn change_close_policy() {
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            1000.into(),
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        let valid = policy(Some((2, 1)), Some((1, 2)));
+
+        assert_eq!(
+            lease.change_close_policy(valid.clone(), &LEASE_START),
+            Ok(())
+        );
+        assert_eq!(lease.close_policy, valid);
+        assert_eq!(lease.close_policy_trigger(), Ok(None));
+
+        assert_eq!(
+            lease.change_close_policy(policy(Some((1, 1)), None), &LEASE_START),
+            Err(ContractError::InvalidClosePolicy())
+        );
+        assert_eq!(
+            lease.change_close_policy(policy(None, Some((1, 1))), &LEASE_START),
+            Err(ContractError::InvalidClosePolicy())
+        );
+        assert_eq!(lease.close_policy, valid);
+    }
+
+    #[test]
+    fn cancel_close_policy_trigger() {
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            1000.into(),
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        // a stop-loss crossed by the current price
+        lease.close_policy = policy(Some((1, 1)), Some((1, 2)));
+        assert_eq!(
+            lease.close_policy_trigger(),
+            Ok(Some(ClosePolicyTrigger::StopLoss))
+        );
+
+        lease.cancel_close_policy_trigger(ClosePolicyTrigger::StopLoss);
+        assert_eq!(lease.close_policy, policy(None, Some((1, 2))));
+        assert_eq!(lease.close_policy_trigger(), Ok(None));
+        // the liquidation bound is not replaced by the crossed stop-loss
+        assert_eq!(
+            lease.close_policy.bound_alarm(price(4, 1), None),
+            Ok((price(4, 1), Some(price(1, 2))))
+        );
+    }
+}
@@ -7,7 +7,9 @@ use timealarms::stub::TimeAlarmsRef;
 
 use crate::{api::LeaseCoin, loan::LoanDTO};
 
-#[derive(Serialize, Deserialize)]
+use super::ClosePolicy;
+
+#[derive(Serialize, Deserialize, Clone)]
 #[cfg_attr(test, derive(Debug))]
 pub struct LeaseDTO {
     pub(crate) addr: Addr,
@@ -17,6 +19,8 @@ pub struct LeaseDTO {
     pub(crate) loan: LoanDTO,
     pub(crate) time_alarms: TimeAlarmsRef,
     pub(crate) oracle: OracleRef,
+    #[serde(default)]
+    pub(crate) close_policy: ClosePolicy,
 }
 
 impl LeaseDTO {
@@ -28,6 +32,7 @@ impl LeaseDTO {
         loan: LoanDTO,
         time_alarms: TimeAlarmsRef,
         oracle: OracleRef,
+        close_policy: ClosePolicy,
     ) -> Self {
         Self {
             addr,
@@ -37,6 +42,7 @@ impl LeaseDTO {
             loan,
             time_alarms,
             oracle,
+            close_policy,
         }
     }
 }
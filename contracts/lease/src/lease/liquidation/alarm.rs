@@ -16,7 +16,10 @@ use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
 
 use crate::{
     error::ContractResult,
-    lease::{IntoDTOResult, Lease, LiquidationInfo, OnAlarmResult, Status, WarningLevel},
+    lease::{
+        IntoDTOResult, Lease, LiquidationInfo, LiquidationPrices, OnAlarmResult, Status,
+        WarningLevel,
+    },
     loan::LiabilityStatus,
 };
 
@@ -63,6 +66,24 @@ where
         )
     }
 
+    #[inline]
+    pub(in crate::lease) fn reschedule_on_close_policy_change(
+        &mut self,
+        now: &Timestamp,
+    ) -> ContractResult<()> {
+        let lease_lpn = self.lease_amount_lpn()?;
+
+        self.reschedule_price_alarm(
+            lease_lpn,
+            now,
+            &self.handle_warnings(
+                self.loan
+                    .liability_status(*now, self.addr.clone(), lease_lpn)?
+                    .ltv,
+            ),
+        )
+    }
+
     fn on_alarm<F>(
         mut self,
         handler: F,
@@ -91,24 +112,34 @@ where
 
         let status = handler(&mut self, lease_lpn, now, ltv, liability_lpn)?;
 
-        if let Status::PartialLiquidation {
-            liquidation_info: LiquidationInfo { receipt, .. },
-            ..
-        } = &status
-        {
-            self.amount -= total(receipt.total(), price_to_lpn.inv());
-        }
+        let liquidated = match &status {
+            Status::None | Status::Warning(..) => Coin::default(),
+            Status::PartialLiquidation {
+                liquidation_info:
+                    LiquidationInfo {
+                        receipt, penalty, ..
+                    },
+                ..
+            } => {
+                let liquidated = total(receipt.total() + *penalty, price_to_lpn.inv());
+                self.amount -= liquidated;
+                liquidated
+            }
+            Status::FullLiquidation { .. } => self.amount,
+        };
 
         if !matches!(status, Status::FullLiquidation { .. }) {
             self.reschedule(lease_lpn, &now, &status)?;
         }
 
-        Ok(self.into_on_alarm_result(status))
+        Ok(self.into_on_alarm_result(status, liquidated, price_to_lpn))
     }
 
     fn into_on_alarm_result(
         self,
         liquidation_status: Status<Lpn, Asset>,
+        liquidated: Coin<Asset>,
+        price: Price<Asset, Lpn>,
     ) -> OnAlarmResult<Lpn, Asset> {
         let IntoDTOResult {
             lease: lease_dto,
@@ -119,6 +150,8 @@ where
             batch,
             lease_dto,
             liquidation_status,
+            liquidated,
+            price,
         }
     }
 
@@ -195,11 +228,28 @@ where
             .map(|above| self.price_alarm_by_percent(total_liability, above))
             .transpose()?;
 
+        let (below, above) = self.close_policy.bound_alarm(below, above)?;
+
         self.oracle
             .add_alarm(Alarm::new(below.into(), above.map(Into::<SpotPrice>::into)))
             .map_err(Into::into)
     }
 
+    pub(in crate::lease) fn liquidation_prices(
+        &self,
+        liability: Coin<Lpn>,
+    ) -> ContractResult<LiquidationPrices<Asset, Lpn>> {
+        Ok(LiquidationPrices {
+            first_warning: self
+                .price_alarm_by_percent(liability, self.liability.first_liq_warn_percent())?,
+            second_warning: self
+                .price_alarm_by_percent(liability, self.liability.second_liq_warn_percent())?,
+            third_warning: self
+                .price_alarm_by_percent(liability, self.liability.third_liq_warn_percent())?,
+            liquidation: self.price_alarm_by_percent(liability, self.liability.max_percent())?,
+        })
+    }
+
     fn price_alarm_by_percent(
         &self,
         liability: Coin<Lpn>,
@@ -7,6 +7,7 @@ use finance::{
     currency::Currency,
     fraction::Fraction,
     percent::{Percent, Units},
+    price::{self, Price},
     ratio::Rational,
 };
 use lpp::stub::lender::LppLender as LppLenderTrait;
@@ -35,6 +36,33 @@ where
     Profit: ProfitTrait,
     Asset: Currency + Serialize,
 {
+    /// Run the liquidation checks at a hypothetical price of the lease asset and time
+    ///
+    /// The liability is checked before the overdue interest. The lease is consumed
+    /// so the effects of a liquidation are discarded.
+    pub(crate) fn liquidation_status_at(
+        mut self,
+        price_to_lpn: Price<Asset, Lpn>,
+        at: Timestamp,
+    ) -> ContractResult<Status<Lpn, Asset>> {
+        let lease_lpn = price::total(self.amount, price_to_lpn);
+
+        let LiabilityStatus {
+            ltv,
+            total_lpn: liability_lpn,
+            ..
+        } = self
+            .loan
+            .liability_status(at, self.addr.clone(), lease_lpn)?;
+
+        let status = self.act_on_liability(lease_lpn, at, ltv, liability_lpn)?;
+        if matches!(status, Status::None | Status::Warning(..)) {
+            self.act_on_overdue(lease_lpn, at, ltv, liability_lpn)
+        } else {
+            Ok(status)
+        }
+    }
+
     fn act_on_overdue(
         &mut self,
         lease_lpn: Coin<Lpn>,
@@ -94,19 +122,27 @@ where
         liability_lpn: Coin<Lpn>,
         now: Timestamp,
     ) -> ContractResult<Status<Lpn, Asset>> {
-        // from 'liability - liquidation = healthy% of (lease - liquidation)' follows
-        // 'liquidation = 100% / (100% - healthy%) of (liability - healthy% of lease)'
-        let multiplier = Rational::new(
-            Percent::HUNDRED,
-            Percent::HUNDRED - self.liability.healthy_percent(),
-        );
-        let extra_liability_lpn =
-            liability_lpn - liability_lpn.min(self.liability.healthy_percent().of(lease_lpn));
-        let liquidation_lpn = Fraction::<Units>::of(&multiplier, extra_liability_lpn);
+        // from 'liability - liquidation =
+        //          healthy% of (lease - liquidation - penalty% of liquidation)' follows
+        // 'liquidation = 100% / (100% - healthy% - penalty% of healthy%) of
+        //                  (liability - healthy% of lease)'
+        let healthy = self.liability.healthy_percent();
+        let healthy_with_penalty =
+            healthy + self.liability.liquidation_penalty_percent().of(healthy);
+        let liquidation_lpn = match Percent::HUNDRED.checked_sub(healthy_with_penalty) {
+            Ok(rest) if rest != Percent::ZERO => {
+                let multiplier = Rational::new(Percent::HUNDRED, rest);
+                let extra_liability_lpn = liability_lpn - liability_lpn.min(healthy.of(lease_lpn));
+                Fraction::<Units>::of(&multiplier, extra_liability_lpn)
+            }
+            // the penalty prevents reaching the healthy LTV with a partial liquidation
+            _ => lease_lpn,
+        };
 
         self.liquidate(
             Cause::Liability,
             lease_lpn,
+            liability_lpn,
             liquidation_lpn,
             now,
             self.liability.max_percent(),
@@ -119,25 +155,51 @@ where
         lease_lpn: Coin<Lpn>,
     ) -> ContractResult<Status<Lpn, Asset>> {
         let LiabilityStatus {
-            ltv, overdue_lpn, ..
+            ltv,
+            total_lpn: liability_lpn,
+            overdue_lpn,
+            ..
         } = self
             .loan
             .liability_status(now, self.addr.clone(), lease_lpn)?;
 
-        self.liquidate(Cause::Overdue, lease_lpn, overdue_lpn, now, ltv)
+        self.liquidate(
+            Cause::Overdue,
+            lease_lpn,
+            liability_lpn,
+            overdue_lpn,
+            now,
+            ltv,
+        )
     }
 
+    /// Repay with `liquidation_lpn` and charge the penalty on top of it
+    ///
+    /// If the asset left would be worth less than the minimum, the whole lease is liquidated.
+    /// Then the loan is repaid first, and the penalty is taken only from the rest, if any.
     fn liquidate(
         &mut self,
         cause: Cause,
         lease_lpn: Coin<Lpn>,
-        mut liquidation_lpn: Coin<Lpn>,
+        liability_lpn: Coin<Lpn>,
+        liquidation_lpn: Coin<Lpn>,
         now: Timestamp,
         ltv: Percent,
     ) -> ContractResult<Status<Lpn, Asset>> {
-        liquidation_lpn = lease_lpn.min(liquidation_lpn);
+        let penalty_percent = self.liability.liquidation_penalty_percent();
+
+        let mut liquidation_lpn = lease_lpn.min(liquidation_lpn);
+        let mut penalty_lpn = penalty_percent.of(liquidation_lpn);
+        let full = lease_lpn <= liquidation_lpn + penalty_lpn
+            || lease_lpn - liquidation_lpn - penalty_lpn < self.liability.min_asset_lpn().into();
+        if full {
+            let equity_lpn = lease_lpn - lease_lpn.min(liability_lpn);
+            penalty_lpn = penalty_percent.of(liability_lpn).min(equity_lpn);
+            liquidation_lpn = lease_lpn - penalty_lpn;
+        }
 
         let receipt = self.no_reschedule_repay(liquidation_lpn, now)?;
+        self.loan.pay_penalty(penalty_lpn);
 
         let info = LeaseInfo::new(self.customer.clone(), self.addr.clone(), ltv);
 
@@ -145,10 +207,10 @@ where
             cause,
             lease: self.addr.clone(),
             receipt,
+            penalty: penalty_lpn,
         };
 
-        // TODO liquidate fully if the remaining value, lease_lpn - liquidation_lpn < 100
-        Ok(if liquidation_lpn == lease_lpn {
+        Ok(if full {
             Status::FullLiquidation {
                 info,
                 liquidation_info,
@@ -171,6 +233,10 @@ where
     pub batch: Batch,
     pub lease_dto: LeaseDTO,
     pub liquidation_status: Status<Lpn, Asset>,
+    /// The amount of the lease asset liquidated, zero if none
+    pub liquidated: Coin<Asset>,
+    /// The price the liquidation checks have been run at
+    pub price: Price<Asset, Lpn>,
 }
 
 #[cfg_attr(test, derive(Debug, Eq, PartialEq))]
@@ -225,6 +291,8 @@ where
     pub cause: Cause,
     pub lease: Addr,
     pub receipt: RepayReceipt<Lpn>,
+    /// The penalty charged on top of the liquidated amount
+    pub penalty: Coin<Lpn>,
 }
 
 generate_ids! {
@@ -256,7 +324,9 @@ impl WarningLevel {
 
 #[cfg(test)]
 mod tests {
-    use finance::{duration::Duration, interest::InterestPeriod, percent::Percent};
+    use finance::{
+        coin::Amount, duration::Duration, interest::InterestPeriod, percent::Percent, price,
+    };
     use lpp::msg::LoanResponse;
     use sdk::cosmwasm_std::{Addr, Timestamp};
 
@@ -481,6 +551,7 @@ mod tests {
                         lpn_coin(0),
                         false
                     ),
+                    penalty: lpn_coin(0),
                 },
                 healthy_ltv: lease.liability.healthy_percent(),
             }
@@ -544,8 +615,317 @@ mod tests {
                         lpn_coin(0),
                         true,
                     ),
+                    penalty: lpn_coin(0),
                 },
             }
         );
     }
+
+    #[test]
+    fn liquidate_partial_min_asset_boundary() {
+        // the partial liquidation of 273 leaves 527 out of 800
+        liquidate_with_min_asset(0, false);
+        liquidate_with_min_asset(526, false);
+        liquidate_with_min_asset(527, false);
+    }
+
+    #[test]
+    fn liquidate_full_below_min_asset() {
+        liquidate_with_min_asset(528, true);
+        liquidate_with_min_asset(800, true);
+        liquidate_with_min_asset(10000, true);
+    }
+
+    fn liquidate_with_min_asset(min_asset_lpn: Amount, exp_full: bool) {
+        let lease_amount_lpn = lpn_coin(800);
+        let loan_amount_lpn = lpn_coin(500);
+        let past_open = Duration::from_days(90);
+        let now = LEASE_START + past_open;
+        let loan = LoanResponse {
+            principal_due: loan_amount_lpn,
+            annual_interest_rate: Percent::from_percent(114),
+            interest_paid: LEASE_START,
+        };
+        let interest_due = loan.interest_due(now);
+        let margin_due = InterestPeriod::with_interest(MARGIN_INTEREST_RATE)
+            .from(LEASE_START)
+            .spanning(past_open)
+            .interest(loan_amount_lpn);
+
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(100),
+            Some(loan),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        lease.liability = lease.liability.with_min_asset_lpn(min_asset_lpn);
+
+        let status = lease
+            .liquidate_on_liability(
+                lease_amount_lpn,
+                loan_amount_lpn + interest_due + margin_due,
+                now,
+            )
+            .unwrap();
+        if exp_full {
+            assert!(
+                matches!(status, Status::FullLiquidation { liquidation_info, .. } if liquidation_info.receipt.close())
+            );
+        } else {
+            assert!(matches!(status, Status::PartialLiquidation { .. }));
+        }
+    }
+
+    #[test]
+    fn liquidate_partial_with_penalty() {
+        let lease_amount_lpn = lpn_coin(800);
+        let loan_amount_lpn = lpn_coin(500);
+        let past_open = Duration::from_days(90);
+        let now = LEASE_START + past_open;
+        let loan = LoanResponse {
+            principal_due: loan_amount_lpn,
+            annual_interest_rate: Percent::from_percent(114),
+            interest_paid: LEASE_START,
+        };
+        let interest_due = loan.interest_due(now);
+        let exp_liquidation_lpn = lpn_coin(356);
+        let margin_due = InterestPeriod::with_interest(MARGIN_INTEREST_RATE)
+            .from(LEASE_START)
+            .spanning(past_open)
+            .interest(loan_amount_lpn);
+
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(100),
+            Some(loan),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        lease.liability = lease
+            .liability
+            .with_liquidation_penalty(Percent::from_percent(10));
+
+        let status = lease
+            .liquidate_on_liability(
+                lease_amount_lpn,
+                loan_amount_lpn + interest_due + margin_due,
+                now,
+            )
+            .unwrap();
+        assert!(matches!(
+            status,
+            Status::PartialLiquidation { liquidation_info, .. }
+                if liquidation_info.receipt.total() == exp_liquidation_lpn
+                    && liquidation_info.penalty == lpn_coin(35)
+        ));
+    }
+
+    #[test]
+    fn liquidate_full_with_penalty_below_min_asset() {
+        // the partial liquidation of 356 and the penalty of 35 leave 409 out of 800
+        liquidate_with_penalty_and_min_asset(409, false);
+        liquidate_with_penalty_and_min_asset(410, true);
+    }
+
+    fn liquidate_with_penalty_and_min_asset(min_asset_lpn: Amount, exp_full: bool) {
+        let lease_amount_lpn = lpn_coin(800);
+        let loan_amount_lpn = lpn_coin(500);
+        let past_open = Duration::from_days(90);
+        let now = LEASE_START + past_open;
+        let loan = LoanResponse {
+            principal_due: loan_amount_lpn,
+            annual_interest_rate: Percent::from_percent(114),
+            interest_paid: LEASE_START,
+        };
+        let interest_due = loan.interest_due(now);
+        let margin_due = InterestPeriod::with_interest(MARGIN_INTEREST_RATE)
+            .from(LEASE_START)
+            .spanning(past_open)
+            .interest(loan_amount_lpn);
+
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(100),
+            Some(loan),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        lease.liability = lease
+            .liability
+            .with_min_asset_lpn(min_asset_lpn)
+            .with_liquidation_penalty(Percent::from_percent(10));
+
+        let status = lease
+            .liquidate_on_liability(
+                lease_amount_lpn,
+                loan_amount_lpn + interest_due + margin_due,
+                now,
+            )
+            .unwrap();
+        if exp_full {
+            assert!(matches!(
+                status,
+                Status::FullLiquidation { liquidation_info, .. }
+                    if liquidation_info.receipt.close()
+                        && liquidation_info.receipt.total() + liquidation_info.penalty == lease_amount_lpn
+            ));
+        } else {
+            assert!(matches!(status, Status::PartialLiquidation { .. }));
+        }
+    }
+
+    #[test]
+    fn liquidate_full_with_penalty_above_equity() {
+        let loan_amount_lpn = lpn_coin(500);
+        let past_open = Duration::from_days(90);
+        let now = LEASE_START + past_open;
+        let loan = LoanResponse {
+            principal_due: loan_amount_lpn,
+            annual_interest_rate: Percent::from_percent(114),
+            interest_paid: LEASE_START,
+        };
+        let interest_due = loan.interest_due(now);
+        let margin_due = InterestPeriod::with_interest(MARGIN_INTEREST_RATE)
+            .from(LEASE_START)
+            .spanning(past_open)
+            .interest(loan_amount_lpn);
+        let liability_lpn = loan_amount_lpn + interest_due + margin_due;
+
+        let liquidate = |lease_amount_lpn| {
+            let mut lease = open_lease(
+                Addr::unchecked("lease"),
+                coin(100),
+                Some(loan.clone()),
+                Addr::unchecked(String::new()),
+                Addr::unchecked(String::new()),
+                Addr::unchecked(String::new()),
+            );
+            lease.liability = lease
+                .liability
+                .with_liquidation_penalty(Percent::from_percent(10));
+
+            lease
+                .liquidate_on_liability(lease_amount_lpn, liability_lpn, now)
+                .unwrap()
+        };
+
+        // the equity left after the loan is repaid is less than the penalty of 10% of the liability
+        let lease_amount_lpn = liability_lpn + lpn_coin(8);
+        assert!(matches!(
+            liquidate(lease_amount_lpn),
+            Status::FullLiquidation { liquidation_info, .. }
+                if liquidation_info.receipt.close() && liquidation_info.penalty == lpn_coin(8)
+        ));
+
+        // no equity is left after the loan is repaid
+        let lease_amount_lpn = liability_lpn - lpn_coin(8);
+        assert!(matches!(
+            liquidate(lease_amount_lpn),
+            Status::FullLiquidation { liquidation_info, .. }
+                if liquidation_info.receipt.total() == lease_amount_lpn
+                    && liquidation_info.penalty == lpn_coin(0)
+        ));
+    }
+
+    #[test]
+    fn liquidation_status_at_none() {
+        let loan = LoanResponse {
+            principal_due: lpn_coin(500),
+            annual_interest_rate: Percent::from_permille(50),
+            interest_paid: LEASE_START,
+        };
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(1000),
+            Some(loan),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+
+        assert_eq!(
+            lease
+                .liquidation_status_at(price::total_of(coin(1)).is(lpn_coin(1)), LEASE_START)
+                .unwrap(),
+            Status::None
+        );
+    }
+
+    #[test]
+    fn liquidation_status_at_price_shock() {
+        let loan = LoanResponse {
+            principal_due: lpn_coin(500),
+            annual_interest_rate: Percent::from_permille(50),
+            interest_paid: LEASE_START,
+        };
+        let lease_addr = Addr::unchecked("lease");
+        let lease = open_lease(
+            lease_addr.clone(),
+            coin(1000),
+            Some(loan),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        let max_ltv = lease.liability.max_percent();
+
+        assert_eq!(
+            lease
+                .liquidation_status_at(price::total_of(coin(2)).is(lpn_coin(1)), LEASE_START)
+                .unwrap(),
+            Status::FullLiquidation {
+                info: LeaseInfo::new(Addr::unchecked("customer"), lease_addr.clone(), max_ltv),
+                liquidation_info: LiquidationInfo {
+                    cause: Cause::Liability,
+                    lease: lease_addr,
+                    receipt: RepayReceipt::new(
+                        lpn_coin(0),
+                        lpn_coin(0),
+                        lpn_coin(0),
+                        lpn_coin(0),
+                        lpn_coin(500),
+                        lpn_coin(0),
+                        true,
+                    ),
+                    penalty: lpn_coin(0),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn liquidation_status_at_overdue() {
+        let loan = LoanResponse {
+            principal_due: lpn_coin(500),
+            annual_interest_rate: Percent::from_permille(50),
+            interest_paid: LEASE_START,
+        };
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(1000),
+            Some(loan),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        let grace_period_end = lease.loan.grace_period_end();
+
+        let status = lease
+            .liquidation_status_at(price::total_of(coin(1)).is(lpn_coin(1)), grace_period_end)
+            .unwrap();
+        assert!(matches!(
+            status,
+            Status::PartialLiquidation {
+                liquidation_info: LiquidationInfo {
+                    cause: Cause::Overdue,
+                    ..
+                },
+                ..
+            }
+        ));
+    }
 }
@@ -15,16 +15,18 @@ use timealarms::stub::{TimeAlarms as TimeAlarmsTrait, TimeAlarmsBatch};
 
 use crate::{
     error::{ContractError, ContractResult},
-    loan::Loan,
+    loan::{LiabilityStatus, Loan},
 };
 
 pub(super) use self::{
+    close_policy::{ClosePolicy, ClosePolicyTrigger},
     dto::LeaseDTO,
-    liquidation::{LeaseInfo, LiquidationInfo, OnAlarmResult, Status, WarningLevel},
+    liquidation::{Cause, LeaseInfo, LiquidationInfo, OnAlarmResult, Status, WarningLevel},
     repay::Result as RepayResult,
-    state::State,
+    state::{LiquidationPrices, State},
 };
 
+mod close_policy;
 mod dto;
 mod liquidation;
 mod repay;
@@ -43,6 +45,7 @@ where
     loan: Loan<Lpn, Lpp, Profit>,
     alarms: TimeAlarms,
     oracle: Oracle,
+    close_policy: ClosePolicy,
 }
 
 #[cfg_attr(test, derive(Debug))]
@@ -81,6 +84,7 @@ where
             loan,
             alarms: deps.0,
             oracle: deps.1,
+            close_policy: ClosePolicy::default(),
         };
         res.initial_alarm_schedule(&start_at)?;
         Ok(res)
@@ -104,6 +108,7 @@ where
             loan: Loan::from_dto(dto.loan, lpp, profit),
             alarms: time_alarms,
             oracle,
+            close_policy: dto.close_policy,
         }
     }
 
@@ -129,11 +134,54 @@ where
                 loan_dto,
                 time_alarms_ref,
                 oracle_ref,
+                self.close_policy,
             ),
             batch: loan_batch.merge(time_alarms_batch).merge(oracle_batch),
         }
     }
 
+    pub(crate) fn add_collateral(
+        &mut self,
+        amount: Coin<Asset>,
+        now: &Timestamp,
+    ) -> ContractResult<()> {
+        debug_assert!(!amount.is_zero());
+
+        self.amount += amount;
+        self.reschedule_on_repay(now)
+    }
+
+    /// Request an additional loan amount
+    ///
+    /// The margin interest due is paid out of `amount` and the change, returned as a result,
+    /// is to be swapped into the lease asset and added as collateral. The combined position
+    /// should not exceed the initial LTV.
+    pub(crate) fn borrow_more(
+        &mut self,
+        amount: Coin<Lpn>,
+        now: &Timestamp,
+    ) -> ContractResult<Coin<Lpn>> {
+        debug_assert!(!amount.is_zero());
+
+        let lease_lpn = self.lease_amount_lpn()?;
+        let LiabilityStatus { total_lpn, .. } =
+            self.loan
+                .liability_status(*now, self.addr.clone(), lease_lpn)?;
+
+        let collateral = self.loan.increase_req(amount, *now, self.addr.clone())?;
+        if collateral.is_zero() {
+            return Err(ContractError::InvalidBorrowAmount(amount.into()));
+        }
+
+        let margin_paid = amount - collateral;
+        let total_lpn_updated = total_lpn - margin_paid + amount;
+        if self.liability.initial_percent().of(lease_lpn + collateral) < total_lpn_updated {
+            return Err(ContractError::LoanIncreaseAboveInitialLtv());
+        }
+
+        Ok(collateral)
+    }
+
     pub(crate) fn sent_by_time_alarms(&self, addr: &Addr) -> bool {
         self.alarms.owned_by(addr)
     }
@@ -142,7 +190,24 @@ where
         self.oracle.owned_by(addr)
     }
 
-    pub(crate) fn close<B>(mut self, lease_account: B) -> ContractResult<IntoDTOResult>
+    pub(crate) fn close<B>(self, lease_account: B) -> ContractResult<IntoDTOResult>
+    where
+        B: BankAccount,
+    {
+        self.close_impl(lease_account, true)
+    }
+
+    /// Close a paid lease whose amount has already been delivered to the customer
+    ///
+    /// Only the surplus, if any, is sent to the customer.
+    pub(crate) fn close_delivered<B>(self, lease_account: B) -> ContractResult<IntoDTOResult>
+    where
+        B: BankAccount,
+    {
+        self.close_impl(lease_account, false)
+    }
+
+    fn close_impl<B>(mut self, lease_account: B, send_amount: bool) -> ContractResult<IntoDTOResult>
     where
         B: BankAccount,
     {
@@ -150,7 +215,7 @@ where
         match state {
             State::Opened { .. } => Err(ContractError::LoanNotPaid()),
             State::Paid(..) => {
-                let bank_transfers = self.send_funds_to_customer(lease_account)?;
+                let bank_transfers = self.send_funds_to_customer(lease_account, send_amount)?;
                 self.amount = Coin::<Asset>::default();
 
                 let IntoDTOResult { lease, batch } = self.into_dto();
@@ -170,6 +235,10 @@ where
             let loan_state = self.loan.state(now, self.addr.clone())?;
 
             loan_state.map_or(Ok(State::Paid(self.amount)), |state| {
+                let LiabilityStatus { ltv, total_lpn, .. } =
+                    self.loan
+                        .liability_status(now, self.addr.clone(), self.lease_amount_lpn()?)?;
+
                 Ok(State::Opened {
                     amount: self.amount,
                     interest_rate: state.annual_interest,
@@ -177,8 +246,11 @@ where
                     principal_due: state.principal_due,
                     previous_margin_due: state.previous_margin_interest_due,
                     previous_interest_due: state.previous_interest_due,
+                    late_payment_penalty_due: state.late_payment_penalty_due,
                     current_margin_due: state.current_margin_interest_due,
                     current_interest_due: state.current_interest_due,
+                    ltv,
+                    liquidation_prices: self.liquidation_prices(total_lpn)?,
                     validity: now,
                 })
             })
@@ -193,7 +265,11 @@ where
         Ok(total(self.amount, self.price_of_lease_currency()?))
     }
 
-    fn send_funds_to_customer<B>(&self, mut lease_account: B) -> ContractResult<Batch>
+    fn send_funds_to_customer<B>(
+        &self,
+        mut lease_account: B,
+        send_amount: bool,
+    ) -> ContractResult<Batch>
     where
         B: BankAccount,
     {
@@ -203,7 +279,9 @@ where
             lease_account.send(surplus, &self.customer);
         }
 
-        lease_account.send(self.amount, &self.customer);
+        if send_amount {
+            lease_account.send(self.amount, &self.customer);
+        }
 
         Ok(lease_account.into())
     }
@@ -218,14 +296,15 @@ mod tests {
         coin::Coin,
         currency::{self, Currency},
         duration::Duration,
+        fraction::Fraction,
         liability::Liability,
         percent::Percent,
-        price::Price,
+        price::{total_of, Price},
         zero::Zero,
     };
     use lpp::{
         error::ContractError as LppError,
-        msg::{LoanResponse, QueryLoanResponse},
+        msg::{LoanResponse, QueryLoanResponse, QueryQuoteResponse},
         stub::{
             lender::{LppLender, LppLenderRef},
             LppBatch,
@@ -248,9 +327,9 @@ mod tests {
         stub::{TimeAlarms, TimeAlarmsBatch, TimeAlarmsRef},
     };
 
-    use crate::{api::InterestPaymentSpec, loan::Loan, reply_id::ReplyId};
+    use crate::{api::InterestPaymentSpec, error::ContractError, loan::Loan, reply_id::ReplyId};
 
-    use super::{Lease, State};
+    use super::{Lease, LiquidationPrices, State};
 
     const CUSTOMER: &str = "customer";
     pub const MARGIN_INTEREST_RATE: Percent = Percent::from_permille(23);
@@ -326,7 +405,7 @@ mod tests {
         Lpn: Currency,
     {
         fn open_loan_req(&mut self, _amount: Coin<Lpn>) -> LppResult<()> {
-            unreachable!()
+            Ok(())
         }
 
         fn open_loan_resp(&self, _resp: cosmwasm_std::Reply) -> LppResult<LoanResponse<Lpn>> {
@@ -341,8 +420,10 @@ mod tests {
             Ok(self.loan.clone())
         }
 
-        fn quote(&self, _amount: Coin<Lpn>) -> LppResult<lpp::msg::QueryQuoteResponse> {
-            unreachable!()
+        fn quote(&self, _amount: Coin<Lpn>) -> LppResult<QueryQuoteResponse> {
+            Ok(QueryQuoteResponse::QuoteInterestRate(
+                Percent::from_percent(10),
+            ))
         }
     }
 
@@ -703,6 +784,8 @@ mod tests {
             Addr::unchecked(String::new()),
             Addr::unchecked(String::new()),
         );
+        let liability = lease.liability;
+        let price_at = |percent: Percent| total_of(percent.of(lease_amount)).is(loan.principal_due);
 
         let res = request_state(lease);
         let exp = State::Opened {
@@ -712,8 +795,16 @@ mod tests {
             principal_due: loan.principal_due,
             previous_margin_due: lpn_coin(0),
             previous_interest_due: lpn_coin(0),
+            late_payment_penalty_due: lpn_coin(0),
             current_margin_due: lpn_coin(0),
             current_interest_due: lpn_coin(0),
+            ltv: Percent::from_percent(30),
+            liquidation_prices: LiquidationPrices {
+                first_warning: price_at(liability.first_liq_warn_percent()),
+                second_warning: price_at(liability.second_liq_warn_percent()),
+                third_warning: price_at(liability.third_liq_warn_percent()),
+                liquidation: price_at(liability.max_percent()),
+            },
             validity: LEASE_STATE_AT,
         };
 
@@ -811,6 +902,242 @@ mod tests {
         });
     }
 
+    #[test]
+    fn close_delivered() {
+        let lease_amount = 10.into();
+        let surplus_amount = 2.into();
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            lease_amount,
+            None,
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        let lease_account = BankStub::new(MockBankView::new(lease_amount, surplus_amount));
+        let res = lease.close_delivered(lease_account).unwrap();
+        assert_eq!(
+            res.batch,
+            expect_bank_send(Batch::default(), surplus_amount)
+        );
+        assert!(res.lease.amount.is_zero());
+    }
+
+    #[test]
+    fn close_position_partial() {
+        let lease_amount = coin(1000);
+        let close_amount = coin(400);
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            lease_amount,
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        let lease_account = BankStub::new(MockBankView::new(lease_amount, lpn_coin(400)));
+
+        let res = lease
+            .close_position(close_amount, lpn_coin(400), LEASE_STATE_AT, lease_account)
+            .unwrap();
+        assert_eq!(res.lease.amount, (lease_amount - close_amount).into());
+        assert_eq!(res.receipt.principal_paid(), loan().principal_due);
+        assert_eq!(res.receipt.change(), lpn_coin(300));
+        assert!(res.receipt.close());
+    }
+
+    #[test]
+    fn close_position_full() {
+        let lease_amount = coin(1000);
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            lease_amount,
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        let lease_account = BankStub::new(MockBankView::new(lease_amount, lpn_coin(1000)));
+
+        let res = lease
+            .close_position(lease_amount, lpn_coin(1000), LEASE_STATE_AT, lease_account)
+            .unwrap();
+        assert_eq!(res.lease.amount, coin(0).into());
+        assert_eq!(res.receipt.change(), lpn_coin(900));
+        assert!(res.receipt.close());
+    }
+
+    #[test]
+    fn close_position_full_underwater() {
+        let lease_amount = coin(1000);
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            lease_amount,
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+        let lease_account = BankStub::new(MockBankView::new(lease_amount, lpn_coin(50)));
+
+        // the proceeds of the whole asset do not cover the loan principal of 100
+        assert_eq!(
+            lease
+                .close_position(lease_amount, lpn_coin(50), LEASE_STATE_AT, lease_account)
+                .map(|_| ()),
+            Err(ContractError::LoanNotPaid())
+        );
+    }
+
+    #[test]
+    fn close_position_invalid_amount() {
+        let lease_amount = coin(1000);
+        let try_close = |amount| {
+            open_lease(
+                Addr::unchecked("lease"),
+                lease_amount,
+                Some(loan()),
+                Addr::unchecked(String::new()),
+                Addr::unchecked(String::new()),
+                Addr::unchecked(String::new()),
+            )
+            .close_position(
+                amount,
+                lpn_coin(400),
+                LEASE_STATE_AT,
+                BankStub::new(MockBankView::only_balance(lease_amount)),
+            )
+            .map(|_| ())
+        };
+
+        assert_eq!(
+            try_close(coin(0)),
+            Err(ContractError::InvalidPositionCloseAmount(coin(0).into()))
+        );
+        assert_eq!(
+            try_close(lease_amount + coin(1)),
+            Err(ContractError::InvalidPositionCloseAmount(
+                (lease_amount + coin(1)).into()
+            ))
+        );
+    }
+
+    #[test]
+    fn full_close_amount() {
+        let lease_amount = coin(1000);
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            lease_amount,
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+
+        // the loan principal of 100 grossed up with the slippage at the identity price
+        assert_eq!(
+            lease.full_close_amount(Percent::ZERO, LEASE_STATE_AT),
+            Ok(coin(100))
+        );
+        assert_eq!(
+            lease.full_close_amount(Percent::from_percent(5), LEASE_STATE_AT),
+            Ok(coin(105))
+        );
+        assert_eq!(
+            lease.full_close_amount(Percent::from_percent(95), LEASE_STATE_AT),
+            Ok(lease_amount)
+        );
+        assert_eq!(
+            lease.full_close_amount(Percent::HUNDRED, LEASE_STATE_AT),
+            Ok(lease_amount)
+        );
+    }
+
+    #[test]
+    fn full_sale_repays() {
+        let sale_repays = |lease_amount| {
+            open_lease(
+                Addr::unchecked("lease"),
+                lease_amount,
+                Some(loan()),
+                Addr::unchecked(String::new()),
+                Addr::unchecked(String::new()),
+                Addr::unchecked(String::new()),
+            )
+            .full_sale_repays(Percent::from_percent(5), LEASE_STATE_AT)
+        };
+
+        // the loan principal of 100 against the proceeds less 5% slippage
+        assert_eq!(sale_repays(coin(1000)), Ok(true));
+        assert_eq!(sale_repays(coin(105)), Ok(true));
+        assert_eq!(sale_repays(coin(104)), Ok(false));
+        assert_eq!(sale_repays(coin(100)), Ok(false));
+    }
+
+    #[test]
+    fn full_repay_amount() {
+        let lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(1000),
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+
+        // the loan principal, no interest is due yet
+        assert_eq!(lease.full_repay_amount(LEASE_STATE_AT), Ok(lpn_coin(100)));
+    }
+
+    #[test]
+    fn add_collateral() {
+        let lease_amount = coin(1000);
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            lease_amount,
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+
+        lease.add_collateral(coin(300), &LEASE_STATE_AT).unwrap();
+        assert_eq!(lease.amount, coin(1300));
+        assert!(!lease.oracle.batch.is_empty());
+    }
+
+    #[test]
+    fn borrow_more() {
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(1000),
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+
+        let collateral = lease.borrow_more(lpn_coin(400), &LEASE_STATE_AT).unwrap();
+        assert_eq!(collateral, lpn_coin(400));
+    }
+
+    #[test]
+    fn borrow_more_above_initial_ltv() {
+        let mut lease = open_lease(
+            Addr::unchecked("lease"),
+            coin(1000),
+            Some(loan()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+            Addr::unchecked(String::new()),
+        );
+
+        assert_eq!(
+            lease.borrow_more(lpn_coin(2000), &LEASE_STATE_AT),
+            Err(ContractError::LoanIncreaseAboveInitialLtv())
+        );
+    }
+
     fn expect_bank_send<C>(mut batch: Batch, amount: Coin<C>) -> Batch
     where
         C: Currency,
@@ -1,17 +1,24 @@
 use serde::Serialize;
 
-use finance::{coin::Coin, currency::Currency};
+use finance::{
+    coin::Coin,
+    currency::Currency,
+    fraction::Fraction,
+    percent::{Percent, Units},
+    price::total,
+    ratio::Rational,
+};
 use lpp::stub::lender::LppLender as LppLenderTrait;
 use oracle::stub::Oracle as OracleTrait;
-use platform::batch::Batch;
+use platform::{bank::BankAccount, batch::Batch};
 use profit::stub::Profit as ProfitTrait;
 use sdk::cosmwasm_std::Timestamp;
 use timealarms::stub::TimeAlarms as TimeAlarmsTrait;
 
 use crate::{
-    error::ContractResult,
+    error::{ContractError, ContractResult},
     lease::{Lease, LeaseDTO},
-    loan::RepayReceipt,
+    loan::{LiabilityStatus, RepayReceipt},
 };
 
 use super::IntoDTOResult;
@@ -43,6 +50,109 @@ where
         })
     }
 
+    /// Reduce the lease amount with `asset` and repay with `payment` obtained from selling it
+    ///
+    /// If the whole asset is sold, the loan should get fully repaid and the remaining LPN
+    /// is sent to the customer. Otherwise, the close is rejected since the lease cannot
+    /// stay open without an asset. The callers sell the whole asset only if
+    /// [`Self::full_sale_repays`], so that the rejection is not expected.
+    pub(crate) fn close_position<B>(
+        mut self,
+        asset: Coin<Asset>,
+        payment: Coin<Lpn>,
+        now: Timestamp,
+        mut lease_account: B,
+    ) -> ContractResult<Result<Lpn>>
+    where
+        B: BankAccount,
+    {
+        if asset.is_zero() || self.amount < asset {
+            return Err(ContractError::InvalidPositionCloseAmount(asset.into()));
+        }
+        self.amount -= asset;
+
+        if self.amount.is_zero() {
+            let balance = lease_account.balance::<Lpn>()?;
+            let receipt = self.no_reschedule_repay(payment, now)?;
+            if !receipt.close() {
+                return Err(ContractError::LoanNotPaid());
+            }
+
+            let surplus = balance - payment + receipt.change();
+            if !surplus.is_zero() {
+                lease_account.send(surplus, &self.customer);
+            }
+
+            let IntoDTOResult { lease, batch } = self.into_dto();
+            Ok(Result {
+                batch: batch.merge(lease_account.into()),
+                lease,
+                receipt,
+            })
+        } else {
+            self.repay(payment, now)
+        }
+    }
+
+    /// The amount of asset to sell to cover the principal and all due interest
+    ///
+    /// The debt is grossed up with `max_slippage` so that any sale output the DEX
+    /// accepts covers it, the same way as in [`Self::full_sale_repays`].
+    /// The result does not exceed the lease amount.
+    pub(crate) fn full_close_amount(
+        &self,
+        max_slippage: Percent,
+        now: Timestamp,
+    ) -> ContractResult<Coin<Asset>> {
+        let price_to_lpn = self.price_of_lease_currency()?;
+
+        let LiabilityStatus { total_lpn, .. } =
+            self.loan
+                .liability_status(now, self.addr.clone(), total(self.amount, price_to_lpn))?;
+
+        if Percent::HUNDRED <= max_slippage {
+            return Ok(self.amount);
+        }
+        let sale_lpn = Fraction::<Units>::of(&slippage_multiplier(max_slippage), total_lpn);
+        let sale = total(sale_lpn, price_to_lpn.inv());
+        // a debt worth less than a unit of the asset still needs a unit sold
+        let sale = if sale.is_zero() && !total_lpn.is_zero() {
+            Coin::new(1)
+        } else {
+            sale
+        };
+        Ok(self.amount.min(sale))
+    }
+
+    /// Check whether selling the whole lease amount would repay the loan in full
+    ///
+    /// The sale output may fall below the oracle price by up to `max_slippage`.
+    pub(crate) fn full_sale_repays(
+        &self,
+        max_slippage: Percent,
+        now: Timestamp,
+    ) -> ContractResult<bool> {
+        let lease_lpn = self.lease_amount_lpn()?;
+
+        let LiabilityStatus { total_lpn, .. } =
+            self.loan
+                .liability_status(now, self.addr.clone(), lease_lpn)?;
+
+        let min_proceeds = lease_lpn - max_slippage.of(lease_lpn);
+        Ok(total_lpn <= min_proceeds)
+    }
+
+    /// The amount of LPN to pay to cover the principal and all due interest
+    pub(crate) fn full_repay_amount(&self, now: Timestamp) -> ContractResult<Coin<Lpn>> {
+        let price_to_lpn = self.price_of_lease_currency()?;
+
+        let LiabilityStatus { total_lpn, .. } =
+            self.loan
+                .liability_status(now, self.addr.clone(), total(self.amount, price_to_lpn))?;
+
+        Ok(total_lpn)
+    }
+
     pub(super) fn no_reschedule_repay(
         &mut self,
         payment: Coin<Lpn>,
@@ -54,6 +164,14 @@ where
     }
 }
 
+/// The multiplier of an amount to obtain at least it from a sale with `max_slippage`
+///
+/// From 'sale - max_slippage% of sale = amount' follows
+/// 'sale = 100% / (100% - max_slippage%) of amount'
+fn slippage_multiplier(max_slippage: Percent) -> Rational<Percent> {
+    Rational::new(Percent::HUNDRED, Percent::HUNDRED - max_slippage)
+}
+
 pub(crate) struct Result<Lpn>
 where
     Lpn: Currency,
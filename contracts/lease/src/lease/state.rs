@@ -1,5 +1,5 @@
 use cosmwasm_std::Timestamp;
-use finance::{coin::Coin, currency::Currency, percent::Percent};
+use finance::{coin::Coin, currency::Currency, percent::Percent, price::Price};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum State<Asset, Lpn>
@@ -14,10 +14,26 @@ where
         principal_due: Coin<Lpn>,
         previous_margin_due: Coin<Lpn>,
         previous_interest_due: Coin<Lpn>,
+        late_payment_penalty_due: Coin<Lpn>,
         current_margin_due: Coin<Lpn>,
         current_interest_due: Coin<Lpn>,
+        ltv: Percent,
+        liquidation_prices: LiquidationPrices<Asset, Lpn>,
         validity: Timestamp,
     },
     Paid(Coin<Asset>),
     Closed(),
 }
+
+/// The prices of the lease asset at which the liability reaches the warning and liquidation levels
+#[derive(Debug, PartialEq, Eq)]
+pub struct LiquidationPrices<Asset, Lpn>
+where
+    Asset: Currency,
+    Lpn: Currency,
+{
+    pub first_warning: Price<Asset, Lpn>,
+    pub second_warning: Price<Asset, Lpn>,
+    pub third_warning: Price<Asset, Lpn>,
+    pub liquidation: Price<Asset, Lpn>,
+}
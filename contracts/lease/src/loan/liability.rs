@@ -25,7 +25,9 @@ where
     {
         self.state(now, lease.into())?
             .map(|state| {
-                let overdue_lpn = state.previous_margin_interest_due + state.previous_interest_due;
+                let overdue_lpn = state.late_payment_penalty_due
+                    + state.previous_margin_interest_due
+                    + state.previous_interest_due;
 
                 let total_lpn = state.principal_due
                     + overdue_lpn
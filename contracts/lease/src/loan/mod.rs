@@ -11,7 +11,7 @@ use finance::{
 };
 use lpp::{
     loan::LoanData,
-    msg::QueryLoanResponse,
+    msg::{QueryLoanResponse, QueryQuoteResponse},
     stub::{
         lender::{LppLender as LppLenderTrait, LppLenderRef},
         LppBatch,
@@ -40,6 +40,8 @@ pub(crate) struct LoanDTO {
     interest_payment_spec: InterestPaymentSpec,
     current_period: InterestPeriod<Units, Percent>,
     profit: ProfitRef,
+    #[serde(default)]
+    late_payment_paid_till: Timestamp,
 }
 
 impl LoanDTO {
@@ -49,6 +51,7 @@ impl LoanDTO {
         interest_payment_spec: InterestPaymentSpec,
         current_period: InterestPeriod<Units, Percent>,
         profit: ProfitRef,
+        late_payment_paid_till: Timestamp,
     ) -> Self {
         Self {
             annual_margin_interest,
@@ -56,6 +59,7 @@ impl LoanDTO {
             interest_payment_spec,
             current_period,
             profit,
+            late_payment_paid_till,
         }
     }
 
@@ -79,6 +83,8 @@ pub struct Loan<Lpn, Lpp, Profit> {
     interest_payment_spec: InterestPaymentSpec,
     current_period: InterestPeriod<Units, Percent>,
     profit: Profit,
+    /// The time till which the late payment penalty of the current overdue period is paid
+    late_payment_paid_till: Timestamp,
 }
 
 impl<Lpn, Lpp, Profit> Loan<Lpn, Lpp, Profit>
@@ -106,6 +112,7 @@ where
             interest_payment_spec,
             current_period,
             profit,
+            late_payment_paid_till: Timestamp::default(),
         }
     }
 
@@ -114,6 +121,7 @@ where
             let annual_margin_interest = dto.annual_margin_interest;
             let interest_payment_spec = dto.interest_payment_spec;
             let current_period = dto.current_period;
+            let late_payment_paid_till = dto.late_payment_paid_till;
             Self {
                 annual_margin_interest,
                 lpn: PhantomData,
@@ -121,6 +129,7 @@ where
                 interest_payment_spec,
                 current_period,
                 profit,
+                late_payment_paid_till,
             }
         }
     }
@@ -142,6 +151,7 @@ where
             self.interest_payment_spec,
             self.current_period,
             profit_ref,
+            self.late_payment_paid_till,
         );
 
         (dto, lpp_batch.merge(profit_batch))
@@ -151,6 +161,13 @@ where
         self.current_period.till() + self.interest_payment_spec.grace_period()
     }
 
+    /// Send a penalty charged on top of a repayment to the Profit contract
+    pub(crate) fn pay_penalty(&mut self, penalty: Coin<Lpn>) {
+        if !penalty.is_zero() {
+            self.profit.send(penalty);
+        }
+    }
+
     pub(crate) fn repay<A>(
         &mut self,
         payment: Coin<Lpn>,
@@ -199,6 +216,38 @@ where
         Ok(receipt)
     }
 
+    /// Request an additional amount from the LPP
+    ///
+    /// The margin interest accrues on the current principal, so it is paid by `by`
+    /// out of the borrowed amount, and the change is returned. The LPP capitalizes
+    /// the loan interest due.
+    pub(crate) fn increase_req<A>(
+        &mut self,
+        amount: Coin<Lpn>,
+        by: Timestamp,
+        lease: A,
+    ) -> ContractResult<Coin<Lpn>>
+    where
+        A: Into<Addr>,
+    {
+        self.debug_check_start_due_before(by, "before the 'increase-by' time");
+
+        if self.overdue_at(by) {
+            return Err(ContractError::LoanOverdue());
+        }
+
+        if let QueryQuoteResponse::NoLiquidity = self.lpp.quote(amount)? {
+            return Err(ContractError::NoLiquidity());
+        }
+
+        let loan = self.load_loan(lease)?.ok_or(ContractError::LoanClosed())?;
+
+        self.lpp.open_loan_req(amount)?;
+
+        self.repay_margin_interest(loan.principal_due, by, amount)
+            .map(|(_margin_paid, change)| change)
+    }
+
     pub(crate) fn state(&self, now: Timestamp, lease: Addr) -> ContractResult<Option<State<Lpn>>> {
         self.debug_check_start_due_before(now, "in the past of");
 
@@ -235,6 +284,12 @@ where
         let previous_interest_due = loan.interest_due(margin_interest_overdue_period.till());
         let current_interest_due = loan.interest_due(now) - previous_interest_due;
 
+        let late_payment_penalty_due = self
+            .late_payment_period(now)
+            .map_or_else(Coin::default, |period| {
+                period.interest(previous_margin_interest_due + previous_interest_due)
+            });
+
         Ok(Some(State {
             annual_interest: loan.annual_interest_rate,
             annual_interest_margin: self.annual_margin_interest,
@@ -243,6 +298,7 @@ where
             current_interest_due,
             previous_margin_interest_due,
             current_margin_interest_due,
+            late_payment_penalty_due,
         }))
     }
 
@@ -257,8 +313,11 @@ where
         loan: &LoanData<Lpn>,
         receipt: &mut RepayReceipt<Lpn>,
     ) -> ContractResult<(Coin<Lpn>, Coin<Lpn>)> {
+        let (penalty_paid, change) = self.repay_late_payment_penalty(loan, by, payment);
+        receipt.pay_late_payment_penalty(penalty_paid);
+
         let (prev_margin_paid, change) =
-            self.repay_margin_interest(loan.principal_due, by, payment)?;
+            self.repay_margin_interest(loan.principal_due, by, change)?;
         receipt.pay_previous_margin(prev_margin_paid);
 
         if change.is_zero() {
@@ -334,6 +393,54 @@ where
         Ok((paid, change))
     }
 
+    fn repay_late_payment_penalty(
+        &mut self,
+        loan: &LoanData<Lpn>,
+        by: Timestamp,
+        payment: Coin<Lpn>,
+    ) -> (Coin<Lpn>, Coin<Lpn>) {
+        let period = if let Some(period) = self.late_payment_period(by) {
+            period
+        } else {
+            return (Coin::default(), payment);
+        };
+
+        let overdue = self.current_period.interest(loan.principal_due)
+            + loan.interest_due(self.current_period.till());
+        let (period, change) = period.pay(overdue, payment, by);
+        self.late_payment_paid_till = period.start();
+
+        let paid = payment - change;
+
+        if !paid.is_zero() {
+            self.profit.send(paid);
+        }
+
+        (paid, change)
+    }
+
+    /// The period over which the unpaid late payment penalty accrues by `by`
+    ///
+    /// The penalty accrues on the overdue interest from the end of the due period
+    /// till the end of the grace period.
+    fn late_payment_period(&self, by: Timestamp) -> Option<InterestPeriod<Units, Percent>> {
+        if !self.overdue_at(by) {
+            return None;
+        }
+
+        self.interest_payment_spec
+            .late_payment_interest()
+            .map(|late_payment_interest| {
+                let due_end = self.current_period.till();
+                let till = by.min(due_end + self.interest_payment_spec.grace_period());
+                let start = due_end.max(self.late_payment_paid_till).min(till);
+
+                InterestPeriod::with_interest(late_payment_interest)
+                    .from(start)
+                    .spanning(Duration::between(start, till))
+            })
+    }
+
     fn open_next_period(&mut self) {
         debug_assert!(self.current_period.zero_length());
 
@@ -519,12 +626,24 @@ mod tests {
     fn create_loan(
         loan: LoanResponse<TestCurrency>,
         now: Timestamp,
+    ) -> Loan<TestCurrency, LppLenderLocalStub, ProfitLocalStub> {
+        create_loan_with_spec(
+            loan,
+            now,
+            InterestPaymentSpec::new(Duration::YEAR, Duration::from_secs(0)),
+        )
+    }
+
+    fn create_loan_with_spec(
+        loan: LoanResponse<TestCurrency>,
+        now: Timestamp,
+        interest_payment_spec: InterestPaymentSpec,
     ) -> Loan<TestCurrency, LppLenderLocalStub, ProfitLocalStub> {
         Loan::new(
             LEASE_START,
             LppLenderLocalStub::new(loan, now),
             MARGIN_INTEREST_RATE,
-            InterestPaymentSpec::new(Duration::YEAR, Duration::from_secs(0)),
+            interest_payment_spec,
             ProfitLocalStub {},
         )
     }
@@ -971,6 +1090,115 @@ mod tests {
         test_state(Duration::YEAR - Duration::from_days(1))
     }
 
+    #[test]
+    fn late_payment_penalty_none() {
+        let (loan, _) = late_payment_loan(None, Duration::from_days(5));
+
+        let state = loan
+            .state(
+                LEASE_START + Duration::YEAR + Duration::from_days(5),
+                lease(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(coin(0), state.late_payment_penalty_due);
+    }
+
+    #[test]
+    fn late_payment_penalty_within_grace() {
+        let late_payment_interest = Percent::from_percent(20);
+        let late_for = Duration::from_days(5);
+        let (loan, overdue) = late_payment_loan(Some(late_payment_interest), late_for);
+
+        let state = loan
+            .state(LEASE_START + Duration::YEAR + late_for, lease())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            late_payment_penalty(late_payment_interest, late_for, overdue),
+            state.late_payment_penalty_due
+        );
+    }
+
+    #[test]
+    fn late_payment_penalty_after_grace() {
+        let late_payment_interest = Percent::from_percent(20);
+        let (loan, overdue) =
+            late_payment_loan(Some(late_payment_interest), Duration::from_days(15));
+
+        let state = loan
+            .state(
+                LEASE_START + Duration::YEAR + Duration::from_days(15),
+                lease(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            late_payment_penalty(late_payment_interest, GRACE_PERIOD, overdue),
+            state.late_payment_penalty_due
+        );
+    }
+
+    #[test]
+    fn late_payment_penalty_repay() {
+        let late_payment_interest = Percent::from_percent(20);
+        let late_for = Duration::from_days(5);
+        let payment_at = LEASE_START + Duration::YEAR + late_for;
+        let (mut loan, overdue) = late_payment_loan(Some(late_payment_interest), late_for);
+        let penalty = late_payment_penalty(late_payment_interest, late_for, overdue);
+        let payment = penalty + coin(10);
+
+        let mut receipt = RepayReceipt::default();
+        receipt.pay_late_payment_penalty(penalty);
+        receipt.pay_previous_margin(coin(10));
+        assert_eq!(loan.repay(payment, payment_at, lease()), Ok(receipt));
+
+        let state = loan.state(payment_at, lease()).unwrap().unwrap();
+        assert_eq!(coin(0), state.late_payment_penalty_due);
+    }
+
+    const GRACE_PERIOD: Duration = Duration::from_days(10);
+
+    fn late_payment_loan(
+        late_payment_interest: Option<Percent>,
+        late_for: Duration,
+    ) -> (
+        Loan<TestCurrency, LppLenderLocalStub, ProfitLocalStub>,
+        Coin<TestCurrency>,
+    ) {
+        let principal_due = coin(100_000);
+        let interest_rate = Percent::from_percent(10);
+        let loan_resp = LoanResponse {
+            principal_due,
+            annual_interest_rate: interest_rate,
+            interest_paid: LEASE_START,
+        };
+        let spec = InterestPaymentSpec::new(Duration::YEAR, GRACE_PERIOD);
+        let spec = if let Some(late_payment_interest) = late_payment_interest {
+            spec.with_late_payment_interest(late_payment_interest)
+        } else {
+            spec
+        };
+        let now = LEASE_START + Duration::YEAR + late_for;
+        let overdue = MARGIN_INTEREST_RATE.of(principal_due) + interest_rate.of(principal_due);
+        (create_loan_with_spec(loan_resp, now, spec), overdue)
+    }
+
+    fn late_payment_penalty(
+        late_payment_interest: Percent,
+        late_for: Duration,
+        overdue: Coin<TestCurrency>,
+    ) -> Coin<TestCurrency> {
+        InterestPeriod::with_interest(late_payment_interest)
+            .from(LEASE_START + Duration::YEAR)
+            .spanning(late_for)
+            .interest(overdue)
+    }
+
+    fn lease() -> Addr {
+        Addr::unchecked("lease")
+    }
+
     fn coin(a: Amount) -> Coin<TestCurrency> {
         Coin::<TestCurrency>::new(a)
     }
@@ -5,6 +5,7 @@ pub(crate) struct Receipt<C>
 where
     C: Currency,
 {
+    late_payment_penalty_paid: Coin<C>,
     previous_margin_paid: Coin<C>,
     current_margin_paid: Coin<C>,
     previous_interest_paid: Coin<C>,
@@ -18,6 +19,10 @@ impl<C> Receipt<C>
 where
     C: Currency,
 {
+    pub fn late_payment_penalty_paid(&self) -> Coin<C> {
+        self.late_payment_penalty_paid
+    }
+
     pub fn previous_margin_paid(&self) -> Coin<C> {
         self.previous_margin_paid
     }
@@ -47,7 +52,8 @@ where
     }
 
     pub fn total(&self) -> Coin<C> {
-        self.previous_margin_paid
+        self.late_payment_penalty_paid
+            + self.previous_margin_paid
             + self.previous_interest_paid
             + self.current_margin_paid
             + self.current_interest_paid
@@ -55,6 +61,12 @@ where
             + self.change
     }
 
+    pub(super) fn pay_late_payment_penalty(&mut self, payment: Coin<C>) {
+        debug_assert_eq!(self.late_payment_penalty_paid, Coin::default());
+
+        self.late_payment_penalty_paid = payment;
+    }
+
     pub(super) fn pay_previous_margin(&mut self, payment: Coin<C>) {
         debug_assert_eq!(self.previous_margin_paid, Coin::default());
 
@@ -111,6 +123,7 @@ where
         close: bool,
     ) -> Receipt<C> {
         Self {
+            late_payment_penalty_paid: Coin::default(),
             previous_margin_paid,
             current_margin_paid,
             previous_interest_paid,
@@ -11,4 +11,5 @@ where
     pub current_interest_due: Coin<Lpn>,
     pub previous_margin_interest_due: Coin<Lpn>,
     pub current_margin_interest_due: Coin<Lpn>,
+    pub late_payment_penalty_due: Coin<Lpn>,
 }
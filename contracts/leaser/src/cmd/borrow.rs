@@ -60,6 +60,8 @@ impl Borrow {
                     },
                     time_alarms: config.time_alarms,
                     market_price_oracle: config.market_price_oracle,
+                    max_slippage: config.lease_max_slippage,
+                    swap_fee: config.lease_swap_fee,
                 },
                 dex,
             })
@@ -14,6 +14,7 @@ pub struct Quote<'r> {
     liability: Liability,
     lease_interest_rate_margin: Percent,
     max_ltv: Option<Percent>,
+    swap_fee: Percent,
 }
 
 pub struct Borrow {}
@@ -39,6 +39,7 @@ impl<'r> WithLppLender for Quote<'r> {
                 liability: self.liability,
                 lease_interest_rate_margin: self.lease_interest_rate_margin,
                 max_ltv: self.max_ltv,
+                swap_fee: self.swap_fee,
             },
             &self.querier,
         )
@@ -54,6 +55,7 @@ impl<'r> Quote<'r> {
         liability: Liability,
         lease_interest_rate_margin: Percent,
         max_ltv: Option<Percent>,
+        swap_fee: Percent,
     ) -> Self {
         Self {
             querier,
@@ -63,6 +65,7 @@ impl<'r> Quote<'r> {
             liability,
             lease_interest_rate_margin,
             max_ltv,
+            swap_fee,
         }
     }
 }
@@ -109,6 +112,7 @@ where
     liability: Liability,
     lease_interest_rate_margin: Percent,
     max_ltv: Option<Percent>,
+    swap_fee: Percent,
 }
 
 impl<Lpn, Lpp> WithOracle<Lpn> for QuoteStage2<Lpn, Lpp>
@@ -135,6 +139,7 @@ where
                 liability: self.liability,
                 lease_interest_rate_margin: self.lease_interest_rate_margin,
                 max_ltv: self.max_ltv,
+                swap_fee: self.swap_fee,
             },
         )
         .map_err(|_| ContractError::UnknownCurrency {
@@ -156,6 +161,7 @@ where
     liability: Liability,
     lease_interest_rate_margin: Percent,
     max_ltv: Option<Percent>,
+    swap_fee: Percent,
 }
 
 impl<Lpn, Lpp, Oracle> AnyVisitor for QuoteStage3<Lpn, Lpp, Oracle>
@@ -180,6 +186,7 @@ where
                 liability: self.liability,
                 lease_interest_rate_margin: self.lease_interest_rate_margin,
                 max_ltv: self.max_ltv,
+                swap_fee: self.swap_fee,
             },
         )
         .map_err({
@@ -204,6 +211,7 @@ where
     liability: Liability,
     lease_interest_rate_margin: Percent,
     max_ltv: Option<Percent>,
+    swap_fee: Percent,
 }
 
 impl<Lpn, Dpc, Lpp, Oracle> AnyVisitor for QuoteStage4<Lpn, Dpc, Lpp, Oracle>
@@ -221,6 +229,8 @@ where
         Asset: 'static + Currency + Serialize + DeserializeOwned,
     {
         let downpayment_lpn = total(self.downpayment, self.oracle.price_of()?);
+        let swap_fee = self.swap_fee.of(downpayment_lpn);
+        let downpayment_lpn = downpayment_lpn - swap_fee;
 
         if downpayment_lpn.is_zero() {
             return Err(ContractError::ZeroDownpayment {});
@@ -232,6 +242,7 @@ where
 
         let asset_price = self.oracle.price_of::<Asset>()?.inv();
 
+        // the swap fee is withheld from the loan
         let total_asset = total(downpayment_lpn + borrow, asset_price);
 
         let annual_interest_rate = self.lpp_quote.with(borrow)?;
@@ -239,6 +250,7 @@ where
         Ok(QuoteResponse {
             total: total_asset.into(),
             borrow: borrow.into(),
+            swap_fee: swap_fee.into(),
             annual_interest_rate,
             annual_interest_rate_margin: self.lease_interest_rate_margin,
         })
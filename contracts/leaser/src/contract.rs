@@ -63,21 +63,38 @@ pub fn execute(
         ExecuteMsg::SetupDex(params) => {
             owner_allowed_only(deps.storage, info, |s| leaser::try_setup_dex(s, params))
         }
+        ExecuteMsg::UpdateDexRelaying(relaying) => owner_allowed_only(deps.storage, info, |s| {
+            leaser::try_update_dex_relaying(s, relaying)
+        }),
         ExecuteMsg::Config {
             lease_interest_rate_margin,
             liability,
             lease_interest_payment,
+            lease_max_slippage,
+            lease_swap_fee,
         } => owner_allowed_only(deps.storage, info, |s| {
             leaser::try_configure(
                 s,
                 lease_interest_rate_margin,
                 liability,
                 lease_interest_payment,
+                lease_max_slippage,
+                lease_swap_fee,
             )
         }),
         ExecuteMsg::MigrateLeases { new_code_id } => owner_allowed_only(deps.storage, info, |s| {
             leaser::try_migrate_leases(s, new_code_id.u64())
         }),
+        ExecuteMsg::CancelLease { lease } => {
+            let funds = info.funds.clone();
+            owner_allowed_only(deps.storage, info, |_| {
+                leaser::try_cancel_lease(lease, funds)
+            })
+        }
+        ExecuteMsg::ChangeLeaseCustomer {
+            customer,
+            new_customer,
+        } => leaser::try_change_lease_customer(deps.storage, info.sender, customer, new_customer),
         ExecuteMsg::OpenLease { currency, max_ltv } => Borrow::with(
             deps,
             info.funds,
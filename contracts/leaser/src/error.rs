@@ -1,5 +1,6 @@
 use thiserror::Error;
 
+use finance::percent::Percent;
 use sdk::cosmwasm_std::StdError;
 
 #[derive(Error, Debug, PartialEq)]
@@ -39,6 +40,12 @@ pub enum ContractError {
 
     #[error("[Leaser] DEX connectivity already setup")]
     DEXConnectivityAlreadySetup {},
+
+    #[error("[Leaser] The lease swap fee {fee} should be less than 100%")]
+    InvalidSwapFee { fee: Percent },
+
+    #[error("[Leaser] The lease max slippage {slippage} should not exceed 100%")]
+    InvalidMaxSlippage { slippage: Percent },
 }
 
 pub type ContractResult<T> = Result<T, ContractError>;
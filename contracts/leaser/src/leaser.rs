@@ -2,13 +2,16 @@ use std::collections::HashSet;
 
 use currency::native::Nls;
 use finance::{currency::SymbolOwned, liability::Liability, percent::Percent};
-use lease::api::{dex::ConnectionParams, DownpaymentCoin, InterestPaymentSpec};
+use lease::api::{
+    dex::{ConnectionParams, RelayingParams},
+    DownpaymentCoin, InterestPaymentSpec,
+};
 use lpp::{msg::ExecuteMsg, stub::lender::LppLenderRef};
 use oracle::stub::OracleRef;
 use platform::batch::Batch;
 use sdk::{
     cosmwasm_ext::Response,
-    cosmwasm_std::{Addr, Deps, StdResult, Storage},
+    cosmwasm_std::{wasm_execute, Addr, Coin, Deps, StdResult, Storage},
 };
 
 use crate::{
@@ -57,6 +60,7 @@ impl<'a> Leaser<'a> {
                 config.liability,
                 config.lease_interest_rate_margin,
                 max_ltv,
+                config.lease_swap_fee,
             ),
             &self.deps.querier,
         )?;
@@ -74,17 +78,41 @@ pub fn try_setup_dex(
     Ok(Response::default())
 }
 
+/// Update the DEX relaying parameters and push them to the existing leases
+pub fn try_update_dex_relaying(
+    storage: &mut dyn Storage,
+    relaying: RelayingParams,
+) -> ContractResult<Response> {
+    Config::update_dex_relaying(storage, relaying.clone())?;
+
+    let update_msg = lease::api::ExecuteMsg::UpdateDexRelaying(relaying);
+    Leases::iter(storage)
+        .try_fold(Batch::default(), |mut batch, lease| {
+            lease.and_then(|lease| {
+                batch
+                    .schedule_execute_wasm_no_reply::<_, Nls>(&lease, update_msg.clone(), None)
+                    .map(|()| batch)
+                    .map_err(Into::into)
+            })
+        })
+        .map(Into::into)
+}
+
 pub fn try_configure(
     storage: &mut dyn Storage,
     lease_interest_rate_margin: Percent,
     liability: Liability,
     lease_interest_payment: InterestPaymentSpec,
+    lease_max_slippage: Percent,
+    lease_swap_fee: Percent,
 ) -> ContractResult<Response> {
     Config::update(
         storage,
         lease_interest_rate_margin,
         liability,
         lease_interest_payment,
+        lease_max_slippage,
+        lease_swap_fee,
     )?;
 
     Ok(Response::default())
@@ -100,6 +128,32 @@ pub fn try_migrate_leases(storage: &mut dyn Storage, new_code_id: u64) -> Contra
     Ok(batch.into())
 }
 
+/// Cancel the opening of a lease forwarding the funds sent along
+///
+/// The funds cover the loan interest if the downpayment is not in LPN.
+pub fn try_cancel_lease(lease: Addr, funds: Vec<Coin>) -> ContractResult<Response> {
+    let mut batch = Batch::default();
+    batch.schedule_execute_no_reply(wasm_execute(
+        lease,
+        &lease::api::ExecuteMsg::Cancel(),
+        funds,
+    )?);
+    Ok(batch.into())
+}
+
+pub fn try_change_lease_customer(
+    storage: &mut dyn Storage,
+    lease: Addr,
+    customer: Addr,
+    new_customer: Addr,
+) -> ContractResult<Response> {
+    if Leases::change_customer(storage, lease, customer, new_customer)? {
+        Ok(Response::default())
+    } else {
+        Err(access_control::Unauthorized.into())
+    }
+}
+
 pub(super) fn update_lpp(
     storage: &mut dyn Storage,
     new_code_id: u64,
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use finance::{currency::SymbolOwned, liability::Liability, percent::Percent};
-use lease::api::{dex::ConnectionParams, DownpaymentCoin, InterestPaymentSpec, LeaseCoin};
+use lease::api::{
+    dex::{ConnectionParams, RelayingParams},
+    DownpaymentCoin, InterestPaymentSpec, LeaseCoin, LpnCoin,
+};
 use lpp::msg::LppCoin;
 use sdk::{
     cosmwasm_std::{Addr, Uint64},
@@ -17,6 +20,8 @@ pub struct InstantiateMsg {
     pub lease_interest_rate_margin: Percent,
     pub liability: Liability,
     pub lease_interest_payment: InterestPaymentSpec,
+    pub lease_max_slippage: Percent,
+    pub lease_swap_fee: Percent,
     pub time_alarms: Addr,
     pub market_price_oracle: Addr,
     pub profit: Addr,
@@ -29,19 +34,44 @@ pub struct MigrateMsg {}
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     SetupDex(ConnectionParams),
+    /// Update the relaying parameters of the DEX connectivity
+    ///
+    /// They apply to the leases opened afterwards, and are pushed to the existing ones.
+    UpdateDexRelaying(RelayingParams),
     Config {
         lease_interest_rate_margin: Percent,
         liability: Liability,
         lease_interest_payment: InterestPaymentSpec,
+        #[serde(default = "lease::api::default_max_slippage")]
+        lease_max_slippage: Percent,
+        #[serde(default)]
+        lease_swap_fee: Percent,
     },
     MigrateLeases {
         new_code_id: Uint64,
     },
+    /// Cancel the opening of a lease
+    ///
+    /// The lease returns the funds to the customer and repays the loan. The funds sent
+    /// along are forwarded to the lease to cover the loan interest, if needed.
+    CancelLease {
+        lease: Addr,
+    },
+    /// Open a lease in `currency` with the downpayment sent along
+    ///
+    /// Any Nls sent along too set aside the fee reserve of the lease.
     OpenLease {
         currency: SymbolOwned,
         #[serde(default)]
         max_ltv: Option<Percent>,
     },
+    /// Move the sending lease from the leases of a customer to the leases of a new one
+    ///
+    /// Sent by a lease on a transfer of its ownership.
+    ChangeLeaseCustomer {
+        customer: Addr,
+        new_customer: Addr,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -69,6 +99,34 @@ pub struct ConfigResponse {
 pub struct QuoteResponse {
     pub total: LeaseCoin,
     pub borrow: LppCoin,
+    /// The swap fee on the downpayment, charged in LPN out of the loan
+    pub swap_fee: LpnCoin,
     pub annual_interest_rate: Percent,
     pub annual_interest_rate_margin: Percent,
 }
+
+#[cfg(test)]
+mod test {
+    use lease::api::LeaserExecuteMsg;
+    use sdk::cosmwasm_std::{from_slice, to_vec, Addr};
+
+    use super::ExecuteMsg;
+
+    #[test]
+    fn lease_msg_compatible() {
+        let customer = Addr::unchecked("customer");
+        let new_customer = Addr::unchecked("new customer");
+        let lease_msg = LeaserExecuteMsg::ChangeLeaseCustomer {
+            customer: customer.clone(),
+            new_customer: new_customer.clone(),
+        };
+
+        assert_eq!(
+            Ok(ExecuteMsg::ChangeLeaseCustomer {
+                customer,
+                new_customer
+            }),
+            from_slice(&to_vec(&lease_msg).unwrap())
+        );
+    }
+}
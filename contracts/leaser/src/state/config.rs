@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use finance::{liability::Liability, percent::Percent};
-use lease::api::{dex::ConnectionParams, InterestPaymentSpec};
+use lease::api::{
+    dex::{ConnectionParams, RelayingParams},
+    InterestPaymentSpec,
+};
 use sdk::{
     cosmwasm_std::{Addr, StdResult, Storage},
     cw_storage_plus::Item,
@@ -19,6 +22,14 @@ pub struct Config {
     pub lease_interest_rate_margin: Percent,
     pub liability: Liability,
     pub lease_interest_payment: InterestPaymentSpec,
+    /// The maximum slippage tolerated by the lease swaps
+    #[serde(default = "lease::api::default_max_slippage")]
+    pub lease_max_slippage: Percent,
+    /// The fee charged on the downpayment and on the non-LPN payments
+    ///
+    /// It is sent to the Profit contract.
+    #[serde(default)]
+    pub lease_swap_fee: Percent,
     pub time_alarms: Addr,
     pub market_price_oracle: Addr,
     pub profit: Addr,
@@ -29,12 +40,17 @@ impl Config {
     const STORAGE: Item<'static, Self> = Item::new("config");
 
     pub fn new(msg: InstantiateMsg) -> Result<Self, ContractError> {
+        Self::check_max_slippage(msg.lease_max_slippage)?;
+        Self::check_swap_fee(msg.lease_swap_fee)?;
+
         Ok(Config {
             lease_code_id: msg.lease_code_id.u64(),
             lpp_addr: msg.lpp_ust_addr,
             lease_interest_rate_margin: msg.lease_interest_rate_margin,
             liability: msg.liability,
             lease_interest_payment: msg.lease_interest_payment,
+            lease_max_slippage: msg.lease_max_slippage,
+            lease_swap_fee: msg.lease_swap_fee,
             time_alarms: msg.time_alarms,
             market_price_oracle: msg.market_price_oracle,
             profit: msg.profit,
@@ -62,16 +78,37 @@ impl Config {
         Ok(())
     }
 
+    pub fn update_dex_relaying(
+        storage: &mut dyn Storage,
+        relaying: RelayingParams,
+    ) -> ContractResult<()> {
+        Self::STORAGE.update(storage, |mut c| {
+            c.dex
+                .as_mut()
+                .map(|dex| dex.relaying = relaying)
+                .ok_or(ContractError::NoDEXConnectivitySetup {})
+                .map(|()| c)
+        })?;
+        Ok(())
+    }
+
     pub fn update(
         storage: &mut dyn Storage,
         lease_interest_rate_margin: Percent,
         liability: Liability,
         repayment: InterestPaymentSpec,
+        max_slippage: Percent,
+        swap_fee: Percent,
     ) -> Result<(), ContractError> {
+        Self::check_max_slippage(max_slippage)?;
+        Self::check_swap_fee(swap_fee)?;
+
         Self::STORAGE.update(storage, |mut c| -> ContractResult<Config> {
             c.lease_interest_rate_margin = lease_interest_rate_margin;
             c.liability = liability;
             c.lease_interest_payment = repayment;
+            c.lease_max_slippage = max_slippage;
+            c.lease_swap_fee = swap_fee;
             Ok(c)
         })?;
         Ok(())
@@ -87,4 +124,21 @@ impl Config {
         })?;
         Ok(())
     }
+
+    fn check_max_slippage(slippage: Percent) -> ContractResult<()> {
+        if slippage <= Percent::HUNDRED {
+            Ok(())
+        } else {
+            Err(ContractError::InvalidMaxSlippage { slippage })
+        }
+    }
+
+    /// The fee is charged on top of the lease amounts, so it cannot take them whole
+    fn check_swap_fee(fee: Percent) -> ContractResult<()> {
+        if fee < Percent::HUNDRED {
+            Ok(())
+        } else {
+            Err(ContractError::InvalidSwapFee { fee })
+        }
+    }
 }
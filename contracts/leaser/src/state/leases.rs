@@ -67,6 +67,38 @@ impl Leases {
         Ok(())
     }
 
+    /// Move a lease from the leases of a customer to the leases of another one
+    ///
+    /// Return `false` if the lease does not belong to the customer.
+    pub fn change_customer(
+        storage: &mut dyn Storage,
+        lease: Addr,
+        customer: Addr,
+        new_customer: Addr,
+    ) -> StdResult<bool> {
+        let mut leases = Self::get(storage, customer.clone())?;
+        if !leases.remove(&lease) {
+            return Ok(false);
+        }
+
+        if leases.is_empty() {
+            Self::STORAGE.remove(storage, customer);
+        } else {
+            Self::STORAGE.save(storage, customer, &leases)?;
+        }
+
+        Self::STORAGE.update(
+            storage,
+            new_customer,
+            |may_leases| -> StdResult<HashSet<Addr>> {
+                let mut leases = may_leases.unwrap_or_default();
+                leases.insert(lease);
+                Ok(leases)
+            },
+        )?;
+        Ok(true)
+    }
+
     pub fn get(storage: &dyn Storage, owner_addr: Addr) -> StdResult<HashSet<Addr>> {
         Ok(match Self::STORAGE.load(storage, owner_addr) {
             Ok(loans) => loans,
@@ -113,6 +145,8 @@ where
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
     use cosmwasm_std::Addr;
     use sdk::{cosmwasm_std::testing, cw_storage_plus::Item};
 
@@ -137,6 +171,44 @@ mod test {
         assert_eq!(id, 0);
     }
 
+    #[test]
+    fn change_customer() {
+        let mut deps = testing::mock_dependencies();
+        let customer = Addr::unchecked("customer");
+        let new_customer = Addr::unchecked("new customer");
+        let lease1 = Addr::unchecked("lease1");
+        let lease2 = Addr::unchecked("lease2");
+
+        [&lease1, &lease2].into_iter().for_each(|lease| {
+            let id = Leases::next(&mut deps.storage, customer.clone()).unwrap();
+            Leases::save(&mut deps.storage, id, lease.clone()).unwrap();
+        });
+
+        assert!(!Leases::change_customer(
+            &mut deps.storage,
+            lease1.clone(),
+            new_customer.clone(),
+            customer.clone()
+        )
+        .unwrap());
+
+        assert!(Leases::change_customer(
+            &mut deps.storage,
+            lease1.clone(),
+            customer.clone(),
+            new_customer.clone()
+        )
+        .unwrap());
+        assert_eq!(
+            HashSet::from([lease2]),
+            Leases::get(&deps.storage, customer).unwrap()
+        );
+        assert_eq!(
+            HashSet::from([lease1]),
+            Leases::get(&deps.storage, new_customer).unwrap()
+        );
+    }
+
     #[test]
     fn transpose_ok() {
         let items = [Addr::unchecked("1"), Addr::unchecked("2")];
@@ -2,11 +2,11 @@ use serde::{Deserialize, Serialize};
 
 use access_control::Unauthorized;
 use finance::{
-    currency::Currency, duration::Duration, liability::Liability, percent::Percent,
+    coin::Coin, currency::Currency, duration::Duration, liability::Liability, percent::Percent,
     test::currency::Usdc,
 };
 use lease::api::{
-    dex::{ConnectionParams, Ics20Channel},
+    dex::{ConnectionParams, Ics20Channel, RelayingParams},
     InterestPaymentSpec,
 };
 use sdk::{
@@ -25,7 +25,7 @@ use crate::{
     contract::{execute, instantiate, query},
     error::ContractResult,
     msg::{ConfigResponse, ExecuteMsg, QueryMsg},
-    state::config::Config,
+    state::{config::Config, leases::Leases},
     ContractError,
 };
 
@@ -39,6 +39,8 @@ type TheCurrency = Usdc;
 
 const DENOM: &str = TheCurrency::TICKER;
 const MARGIN_INTEREST_RATE: Percent = Percent::from_permille(30);
+const MAX_SLIPPAGE: Percent = Percent::from_permille(50);
+const SWAP_FEE: Percent = Percent::from_permille(2);
 
 fn leaser_instantiate_msg(lease_code_id: u64, lpp_addr: Addr) -> crate::msg::InstantiateMsg {
     crate::msg::InstantiateMsg {
@@ -58,6 +60,8 @@ fn leaser_instantiate_msg(lease_code_id: u64, lpp_addr: Addr) -> crate::msg::Ins
             Duration::from_days(90),
             Duration::from_days(10),
         ),
+        lease_max_slippage: MAX_SLIPPAGE,
+        lease_swap_fee: SWAP_FEE,
         time_alarms: Addr::unchecked(TIMEALARMS_ADDR),
         market_price_oracle: Addr::unchecked(ORACLE_ADDR),
         profit: Addr::unchecked(PROFIT_ADDR),
@@ -93,9 +97,27 @@ fn dex_params() -> ConnectionParams {
             local_endpoint: "channel-0".into(),
             remote_endpoint: "channel-2048".into(),
         },
+        relaying: RelayingParams::default(),
+        protocol: Default::default(),
     }
 }
 
+fn relaying_params() -> RelayingParams {
+    RelayingParams {
+        timeout: Duration::from_secs(120),
+        transfer_ack_tip: Coin::new(1000),
+        transfer_timeout_tip: Coin::new(1500),
+        ica_ack_tip: Coin::new(2000),
+        ica_timeout_tip: Coin::new(2500),
+        fee_reserve: Coin::new(10000),
+    }
+}
+
+fn update_dex_relaying(deps: DepsMut<'_>, info: MessageInfo) -> ContractResult<Response> {
+    let msg = ExecuteMsg::UpdateDexRelaying(relaying_params());
+    execute(deps, mock_env(), info, msg)
+}
+
 fn setup_dex_ok(deps: DepsMut<'_>) {
     let resp = setup_dex(deps, owner()).expect("dex update passed");
     assert!(resp.messages.is_empty());
@@ -144,12 +166,16 @@ fn test_update_config() {
         lease_interest_rate_margin: Percent::from_percent(5),
         liability: expected_liability,
         lease_interest_payment: expected_repaiment.clone(),
+        lease_max_slippage: Percent::from_percent(2),
+        lease_swap_fee: Percent::from_permille(3),
     };
     execute(deps.as_mut(), mock_env(), owner(), msg).unwrap();
 
     let config = query_config(deps.as_ref());
     assert_eq!(expected_liability, config.liability);
     assert_eq!(expected_repaiment, config.lease_interest_payment);
+    assert_eq!(Percent::from_percent(2), config.lease_max_slippage);
+    assert_eq!(Percent::from_permille(3), config.lease_swap_fee);
 }
 
 #[test]
@@ -176,6 +202,8 @@ fn test_update_config_invalid_liability() {
             lease_interest_rate_margin: Percent,
             liability: Liability,
             lease_interest_payment: InterestPaymentSpec,
+            lease_max_slippage: Percent,
+            lease_swap_fee: Percent,
         },
         OpenLease {
             currency: String,
@@ -198,6 +226,8 @@ fn test_update_config_invalid_liability() {
             Duration::from_secs(20),
             Duration::from_secs(10),
         ),
+        lease_max_slippage: Percent::from_percent(2),
+        lease_swap_fee: Percent::from_permille(3),
     };
 
     let msg: ExecuteMsg = from_binary(&to_binary(&mock_msg).unwrap()).unwrap();
@@ -227,12 +257,98 @@ fn test_update_config_unauthorized() {
         lease_interest_rate_margin: Percent::from_percent(5),
         liability: expected_liability,
         lease_interest_payment: expected_repaiment,
+        lease_max_slippage: Percent::from_percent(2),
+        lease_swap_fee: Percent::from_permille(3),
     };
 
     let err = execute(deps.as_mut(), mock_env(), customer(), msg).unwrap_err();
     assert_eq!(ContractError::Unauthorized(Unauthorized), err);
 }
 
+#[test]
+fn test_instantiate_invalid_swap_fee() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    let msg = crate::msg::InstantiateMsg {
+        lease_swap_fee: Percent::HUNDRED,
+        ..leaser_instantiate_msg(1, Addr::unchecked(LPP_ADDR))
+    };
+
+    let err = instantiate(deps.as_mut(), mock_env(), owner(), msg).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidSwapFee {
+            fee: Percent::HUNDRED
+        },
+        err
+    );
+}
+
+#[test]
+fn test_update_config_invalid_swap_fee() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+    let config = query_config(deps.as_ref());
+    let msg = ExecuteMsg::Config {
+        lease_interest_rate_margin: config.lease_interest_rate_margin,
+        liability: config.liability,
+        lease_interest_payment: config.lease_interest_payment,
+        lease_max_slippage: config.lease_max_slippage,
+        lease_swap_fee: Percent::from_percent(101),
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), owner(), msg).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidSwapFee {
+            fee: Percent::from_percent(101)
+        },
+        err
+    );
+    assert_eq!(SWAP_FEE, query_config(deps.as_ref()).lease_swap_fee);
+}
+
+#[test]
+fn test_instantiate_invalid_max_slippage() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    let msg = crate::msg::InstantiateMsg {
+        lease_max_slippage: Percent::from_percent(101),
+        ..leaser_instantiate_msg(1, Addr::unchecked(LPP_ADDR))
+    };
+
+    let err = instantiate(deps.as_mut(), mock_env(), owner(), msg).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMaxSlippage {
+            slippage: Percent::from_percent(101)
+        },
+        err
+    );
+}
+
+#[test]
+fn test_update_config_invalid_max_slippage() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+    let config = query_config(deps.as_ref());
+    let msg = ExecuteMsg::Config {
+        lease_interest_rate_margin: config.lease_interest_rate_margin,
+        liability: config.liability,
+        lease_interest_payment: config.lease_interest_payment,
+        lease_max_slippage: Percent::from_percent(101),
+        lease_swap_fee: config.lease_swap_fee,
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), owner(), msg).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMaxSlippage {
+            slippage: Percent::from_percent(101)
+        },
+        err
+    );
+    assert_eq!(MAX_SLIPPAGE, query_config(deps.as_ref()).lease_max_slippage);
+}
+
 #[test]
 fn test_no_dex_setup() {
     let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
@@ -276,6 +392,69 @@ fn test_setup_dex_again() {
     assert_eq!(Err(ContractError::Unauthorized(Unauthorized)), res);
 }
 
+#[test]
+fn test_update_dex_relaying() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+    setup_dex_ok(deps.as_mut());
+
+    let resp = update_dex_relaying(deps.as_mut(), owner()).unwrap();
+    assert!(resp.messages.is_empty());
+
+    let config = query_config(deps.as_ref());
+    assert_eq!(
+        Some(ConnectionParams {
+            relaying: relaying_params(),
+            ..dex_params()
+        }),
+        config.dex
+    );
+}
+
+#[test]
+fn test_update_dex_relaying_existing_leases() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+    setup_dex_ok(deps.as_mut());
+
+    let lease = Addr::unchecked("lease");
+    let reply_id = Leases::next(deps.as_mut().storage, customer().sender).unwrap();
+    Leases::save(deps.as_mut().storage, reply_id, lease.clone()).unwrap();
+
+    let resp = update_dex_relaying(deps.as_mut(), owner()).unwrap();
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: lease.into(),
+            msg: to_binary(&lease::api::ExecuteMsg::UpdateDexRelaying(relaying_params())).unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn test_update_dex_relaying_no_dex_setup() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+
+    let res = update_dex_relaying(deps.as_mut(), owner());
+    assert_eq!(Err(ContractError::NoDEXConnectivitySetup {}), res);
+}
+
+#[test]
+fn test_update_dex_relaying_unauthorized() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+    setup_dex_ok(deps.as_mut());
+
+    let res = update_dex_relaying(deps.as_mut(), customer());
+    assert_eq!(Err(ContractError::Unauthorized(Unauthorized)), res);
+}
+
 fn open_lease_with(max_ltv: Option<Percent>) {
     let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
 
@@ -206,16 +206,32 @@ where
                 None => Err(ContractError::NoLiquidity {}),
             }?;
 
-        Loan::open(
-            deps.storage,
-            lease_addr,
-            amount,
-            annual_interest_rate,
-            current_time,
-        )?;
+        if Loan::<LPN>::query(deps.storage, lease_addr.clone())?.is_some() {
+            let loan = Loan::load(deps.storage, lease_addr)?;
+            let loan_annual_interest_rate = loan.data().annual_interest_rate;
+            let interest =
+                loan.increase(deps.storage, current_time, amount, annual_interest_rate)?;
+
+            self.total.repay(
+                current_time,
+                interest,
+                Coin::default(),
+                loan_annual_interest_rate,
+            )?;
+            self.total
+                .borrow(current_time, interest, loan_annual_interest_rate)?;
+        } else {
+            Loan::open(
+                deps.storage,
+                lease_addr,
+                amount,
+                annual_interest_rate,
+                current_time,
+            )?;
+        }
 
         self.total
-            .borrow(env.block.time, amount, annual_interest_rate)?
+            .borrow(current_time, amount, annual_interest_rate)?
             .store(deps.storage)?;
 
         Ok(annual_interest_rate)
@@ -33,18 +33,31 @@ pub struct MigrateMsg {
 #[cfg_attr(feature = "testing", derive(Debug))]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    NewLeaseCode { lease_code_id: Uint64 },
-    NewBorrowRate { borrow_rate: InterestRate },
+    NewLeaseCode {
+        lease_code_id: Uint64,
+    },
+    NewBorrowRate {
+        borrow_rate: InterestRate,
+    },
 
-    OpenLoan { amount: LppCoin },
+    /// Open a loan, or increase the principal of the existing one, of the sender
+    ///
+    /// The interest due on an existing loan is capitalized.
+    OpenLoan {
+        amount: LppCoin,
+    },
     RepayLoan(),
 
     Deposit(),
     // CW20 interface, withdraw from lender deposit
-    Burn { amount: Uint128 },
+    Burn {
+        amount: Uint128,
+    },
 
     DistributeRewards(),
-    ClaimRewards { other_recipient: Option<Addr> },
+    ClaimRewards {
+        other_recipient: Option<Addr>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
@@ -3,7 +3,8 @@ use std::cmp;
 use serde::{de::DeserializeOwned, Serialize};
 
 use finance::{
-    coin::Coin, currency::Currency, duration::Duration, interest::InterestPeriod, percent::Percent,
+    coin::Coin, currency::Currency, duration::Duration, fraction::Fraction,
+    interest::InterestPeriod, percent::Percent,
 };
 use sdk::{
     cosmwasm_std::{Addr, StdResult, Storage, Timestamp},
@@ -68,6 +69,38 @@ where
         &self.data
     }
 
+    /// Increase the loan principal with an additional amount
+    ///
+    /// The interest due by `ctime` is capitalized. The new annual interest rate is
+    /// the average of the current one and `annual_interest_rate`, weighted by
+    /// the capitalized principal and `amount` respectively. Return the capitalized interest.
+    pub fn increase(
+        self,
+        storage: &mut dyn Storage,
+        ctime: Timestamp,
+        amount: Coin<LPN>,
+        annual_interest_rate: Percent,
+    ) -> Result<Coin<LPN>, ContractError> {
+        let interest = self.data.interest_due(ctime);
+        let principal_capitalized = self.data.principal_due + interest;
+        let principal_due = principal_capitalized + amount;
+
+        let data = LoanData {
+            principal_due,
+            annual_interest_rate: Percent::from_ratio(
+                self.data.annual_interest_rate.of(principal_capitalized)
+                    + annual_interest_rate.of(amount),
+                principal_due,
+            ),
+            interest_paid: ctime,
+        };
+
+        Self::STORAGE
+            .save(storage, self.addr, &data)
+            .map(|()| interest)
+            .map_err(ContractError::Std)
+    }
+
     /// change the Loan state after repay, return (principal_payment, excess_received) pair
     pub fn repay(
         self,
@@ -185,4 +218,41 @@ mod test {
             .is_none();
         assert!(is_none);
     }
+
+    #[test]
+    fn test_increase_loan() {
+        let mut deps = testing::mock_dependencies();
+
+        let addr = Addr::unchecked("leaser");
+        Loan::open(
+            deps.as_mut().storage,
+            addr.clone(),
+            Coin::<Usdc>::new(1000),
+            Percent::from_percent(20),
+            Timestamp::from_nanos(0),
+        )
+        .expect("should open loan");
+
+        let time = Timestamp::from_nanos(Duration::YEAR.nanos() / 2);
+        let loan: Loan<Usdc> =
+            Loan::load(deps.as_ref().storage, addr.clone()).expect("should load loan");
+        let interest = loan
+            .increase(
+                deps.as_mut().storage,
+                time,
+                Coin::<Usdc>::new(900),
+                Percent::from_percent(10),
+            )
+            .expect("should increase");
+        assert_eq!(interest, 100u128.into());
+
+        let loan = Loan::<Usdc>::query(deps.as_ref().storage, addr)
+            .expect("should query loan")
+            .expect("should be some loan");
+
+        assert_eq!(loan.principal_due, 2000u128.into());
+        assert_eq!(loan.annual_interest_rate, Percent::from_permille(155));
+        assert_eq!(loan.interest_paid, time);
+        assert_eq!(loan.interest_due(time), 0u128.into());
+    }
 }
@@ -13,7 +13,7 @@ use sdk::{
 use crate::{
     error::ContractError,
     msg::ExecuteMsg,
-    state::{config::Config, supported_pairs::SupportedPairs},
+    state::{alternative_pools, config::Config, supported_pairs::SupportedPairs},
 };
 
 use super::{
@@ -65,6 +65,23 @@ impl<'a> AnyVisitor for ExecWithOracleBase<'a> {
                 SupportedPairs::<OracleBase>::new(tree.into_tree())?
                     .validate_tickers()?
                     .save(self.deps.storage)?;
+                alternative_pools::clear(self.deps.storage)?;
+
+                Ok(Response::default())
+            }
+            ExecuteMsg::AlternativePools {
+                pool_id,
+                alternatives,
+            } => {
+                SingleUserAccess::check_owner_access::<ContractError>(
+                    self.deps.storage,
+                    &self.sender,
+                )?;
+
+                if !SupportedPairs::<OracleBase>::load(self.deps.storage)?.contains_pool(pool_id) {
+                    return Err(ContractError::UnknownPool(pool_id));
+                }
+                alternative_pools::save(self.deps.storage, pool_id, &alternatives)?;
 
                 Ok(Response::default())
             }
@@ -7,7 +7,7 @@ use sdk::cosmwasm_std::{to_binary, Binary, Deps, Env};
 use super::oracle::Oracle;
 use crate::{
     msg::{PricesResponse, QueryMsg, SwapTreeResponse},
-    state::{config::Config, supported_pairs::SupportedPairs},
+    state::{alternative_pools, config::Config, supported_pairs::SupportedPairs},
     ContractError,
 };
 
@@ -57,6 +57,11 @@ impl<'a> AnyVisitor for QueryWithOracleBase<'a> {
                 &SupportedPairs::<OracleBase>::load(self.deps.storage)?
                     .load_swap_path(&from, &to)?,
             )?),
+            QueryMsg::SwapRoutes { from, to } => Ok(to_binary(&alternative_pools::swap_routes(
+                self.deps.storage,
+                SupportedPairs::<OracleBase>::load(self.deps.storage)?
+                    .load_swap_path(&from, &to)?,
+            )?)?),
             QueryMsg::SwapTree {} => Ok(to_binary(&SwapTreeResponse {
                 tree: SupportedPairs::<OracleBase>::load(self.deps.storage)?
                     .query_swap_tree()
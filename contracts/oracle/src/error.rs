@@ -5,6 +5,7 @@ use thiserror::Error;
 use finance::currency::{Currency, Symbol, SymbolOwned};
 use marketprice::{alarms::errors::AlarmError, error::PriceFeedsError, feeders::PriceFeedersError};
 use sdk::cosmwasm_std::{Addr, StdError};
+use swap::PoolId;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -48,6 +49,9 @@ pub enum ContractError {
     #[error("[Oracle] Duplicated nodes in the currency tree")]
     DuplicatedNodes {},
 
+    #[error("[Oracle] The pool {0} is not in the swap tree")]
+    UnknownPool(PoolId),
+
     #[error("[Oracle] No feeder data for the specified address")]
     UnknownFeeder {},
 
@@ -6,7 +6,7 @@ use sdk::{
     cosmwasm_std::Addr,
     schemars::{self, JsonSchema},
 };
-use swap::SwapTarget;
+use swap::{PoolId, SwapTarget};
 use tree::HumanReadableTree;
 
 use crate::{
@@ -38,9 +38,20 @@ pub enum ExecuteMsg {
         prices: Vec<SpotPrice>,
     },
     UpdateConfig(PriceConfig),
+    /// Replace the swap tree
+    ///
+    /// The alternative pools are removed since they are set against the pools of the tree.
     SwapTree {
         tree: HumanReadableTree<SwapTarget>,
     },
+    /// Set the pools that swap the same currency pair as a pool of the swap tree
+    ///
+    /// The swaps get split across them. An empty list removes the alternatives.
+    /// They are removed on a swap tree replacement.
+    AlternativePools {
+        pool_id: PoolId,
+        alternatives: Vec<PoolId>,
+    },
     AddPriceAlarm {
         alarm: Alarm,
     },
@@ -87,6 +98,11 @@ pub enum QueryMsg {
         from: SymbolOwned,
         to: SymbolOwned,
     },
+    /// Returns the swap path followed by its alternative routes
+    SwapRoutes {
+        from: SymbolOwned,
+        to: SymbolOwned,
+    },
     /// Returns [`Status`] as response data.
     AlarmsStatus {},
 }
@@ -0,0 +1,132 @@
+use std::iter;
+
+use sdk::{
+    cosmwasm_std::{Order, StdResult, Storage},
+    cw_storage_plus::Map,
+};
+use swap::{PoolId, SwapPath, SwapTarget};
+
+/// Pools that swap the same currency pair as a pool of the swap tree
+const ALTERNATIVE_POOLS: Map<'static, PoolId, Vec<PoolId>> = Map::new("alternative_pools");
+
+/// Set the alternatives of a pool, an empty list removes them
+pub fn save(storage: &mut dyn Storage, pool_id: PoolId, alternatives: &[PoolId]) -> StdResult<()> {
+    if alternatives.is_empty() {
+        ALTERNATIVE_POOLS.remove(storage, pool_id);
+        Ok(())
+    } else {
+        ALTERNATIVE_POOLS.save(storage, pool_id, &alternatives.to_vec())
+    }
+}
+
+/// Remove the alternatives of all pools
+///
+/// They are set against the pools of a swap tree so a new tree invalidates them.
+pub fn clear(storage: &mut dyn Storage) -> StdResult<()> {
+    let pools = ALTERNATIVE_POOLS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    pools
+        .into_iter()
+        .for_each(|pool_id| ALTERNATIVE_POOLS.remove(storage, pool_id));
+    Ok(())
+}
+
+pub fn load(storage: &dyn Storage, pool_id: PoolId) -> StdResult<Vec<PoolId>> {
+    ALTERNATIVE_POOLS
+        .may_load(storage, pool_id)
+        .map(Option::unwrap_or_default)
+}
+
+/// Expand a swap path into the swap routes across the alternative pools
+///
+/// The swap path is the first route. The `n`-th alternative route goes through
+/// the `n`-th alternative of each pool on the path, or through the pool itself
+/// if it has fewer alternatives.
+pub fn swap_routes(storage: &dyn Storage, swap_path: SwapPath) -> StdResult<Vec<SwapPath>> {
+    let alternatives = swap_path
+        .iter()
+        .map(|step| load(storage, step.pool_id))
+        .collect::<StdResult<Vec<_>>>()?;
+    let alternative_routes = alternatives.iter().map(Vec::len).max().unwrap_or_default();
+
+    let routes = (0..alternative_routes)
+        .map(|route| {
+            swap_path
+                .iter()
+                .zip(alternatives.iter())
+                .map(|(step, pools)| SwapTarget {
+                    pool_id: pools.get(route).copied().unwrap_or(step.pool_id),
+                    target: step.target.clone(),
+                })
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    Ok(iter::once(swap_path).chain(routes).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use sdk::cosmwasm_std::testing::MockStorage;
+    use swap::{SwapPath, SwapTarget};
+
+    fn step(pool_id: u64, target: &str) -> SwapTarget {
+        SwapTarget {
+            pool_id,
+            target: target.into(),
+        }
+    }
+
+    fn path() -> SwapPath {
+        vec![step(1, "token1"), step(2, "token2")]
+    }
+
+    #[test]
+    fn no_alternatives() {
+        let storage = MockStorage::new();
+
+        assert_eq!(Ok(vec![path()]), super::swap_routes(&storage, path()));
+        assert_eq!(
+            Ok(vec![vec![]]),
+            super::swap_routes(&storage, SwapPath::default())
+        );
+    }
+
+    #[test]
+    fn with_alternatives() {
+        let mut storage = MockStorage::new();
+        super::save(&mut storage, 1, &[11, 12]).unwrap();
+        super::save(&mut storage, 2, &[21]).unwrap();
+
+        assert_eq!(
+            Ok(vec![
+                path(),
+                vec![step(11, "token1"), step(21, "token2")],
+                vec![step(12, "token1"), step(2, "token2")],
+            ]),
+            super::swap_routes(&storage, path())
+        );
+    }
+
+    #[test]
+    fn remove_alternatives() {
+        let mut storage = MockStorage::new();
+        super::save(&mut storage, 1, &[11]).unwrap();
+        super::save(&mut storage, 1, &[]).unwrap();
+
+        assert_eq!(Ok(vec![]), super::load(&storage, 1));
+        assert_eq!(Ok(vec![path()]), super::swap_routes(&storage, path()));
+    }
+
+    #[test]
+    fn clear_alternatives() {
+        let mut storage = MockStorage::new();
+        super::save(&mut storage, 1, &[11, 12]).unwrap();
+        super::save(&mut storage, 2, &[21]).unwrap();
+        super::clear(&mut storage).unwrap();
+
+        assert_eq!(Ok(vec![]), super::load(&storage, 1));
+        assert_eq!(Ok(vec![]), super::load(&storage, 2));
+        assert_eq!(Ok(vec![path()]), super::swap_routes(&storage, path()));
+    }
+}
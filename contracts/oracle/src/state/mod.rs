@@ -1,2 +1,3 @@
+pub mod alternative_pools;
 pub mod config;
 pub mod supported_pairs;
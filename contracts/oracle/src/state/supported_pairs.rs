@@ -10,7 +10,7 @@ use sdk::{
     cosmwasm_std::{StdError, StdResult, Storage},
     cw_storage_plus::Item,
 };
-use swap::SwapTarget;
+use swap::{PoolId, SwapTarget};
 use tree::{FindBy as _, NodeRef};
 
 use crate::error::{self, ContractError};
@@ -206,6 +206,13 @@ where
         })
     }
 
+    /// Check whether a pool connects a pair of currencies in the tree
+    pub fn contains_pool(&self, pool_id: PoolId) -> bool {
+        self.tree
+            .iter()
+            .any(|node| node.parent().is_some() && node.value().pool_id == pool_id)
+    }
+
     pub fn query_swap_tree(self) -> Tree {
         self.tree
     }
@@ -381,6 +388,15 @@ mod tests {
         assert_eq!(resp, expect);
     }
 
+    #[test]
+    fn test_contains_pool() {
+        let tree = SupportedPairs::<Usdc>::new(test_case().into_tree()).unwrap();
+
+        assert!(tree.contains_pool(5));
+        assert!(!tree.contains_pool(0));
+        assert!(!tree.contains_pool(7));
+    }
+
     #[test]
     fn test_load_affected() {
         let tree = SupportedPairs::<Usdc>::new(test_case().into_tree()).unwrap();
@@ -3,13 +3,14 @@ use std::{convert::TryInto, marker::PhantomData, result::Result as StdResult};
 use serde::{Deserialize, Serialize};
 
 use finance::{
+    coin,
     currency::{self, Currency, SymbolOwned},
     price::Price,
 };
 use marketprice::SpotPrice;
 use platform::batch::Batch;
 use sdk::cosmwasm_std::{wasm_execute, Addr, QuerierWrapper};
-use swap::SwapTarget;
+use swap::{SwapPath, SwapTarget};
 
 use crate::{
     alarms::Alarm,
@@ -93,6 +94,31 @@ impl OracleRef {
         }
     }
 
+    /// Query the price of a currency against the oracle base currency
+    ///
+    /// The price of the base currency itself is the identity one.
+    pub fn spot_price(
+        &self,
+        currency: SymbolOwned,
+        querier: &QuerierWrapper<'_>,
+    ) -> Result<SpotPrice> {
+        if currency == self.base_currency {
+            let base = coin::from_amount_ticker(1, &currency)?;
+            return Ok(SpotPrice::new(base.clone(), base));
+        }
+
+        let msg = QueryMsg::Price {
+            currency: currency.clone(),
+        };
+        querier
+            .query_wasm_smart(self.addr.clone(), &msg)
+            .map_err(|error| ContractError::FailedToFetchPrice {
+                from: currency,
+                to: self.base_currency.clone(),
+                error,
+            })
+    }
+
     pub fn swap_path(
         &self,
         from: SymbolOwned,
@@ -106,6 +132,19 @@ impl OracleRef {
             .map_err(ContractError::from)
     }
 
+    pub fn swap_routes(
+        &self,
+        from: SymbolOwned,
+        to: SymbolOwned,
+        querier: &QuerierWrapper<'_>,
+    ) -> Result<Vec<SwapPath>> {
+        let msg = QueryMsg::SwapRoutes { from, to };
+
+        querier
+            .query_wasm_smart(self.addr.clone(), &msg)
+            .map_err(ContractError::from)
+    }
+
     fn into_stub<'a, OracleBase>(
         self,
         querier: &'a QuerierWrapper<'a>,
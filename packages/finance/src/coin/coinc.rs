@@ -168,6 +168,11 @@ impl<G> Default for IntoDTO<G> {
         Self::new()
     }
 }
+impl<G> Clone for IntoDTO<G> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
 impl<G> WithCoin for IntoDTO<G> {
     type Output = CoinDTO<G>;
     type Error = Error;
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sdk::schemars::{self, JsonSchema};
 
 use crate::{
+    coin::Amount,
     duration::Duration,
     error::{Error, Result},
     fractionable::Percentable,
@@ -35,6 +36,19 @@ pub struct Liability {
     ///
     /// Limitation: recalc_time >= 1 hour
     recalc_time: Duration,
+    /// The minimum lease value, in LPN, left after a partial liquidation
+    ///
+    /// A lease that would remain below it is liquidated fully.
+    /// A zero value disables the check.
+    #[serde(default)]
+    min_asset_lpn: Amount,
+    /// The percentage of the liquidated amount charged on top of it as a penalty
+    ///
+    /// It goes to the Profit contract. On a full liquidation, the penalty is taken only
+    /// from what is left after the loan is repaid.
+    /// liquidation_penalty < 100%
+    #[serde(default)]
+    liquidation_penalty: Percent,
 }
 
 impl Liability {
@@ -62,11 +76,33 @@ impl Liability {
             second_liq_warn: second_liquidity_warning,
             third_liq_warn: third_liquidity_warning,
             recalc_time,
+            min_asset_lpn: 0,
+            liquidation_penalty: Percent::ZERO,
         };
         debug_assert_eq!(Ok(()), obj.invariant_held());
         obj
     }
 
+    #[cfg(any(test, feature = "testing"))]
+    pub const fn with_min_asset_lpn(self, min_asset_lpn: Amount) -> Self {
+        Self {
+            min_asset_lpn,
+            ..self
+        }
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub const fn with_liquidation_penalty(self, liquidation_penalty: Percent) -> Self {
+        Self {
+            liquidation_penalty,
+            ..self
+        }
+    }
+
+    pub const fn initial_percent(&self) -> Percent {
+        self.initial
+    }
+
     pub const fn healthy_percent(&self) -> Percent {
         self.healthy
     }
@@ -91,6 +127,14 @@ impl Liability {
         self.recalc_time
     }
 
+    pub const fn min_asset_lpn(&self) -> Amount {
+        self.min_asset_lpn
+    }
+
+    pub const fn liquidation_penalty_percent(&self) -> Percent {
+        self.liquidation_penalty
+    }
+
     pub fn init_borrow_amount<P>(&self, downpayment: P, max_ltv: Option<Percent>) -> P
     where
         P: Percentable,
@@ -138,6 +182,10 @@ impl Liability {
             self.recalc_time >= Duration::HOUR,
             "Recalculation cadence should be >= 1h",
         )?;
+        check(
+            self.liquidation_penalty < Percent::HUNDRED,
+            "Liquidation penalty % should be < 100%",
+        )?;
 
         Ok(())
     }
@@ -165,11 +213,53 @@ mod test {
             third_liq_warn: Percent::from_percent(14),
             max: Percent::from_percent(15),
             recalc_time: Duration::from_hours(10),
+            min_asset_lpn: 0,
+            liquidation_penalty: Percent::ZERO,
         };
         assert_load_ok(br#"{"initial":100,"healthy":100,"first_liq_warn":120,"second_liq_warn":130,"third_liq_warn":140,"max":150,"recalc_time": 36000000000000}"#,
         exp);
     }
 
+    #[test]
+    fn new_with_min_asset() {
+        let exp = Liability {
+            initial: Percent::from_percent(10),
+            healthy: Percent::from_percent(10),
+            first_liq_warn: Percent::from_percent(12),
+            second_liq_warn: Percent::from_percent(13),
+            third_liq_warn: Percent::from_percent(14),
+            max: Percent::from_percent(15),
+            recalc_time: Duration::from_hours(10),
+            min_asset_lpn: 15_000,
+            liquidation_penalty: Percent::ZERO,
+        };
+        assert_load_ok(br#"{"initial":100,"healthy":100,"first_liq_warn":120,"second_liq_warn":130,"third_liq_warn":140,"max":150,"recalc_time": 36000000000000,"min_asset_lpn":"15000"}"#,
+        exp);
+    }
+
+    #[test]
+    fn new_with_liquidation_penalty() {
+        let exp = Liability {
+            initial: Percent::from_percent(10),
+            healthy: Percent::from_percent(10),
+            first_liq_warn: Percent::from_percent(12),
+            second_liq_warn: Percent::from_percent(13),
+            third_liq_warn: Percent::from_percent(14),
+            max: Percent::from_percent(15),
+            recalc_time: Duration::from_hours(10),
+            min_asset_lpn: 0,
+            liquidation_penalty: Percent::from_permille(25),
+        };
+        assert_load_ok(br#"{"initial":100,"healthy":100,"first_liq_warn":120,"second_liq_warn":130,"third_liq_warn":140,"max":150,"recalc_time": 36000000000000,"liquidation_penalty":25}"#,
+        exp);
+    }
+
+    #[test]
+    fn new_invalid_liquidation_penalty() {
+        assert_load_err(br#"{"initial":10,"healthy":10,"first_liq_warn":11,"second_liq_warn":12,"third_liq_warn":13,
+                        "max":14,"recalc_time":3600000000000,"liquidation_penalty":1000}"#, "< 100%");
+    }
+
     #[test]
     fn new_edge_case() {
         let exp = Liability {
@@ -180,6 +270,8 @@ mod test {
             third_liq_warn: Percent::from_permille(13),
             max: Percent::from_permille(14),
             recalc_time: Duration::HOUR,
+            min_asset_lpn: 0,
+            liquidation_penalty: Percent::ZERO,
         };
 
         assert_load_ok(br#"{"initial":10,"healthy":10,"first_liq_warn":11,"second_liq_warn":12,"third_liq_warn":13,
@@ -292,6 +384,8 @@ mod test {
             second_liq_warn: Percent::from_permille(995),
             third_liq_warn: Percent::from_permille(998),
             recalc_time: Duration::from_secs(20000),
+            min_asset_lpn: 0,
+            liquidation_penalty: Percent::ZERO,
         }
         .init_borrow_amount(downpayment, max_p);
 
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use crate::{duration::Duration, error::Error, percent::Percent};
+use crate::{coin::Amount, duration::Duration, error::Error, percent::Percent};
 
 use super::Liability as ValidatedLiability;
 
@@ -14,6 +14,10 @@ pub(super) struct Liability {
     third_liq_warn: Percent,
     max: Percent,
     recalc_time: Duration,
+    #[serde(default)]
+    min_asset_lpn: Amount,
+    #[serde(default)]
+    liquidation_penalty: Percent,
 }
 
 impl TryFrom<Liability> for ValidatedLiability {
@@ -28,6 +32,8 @@ impl TryFrom<Liability> for ValidatedLiability {
             third_liq_warn: dto.third_liq_warn,
             max: dto.max,
             recalc_time: dto.recalc_time,
+            min_asset_lpn: dto.min_asset_lpn,
+            liquidation_penalty: dto.liquidation_penalty,
         };
         res.invariant_held()?;
         Ok(res)
@@ -66,6 +66,24 @@ where
     may_res
 }
 
+/// Run a command on each coin of the specified group
+///
+/// Fail if there is a coin out of the group, so that no funds are kept unaccounted for.
+pub fn received_all<G, V>(cw_amount: Vec<CwCoin>, cmd: V) -> Result<Vec<WithCoinResult<V>>>
+where
+    V: WithCoin + Clone,
+    G: Group,
+{
+    cw_amount
+        .into_iter()
+        .map(|coin| {
+            let denom = coin.denom.clone();
+            from_cosmwasm_any_impl::<G, _>(coin, cmd.clone())
+                .map_err(|_cmd| Error::UnsupportedFunds(denom))
+        })
+        .collect()
+}
+
 pub struct BankView<'a> {
     account: &'a Addr,
     querier: &'a QuerierWrapper<'a>,
@@ -236,9 +254,9 @@ mod test {
         },
     };
 
-    use crate::coin_legacy;
+    use crate::{coin_legacy, error::Error};
 
-    use super::may_received;
+    use super::{may_received, received_all};
     type TheCurrency = Usdc;
     type ExtraCurrency = Dai;
     const AMOUNT: Amount = 42;
@@ -305,4 +323,35 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn received_all_in_group() {
+        let coin_1 = Coin::<TheCurrency>::new(AMOUNT + AMOUNT);
+        let in_coin_1 = coin_legacy::to_cosmwasm(coin_1);
+
+        let coin_2 = Coin::<TheCurrency>::new(AMOUNT);
+        let in_coin_2 = coin_legacy::to_cosmwasm(coin_2);
+
+        assert_eq!(
+            Ok(vec![Ok(false), Ok(true)]),
+            received_all::<TestCurrencies, _>(vec![in_coin_1, in_coin_2], Expect(coin_2))
+        );
+        assert_eq!(
+            Ok(Vec::<Result<bool, _>>::new()),
+            received_all::<TestCurrencies, _>(vec![], Expect(coin_2))
+        );
+    }
+
+    #[test]
+    fn received_all_not_in_group() {
+        let in_coin_1 = coin_legacy::to_cosmwasm(Coin::<ExtraCurrency>::new(AMOUNT));
+
+        let coin_2 = Coin::<TheCurrency>::new(AMOUNT);
+        let in_coin_2 = coin_legacy::to_cosmwasm(coin_2);
+
+        assert_eq!(
+            Err(Error::UnsupportedFunds(in_coin_1.denom.clone())),
+            received_all::<TestCurrencies, _>(vec![in_coin_1, in_coin_2], Expect(coin_2))
+        );
+    }
 }
@@ -0,0 +1,111 @@
+use finance::{coin::CoinDTO, currency::Group};
+use sdk::cosmos_sdk_proto::{
+    cosmos::{bank::v1beta1::MsgSend, base::v1beta1::Coin as CosmosSdkCoin},
+    traits::TypeUrl,
+};
+
+use crate::{
+    coin_legacy, denom::dex::DexMapper, error::Result, ica::HostAccount, trx::Transaction,
+};
+
+/// Send funds from an interchain account to an address on its host network
+///
+/// All amounts go in a single bank message.
+pub struct Sender {
+    sender: HostAccount,
+    receiver: HostAccount,
+    amounts: Vec<CosmosSdkCoin>,
+}
+
+impl Sender {
+    pub fn new(sender: HostAccount, receiver: HostAccount) -> Self {
+        Self {
+            sender,
+            receiver,
+            amounts: vec![],
+        }
+    }
+
+    pub fn send<G>(&mut self, amount: &CoinDTO<G>) -> Result<()>
+    where
+        G: Group,
+    {
+        let cw_coin_at_dex = coin_legacy::to_cosmwasm_on_network::<G, DexMapper>(amount)?;
+        self.amounts.push(CosmosSdkCoin {
+            amount: cw_coin_at_dex.amount.into(),
+            denom: cw_coin_at_dex.denom,
+        });
+        Ok(())
+    }
+}
+
+impl From<Sender> for Transaction {
+    fn from(sender: Sender) -> Self {
+        let mut trx = Self::default();
+        trx.add_message(
+            MsgSend::TYPE_URL,
+            MsgSend {
+                from_address: sender.sender.into(),
+                to_address: sender.receiver.into(),
+                amount: sender.amounts,
+            },
+        );
+        trx
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use finance::{
+        coin::{Amount, Coin},
+        currency::Currency,
+        test::currency::{Nls, TestExtraCurrencies, Usdc},
+    };
+    use sdk::cosmos_sdk_proto::{
+        cosmos::{bank::v1beta1::MsgSend, base::v1beta1::Coin as CosmosSdkCoin},
+        traits::TypeUrl,
+    };
+
+    use crate::{ica::HostAccount, trx::Transaction};
+
+    use super::Sender;
+
+    #[test]
+    fn send() {
+        let sender = HostAccount::try_from(String::from("sender")).unwrap();
+        let receiver = HostAccount::try_from(String::from("receiver")).unwrap();
+        let mut funds_sender = Sender::new(sender.clone(), receiver.clone());
+
+        let coin1: Coin<Nls> = 63.into();
+        let coin2: Coin<Usdc> = 2.into();
+        funds_sender
+            .send::<TestExtraCurrencies>(&coin1.into())
+            .unwrap();
+        funds_sender
+            .send::<TestExtraCurrencies>(&coin2.into())
+            .unwrap();
+
+        assert_eq!(Transaction::from(funds_sender), {
+            let mut trx = Transaction::default();
+            trx.add_message(
+                MsgSend::TYPE_URL,
+                MsgSend {
+                    from_address: sender.into(),
+                    to_address: receiver.into(),
+                    amount: vec![into_cosmos_sdk_coin(coin1), into_cosmos_sdk_coin(coin2)],
+                },
+            );
+            trx
+        });
+    }
+
+    fn into_cosmos_sdk_coin<C>(coin: Coin<C>) -> CosmosSdkCoin
+    where
+        C: Currency,
+    {
+        CosmosSdkCoin {
+            amount: Amount::from(coin).to_string(),
+            denom: C::DEX_SYMBOL.into(),
+        }
+    }
+}
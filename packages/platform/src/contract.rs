@@ -23,6 +23,11 @@ pub fn validate_code_id(
     })
 }
 
+/// Query the administrator of a contract, if any
+pub fn query_admin(querier: &QuerierWrapper<'_>, contract_address: &Addr) -> Result<Option<Addr>> {
+    query_info(querier, contract_address).map(|info| info.admin.map(Addr::unchecked))
+}
+
 fn query_info(
     querier: &QuerierWrapper<'_>,
     contract_address: &Addr,
@@ -18,6 +18,9 @@ pub enum Error {
     #[error("[Platform] Expecting funds consisting of a single coin but found more coins")]
     UnexpectedFundsAny(),
 
+    #[error("[Platform] Expecting funds of a supported currency but found {0}")]
+    UnsupportedFunds(String),
+
     #[error("[Platform] Expecting code id {0} for the contract {1}")]
     UnexpectedCode(String, String),
 
@@ -1,5 +1,6 @@
 pub mod bank;
 pub mod bank_ibc;
+pub mod bank_remote;
 pub mod batch;
 pub mod coin_legacy;
 pub mod contract;
@@ -13,6 +13,12 @@ pub enum Error {
 
     #[error("[Swap] Expected response to {0} is not found")]
     MissingResponse(String),
+
+    #[error("[Swap] The DEX does not support {0}")]
+    Unsupported(String),
+
+    #[error("[Swap] Failed to serialize, or deserialize, a DEX message: {0}")]
+    Serialization(String),
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -11,6 +11,19 @@ pub mod trx;
 pub type PoolId = u64;
 pub type SwapGroup = PaymentGroup;
 
+/// The DEX protocol the swaps are performed with
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DexProtocol {
+    /// Osmosis GAMM pools, the swaps are routed through the pools of the swap path
+    #[default]
+    OsmosisGamm,
+    /// An Astroport router contract, the swaps are routed through the currencies of the swap path
+    ///
+    /// The pool ids of the swap path are not used.
+    Astroport { router: String },
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, JsonSchema)]
 #[schemars(with = "(PoolId, SymbolOwned)")]
 pub struct SwapTarget {
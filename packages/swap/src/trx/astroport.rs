@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+
+use finance::{
+    coin::{Amount, CoinDTO},
+    currency::Group,
+};
+use platform::{
+    ica::HostAccount,
+    trx::{self, Transaction},
+};
+use sdk::{
+    cosmos_sdk_proto::{
+        cosmos::base::{abci::v1beta1::MsgData, v1beta1::Coin as ProtoCoin},
+        cosmwasm::wasm::v1::{MsgExecuteContract, MsgExecuteContractResponse},
+    },
+    cosmwasm_std::{self, Uint128},
+};
+
+use crate::{
+    error::{Error, Result},
+    SwapPath,
+};
+
+use super::Dex;
+
+const REQUEST_MSG_TYPE: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+
+/// Swaps through an Astroport router contract
+///
+/// The router takes the swap operations as pairs of currencies and
+/// finds the pools on its own.
+pub(super) struct Router<'a> {
+    addr: &'a str,
+}
+
+impl<'a> Router<'a> {
+    pub fn new(addr: &'a str) -> Self {
+        Self { addr }
+    }
+}
+
+impl<'a> Dex for Router<'a> {
+    fn exact_amount_in<G>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in: &CoinDTO<G>,
+        min_out: Amount,
+        swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        G: Group,
+    {
+        let token_in = super::to_cwcoin(token_in)?;
+        let operations = to_operations(&token_in.denom, swap_path)?;
+        let msg = cosmwasm_std::to_vec(&ExecuteMsg::ExecuteSwapOperations {
+            operations,
+            minimum_receive: Some(min_out.into()),
+        })
+        .map_err(|err| Error::Serialization(err.to_string()))?;
+
+        trx.add_message(
+            REQUEST_MSG_TYPE,
+            MsgExecuteContract {
+                sender: sender.into(),
+                contract: self.addr.into(),
+                msg,
+                funds: vec![ProtoCoin {
+                    denom: token_in.denom,
+                    amount: token_in.amount.to_string(),
+                }],
+            },
+        );
+        Ok(())
+    }
+
+    fn exact_amount_in_resp<I>(&self, trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+    {
+        let resp = super::next_resp(trx_resps)?;
+        let data =
+            trx::decode_msg_response::<_, MsgExecuteContractResponse>(resp, REQUEST_MSG_TYPE)?.data;
+        if data.is_empty() {
+            return Err(Error::MissingResponse(
+                "the amount out of an Astroport swap".into(),
+            ));
+        }
+        cosmwasm_std::from_slice::<SwapResponseData>(&data)
+            .map(|resp| resp.return_amount.into())
+            .map_err(|err| Error::Serialization(err.to_string()))
+    }
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_in_resp(&self, amount_out: Amount) -> MsgData {
+        use sdk::cosmos_sdk_proto::traits::Message as _;
+
+        let resp = MsgExecuteContractResponse {
+            data: cosmwasm_std::to_vec(&SwapResponseData {
+                return_amount: amount_out.into(),
+            })
+            .expect("the swap response should be serializable"),
+        };
+        MsgData {
+            msg_type: REQUEST_MSG_TYPE.into(),
+            data: resp.encode_to_vec(),
+        }
+    }
+
+    fn supports_exact_amount_out(&self) -> bool {
+        false
+    }
+
+    fn exact_amount_out<GIn, GOut>(
+        &self,
+        _trx: &mut Transaction,
+        _sender: HostAccount,
+        _token_in_max: &CoinDTO<GIn>,
+        _token_out: &CoinDTO<GOut>,
+        _swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        GIn: Group,
+        GOut: Group,
+    {
+        Err(unsupported_exact_amount_out())
+    }
+
+    fn exact_amount_out_resp<I>(&self, _trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+    {
+        Err(unsupported_exact_amount_out())
+    }
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_out_resp(&self, _amount_in: Amount) -> Result<MsgData> {
+        Err(unsupported_exact_amount_out())
+    }
+}
+
+fn unsupported_exact_amount_out() -> Error {
+    Error::Unsupported("swaps of an exact amount out".into())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ExecuteMsg {
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+    },
+}
+
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[serde(rename_all = "snake_case")]
+enum SwapOperation {
+    AstroSwap {
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+}
+
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[serde(rename_all = "snake_case")]
+enum AssetInfo {
+    NativeToken { denom: String },
+}
+
+/// The data the router sets on the response to [`ExecuteMsg::ExecuteSwapOperations`]
+///
+/// The router replies to the last swap operation with the amount of the ask asset
+/// it has received. A router that sets no data is rejected instead of reading the
+/// output as zero.
+#[derive(Serialize, Deserialize)]
+struct SwapResponseData {
+    return_amount: Uint128,
+}
+
+/// Chain the swap path into swap operations each one offering the output of the previous one
+fn to_operations(denom_in: &str, swap_path: &SwapPath) -> Result<Vec<SwapOperation>> {
+    swap_path
+        .iter()
+        .scan(denom_in.to_owned(), |offer_denom, swap_target| {
+            Some(super::to_dex_symbol(&swap_target.target).map(|ask_denom| {
+                let offer_denom = std::mem::replace(offer_denom, ask_denom.into());
+                SwapOperation::AstroSwap {
+                    offer_asset_info: AssetInfo::NativeToken { denom: offer_denom },
+                    ask_asset_info: AssetInfo::NativeToken {
+                        denom: ask_denom.into(),
+                    },
+                }
+            }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use currency::{lease::Atom, lpn::Usdc};
+    use finance::currency::Currency as _;
+    use sdk::cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData;
+
+    use crate::{
+        error::Error,
+        trx::{test::INVALID_TICKER, Dex as _},
+        SwapTarget,
+    };
+
+    use super::{AssetInfo, Router, SwapOperation, REQUEST_MSG_TYPE};
+
+    /// A `MsgExecuteContractResponse` as returned by the router to a swap of 1000 out
+    const ROUTER_RESP: &[u8] = b"\x0a\x18{\"return_amount\":\"1000\"}";
+
+    fn native(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.into(),
+        }
+    }
+
+    #[test]
+    fn into_operations() {
+        let path = vec![
+            SwapTarget {
+                pool_id: 2,
+                target: Atom::TICKER.into(),
+            },
+            SwapTarget {
+                pool_id: 7,
+                target: Usdc::TICKER.into(),
+            },
+        ];
+        let expected = vec![
+            SwapOperation::AstroSwap {
+                offer_asset_info: native("uin"),
+                ask_asset_info: native(Atom::DEX_SYMBOL),
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info: native(Atom::DEX_SYMBOL),
+                ask_asset_info: native(Usdc::DEX_SYMBOL),
+            },
+        ];
+        assert_eq!(Ok(expected), super::to_operations("uin", &path));
+    }
+
+    #[test]
+    fn into_operations_err() {
+        let path = vec![SwapTarget {
+            pool_id: 2,
+            target: INVALID_TICKER.into(),
+        }];
+        assert!(matches!(
+            super::to_operations("uin", &path),
+            Err(Error::Platform(_))
+        ));
+    }
+
+    #[test]
+    fn exact_amount_in_resp() {
+        let resp = MsgData {
+            msg_type: REQUEST_MSG_TYPE.into(),
+            data: ROUTER_RESP.into(),
+        };
+        assert_eq!(
+            Ok(1000),
+            Router::new("router").exact_amount_in_resp(&mut vec![resp].into_iter())
+        );
+    }
+
+    #[test]
+    fn exact_amount_in_resp_no_data() {
+        let resp = MsgData {
+            msg_type: REQUEST_MSG_TYPE.into(),
+            data: vec![],
+        };
+        assert!(matches!(
+            Router::new("router").exact_amount_in_resp(&mut vec![resp].into_iter()),
+            Err(Error::MissingResponse(_))
+        ));
+    }
+}
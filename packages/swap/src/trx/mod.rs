@@ -0,0 +1,390 @@
+use finance::{
+    coin::{self, Amount, CoinDTO},
+    currency::{self, Group, Symbol},
+};
+use platform::{coin_legacy, denom::dex::DexMapper, ica::HostAccount, trx::Transaction};
+use sdk::{
+    cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData,
+    cosmwasm_std::{Coin as CwCoin, Uint128, Uint256},
+};
+
+use crate::{
+    error::{Error, Result},
+    DexProtocol, SwapGroup, SwapPath,
+};
+
+use self::{astroport::Router as AstroportRouter, osmosis::Gamm as OsmosisGamm};
+
+mod astroport;
+mod osmosis;
+
+/// A DEX protocol to swap an exact amount in, or an exact amount out
+pub trait Dex {
+    /// Add to the transaction a request to swap `token_in` along the swap path
+    ///
+    /// The DEX rejects the swap if the output falls below `min_out`.
+    fn exact_amount_in<G>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in: &CoinDTO<G>,
+        min_out: Amount,
+        swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        G: Group;
+
+    /// Decode the amount out of the next response that is to a swap request
+    fn exact_amount_in_resp<I>(&self, trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>;
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_in_resp(&self, amount_out: Amount) -> MsgData;
+
+    /// Add to the transaction a request per swap route to swap a part of `token_in`
+    ///
+    /// `token_in` is split evenly across the routes, and each part is bound by
+    /// a proportional part of `min_out`.
+    fn split_exact_amount_in<G>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in: &CoinDTO<G>,
+        min_out: Amount,
+        swap_routes: &[SwapPath],
+    ) -> Result<()>
+    where
+        G: Group,
+    {
+        split(token_in.amount(), swap_routes.len())
+            .zip(swap_routes)
+            .try_for_each(|(part_in, swap_path)| {
+                let part_token_in = coin::from_amount_ticker(part_in, token_in.ticker())?;
+                let part_min_out = part_of(min_out, part_in, token_in.amount());
+                self.exact_amount_in(trx, sender.clone(), &part_token_in, part_min_out, swap_path)
+            })
+    }
+
+    /// Decode the total amount out of the responses to a swap of `token_in` split across `routes`
+    fn split_exact_amount_in_resp<I>(
+        &self,
+        trx_resps: &mut I,
+        token_in: Amount,
+        routes: usize,
+    ) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+    {
+        split(token_in, routes).try_fold(Amount::default(), |total, _part_in| {
+            self.exact_amount_in_resp(trx_resps)
+                .map(|amount_out| total + amount_out)
+        })
+    }
+
+    /// Whether the DEX is able to swap an exact amount out
+    fn supports_exact_amount_out(&self) -> bool;
+
+    /// Add to the transaction a request to swap into exactly `token_out` along the swap path
+    ///
+    /// The DEX rejects the swap if it takes more than `token_in_max`.
+    fn exact_amount_out<GIn, GOut>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in_max: &CoinDTO<GIn>,
+        token_out: &CoinDTO<GOut>,
+        swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        GIn: Group,
+        GOut: Group;
+
+    /// Decode the amount in of the next response that is to a swap request
+    fn exact_amount_out_resp<I>(&self, trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>;
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_out_resp(&self, amount_in: Amount) -> Result<MsgData>;
+}
+
+impl Dex for DexProtocol {
+    fn exact_amount_in<G>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in: &CoinDTO<G>,
+        min_out: Amount,
+        swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        G: Group,
+    {
+        match self {
+            Self::OsmosisGamm => {
+                OsmosisGamm {}.exact_amount_in(trx, sender, token_in, min_out, swap_path)
+            }
+            Self::Astroport { router } => AstroportRouter::new(router)
+                .exact_amount_in(trx, sender, token_in, min_out, swap_path),
+        }
+    }
+
+    fn exact_amount_in_resp<I>(&self, trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+    {
+        match self {
+            Self::OsmosisGamm => OsmosisGamm {}.exact_amount_in_resp(trx_resps),
+            Self::Astroport { router } => {
+                AstroportRouter::new(router).exact_amount_in_resp(trx_resps)
+            }
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_in_resp(&self, amount_out: Amount) -> MsgData {
+        match self {
+            Self::OsmosisGamm => OsmosisGamm {}.build_exact_amount_in_resp(amount_out),
+            Self::Astroport { router } => {
+                AstroportRouter::new(router).build_exact_amount_in_resp(amount_out)
+            }
+        }
+    }
+
+    fn supports_exact_amount_out(&self) -> bool {
+        match self {
+            Self::OsmosisGamm => OsmosisGamm {}.supports_exact_amount_out(),
+            Self::Astroport { router } => AstroportRouter::new(router).supports_exact_amount_out(),
+        }
+    }
+
+    fn exact_amount_out<GIn, GOut>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in_max: &CoinDTO<GIn>,
+        token_out: &CoinDTO<GOut>,
+        swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        GIn: Group,
+        GOut: Group,
+    {
+        match self {
+            Self::OsmosisGamm => {
+                OsmosisGamm {}.exact_amount_out(trx, sender, token_in_max, token_out, swap_path)
+            }
+            Self::Astroport { router } => AstroportRouter::new(router).exact_amount_out(
+                trx,
+                sender,
+                token_in_max,
+                token_out,
+                swap_path,
+            ),
+        }
+    }
+
+    fn exact_amount_out_resp<I>(&self, trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+    {
+        match self {
+            Self::OsmosisGamm => OsmosisGamm {}.exact_amount_out_resp(trx_resps),
+            Self::Astroport { router } => {
+                AstroportRouter::new(router).exact_amount_out_resp(trx_resps)
+            }
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_out_resp(&self, amount_in: Amount) -> Result<MsgData> {
+        match self {
+            Self::OsmosisGamm => OsmosisGamm {}.build_exact_amount_out_resp(amount_in),
+            Self::Astroport { router } => {
+                AstroportRouter::new(router).build_exact_amount_out_resp(amount_in)
+            }
+        }
+    }
+}
+
+/// Split an amount into evenly sized parts, one per route
+///
+/// There are no more parts than units in the amount so none of them is zero,
+/// unless the amount itself is.
+fn split(amount: Amount, routes: usize) -> impl Iterator<Item = Amount> {
+    let parts = Amount::try_from(routes)
+        .unwrap_or(Amount::MAX)
+        .min(amount.max(1));
+    let (part, rest) = if parts == 0 {
+        (Amount::default(), Amount::default())
+    } else {
+        (amount / parts, amount % parts)
+    };
+    (0..parts).map(move |index| part + Amount::from(index < rest))
+}
+
+/// The part of `amount` in proportion to `part` of `total`
+fn part_of(amount: Amount, part: Amount, total: Amount) -> Amount {
+    if total == Amount::default() {
+        Amount::default()
+    } else {
+        let res = Uint128::from(amount).full_mul(part) / Uint256::from(total);
+        Uint128::try_from(res)
+            .expect("a part does not exceed the whole")
+            .into()
+    }
+}
+
+fn next_resp<I>(trx_resps: &mut I) -> Result<MsgData>
+where
+    I: Iterator<Item = MsgData>,
+{
+    trx_resps
+        .next()
+        .ok_or_else(|| Error::MissingResponse("swap of exact amount request".into()))
+}
+
+fn parse_amount(amount: String) -> Result<Amount> {
+    use std::str::FromStr;
+
+    Amount::from_str(&amount).map_err(|_| Error::InvalidAmount(amount))
+}
+
+fn to_cwcoin<G>(token: &CoinDTO<G>) -> Result<CwCoin>
+where
+    G: Group,
+{
+    coin_legacy::to_cosmwasm_on_network::<G, DexMapper>(token).map_err(Error::from)
+}
+
+fn to_dex_symbol(ticker: Symbol<'_>) -> Result<Symbol<'_>> {
+    currency::visit_any_on_ticker::<SwapGroup, _>(ticker, DexMapper {}).map_err(Error::from)
+}
+
+#[cfg(test)]
+mod test {
+    use currency::lpn::{Lpns, Usdc};
+    use finance::{
+        coin::Coin,
+        currency::{Currency as _, SymbolStatic},
+    };
+    use sdk::cosmwasm_std::Coin as CwCoin;
+
+    use crate::{error::Error, DexProtocol};
+
+    use super::Dex as _;
+
+    pub(super) const INVALID_TICKER: SymbolStatic = "NotATicker";
+
+    #[test]
+    fn to_dex_symbol() {
+        type Currency = Usdc;
+        assert_eq!(
+            Ok(Currency::DEX_SYMBOL),
+            super::to_dex_symbol(Currency::TICKER)
+        );
+    }
+
+    #[test]
+    fn to_dex_symbol_err() {
+        assert!(matches!(
+            super::to_dex_symbol(INVALID_TICKER),
+            Err(Error::Platform(_))
+        ));
+    }
+
+    #[test]
+    fn to_cwcoin() {
+        let coin: Coin<Usdc> = 3541415.into();
+        assert_eq!(
+            CwCoin::new(coin.into(), Usdc::DEX_SYMBOL),
+            super::to_cwcoin::<Lpns>(&coin.into()).unwrap()
+        );
+    }
+
+    #[test]
+    fn resp() {
+        let amount = 20;
+        [
+            DexProtocol::OsmosisGamm,
+            DexProtocol::Astroport {
+                router: "router".into(),
+            },
+        ]
+        .into_iter()
+        .for_each(|dex| {
+            let mut resp = vec![dex.build_exact_amount_in_resp(amount)].into_iter();
+            let parsed = dex.exact_amount_in_resp(&mut resp).unwrap();
+            assert_eq!(amount, parsed);
+            assert_eq!(None, resp.next());
+        });
+    }
+
+    #[test]
+    fn exact_out_resp() {
+        let amount = 20;
+        let dex = DexProtocol::OsmosisGamm;
+        assert!(dex.supports_exact_amount_out());
+
+        let mut resp = vec![dex.build_exact_amount_out_resp(amount).unwrap()].into_iter();
+        let parsed = dex.exact_amount_out_resp(&mut resp).unwrap();
+        assert_eq!(amount, parsed);
+        assert_eq!(None, resp.next());
+    }
+
+    #[test]
+    fn exact_out_unsupported() {
+        let dex = DexProtocol::Astroport {
+            router: "router".into(),
+        };
+        assert!(!dex.supports_exact_amount_out());
+        assert!(matches!(
+            dex.exact_amount_out_resp(&mut vec![].into_iter()),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(matches!(
+            dex.build_exact_amount_out_resp(20),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(vec![34, 33, 33], super::split(100, 3).collect::<Vec<_>>());
+        assert_eq!(vec![100], super::split(100, 1).collect::<Vec<_>>());
+        assert_eq!(vec![1, 1], super::split(2, 5).collect::<Vec<_>>());
+        assert_eq!(vec![0], super::split(0, 2).collect::<Vec<_>>());
+        assert_eq!(None, super::split(100, 0).next());
+    }
+
+    #[test]
+    fn part_of() {
+        assert_eq!(30, super::part_of(90, 34, 102));
+        assert_eq!(0, super::part_of(90, 0, 102));
+        assert_eq!(0, super::part_of(90, 0, 0));
+        assert_eq!(u128::MAX, super::part_of(u128::MAX, 7, 7));
+    }
+
+    #[test]
+    fn split_resp() {
+        let dex = DexProtocol::OsmosisGamm;
+        let mut resp = vec![
+            dex.build_exact_amount_in_resp(20),
+            dex.build_exact_amount_in_resp(22),
+            dex.build_exact_amount_in_resp(5),
+        ]
+        .into_iter();
+        assert_eq!(Ok(42), dex.split_exact_amount_in_resp(&mut resp, 100, 2));
+        assert_eq!(Ok(5), dex.split_exact_amount_in_resp(&mut resp, 100, 1));
+        assert_eq!(None, resp.next());
+    }
+
+    #[test]
+    fn resp_missing() {
+        assert!(matches!(
+            DexProtocol::default().exact_amount_in_resp(&mut vec![].into_iter()),
+            Err(Error::MissingResponse(_))
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+use osmosis_std::types::osmosis::gamm::v1beta1::{
+    MsgSwapExactAmountIn, MsgSwapExactAmountInResponse, MsgSwapExactAmountOut,
+    MsgSwapExactAmountOutResponse, SwapAmountInRoute, SwapAmountOutRoute,
+};
+
+use finance::{
+    coin::{Amount, CoinDTO},
+    currency::Group,
+};
+use platform::{
+    ica::HostAccount,
+    trx::{self, Transaction},
+};
+use sdk::cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData;
+
+use crate::{error::Result, SwapPath, SwapTarget};
+
+use super::Dex;
+
+const REQUEST_MSG_TYPE: &str = "/osmosis.gamm.v1beta1.MsgSwapExactAmountIn";
+const REQUEST_OUT_MSG_TYPE: &str = "/osmosis.gamm.v1beta1.MsgSwapExactAmountOut";
+
+/// Swaps through the Osmosis GAMM pools
+pub(super) struct Gamm {}
+
+impl Dex for Gamm {
+    fn exact_amount_in<G>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in: &CoinDTO<G>,
+        min_out: Amount,
+        swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        G: Group,
+    {
+        // A zero is not accepted so it is replaced with the minimum positive amount.
+        let routes = to_route(swap_path)?;
+        let token_in = Some(super::to_cwcoin(token_in)?);
+        let token_out_min_amount = min_out.max(1).to_string();
+        let msg = MsgSwapExactAmountIn {
+            sender: sender.into(),
+            routes,
+            token_in: token_in.map(Into::into),
+            token_out_min_amount,
+        };
+
+        trx.add_message(REQUEST_MSG_TYPE, msg);
+        Ok(())
+    }
+
+    fn exact_amount_in_resp<I>(&self, trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+    {
+        let resp = super::next_resp(trx_resps)?;
+        let amount =
+            trx::decode_msg_response::<_, MsgSwapExactAmountInResponse>(resp, REQUEST_MSG_TYPE)?
+                .token_out_amount;
+        super::parse_amount(amount)
+    }
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_in_resp(&self, amount_out: Amount) -> MsgData {
+        use sdk::cosmos_sdk_proto::traits::Message as _;
+
+        let resp = MsgSwapExactAmountInResponse {
+            token_out_amount: amount_out.to_string(),
+        };
+        MsgData {
+            msg_type: REQUEST_MSG_TYPE.into(),
+            data: resp.encode_to_vec(),
+        }
+    }
+
+    fn supports_exact_amount_out(&self) -> bool {
+        true
+    }
+
+    fn exact_amount_out<GIn, GOut>(
+        &self,
+        trx: &mut Transaction,
+        sender: HostAccount,
+        token_in_max: &CoinDTO<GIn>,
+        token_out: &CoinDTO<GOut>,
+        swap_path: &SwapPath,
+    ) -> Result<()>
+    where
+        GIn: Group,
+        GOut: Group,
+    {
+        let token_in_max = super::to_cwcoin(token_in_max)?;
+        let routes = to_out_route(&token_in_max.denom, swap_path)?;
+        let token_out = Some(super::to_cwcoin(token_out)?);
+        let msg = MsgSwapExactAmountOut {
+            sender: sender.into(),
+            routes,
+            token_in_max_amount: token_in_max.amount.to_string(),
+            token_out: token_out.map(Into::into),
+        };
+
+        trx.add_message(REQUEST_OUT_MSG_TYPE, msg);
+        Ok(())
+    }
+
+    fn exact_amount_out_resp<I>(&self, trx_resps: &mut I) -> Result<Amount>
+    where
+        I: Iterator<Item = MsgData>,
+    {
+        let resp = super::next_resp(trx_resps)?;
+        let amount = trx::decode_msg_response::<_, MsgSwapExactAmountOutResponse>(
+            resp,
+            REQUEST_OUT_MSG_TYPE,
+        )?
+        .token_in_amount;
+        super::parse_amount(amount)
+    }
+
+    #[cfg(feature = "testing")]
+    fn build_exact_amount_out_resp(&self, amount_in: Amount) -> Result<MsgData> {
+        use sdk::cosmos_sdk_proto::traits::Message as _;
+
+        let resp = MsgSwapExactAmountOutResponse {
+            token_in_amount: amount_in.to_string(),
+        };
+        Ok(MsgData {
+            msg_type: REQUEST_OUT_MSG_TYPE.into(),
+            data: resp.encode_to_vec(),
+        })
+    }
+}
+
+fn to_route(swap_path: &[SwapTarget]) -> Result<Vec<SwapAmountInRoute>> {
+    swap_path
+        .iter()
+        .map(|swap_target| {
+            super::to_dex_symbol(&swap_target.target).map(|dex_symbol| SwapAmountInRoute {
+                pool_id: swap_target.pool_id,
+                token_out_denom: dex_symbol.into(),
+            })
+        })
+        .collect()
+}
+
+/// Chain the swap path into routes each one taking in the output of the previous one
+fn to_out_route(denom_in: &str, swap_path: &[SwapTarget]) -> Result<Vec<SwapAmountOutRoute>> {
+    swap_path
+        .iter()
+        .scan(denom_in.to_owned(), |token_in_denom, swap_target| {
+            Some(
+                super::to_dex_symbol(&swap_target.target).map(|dex_symbol| SwapAmountOutRoute {
+                    pool_id: swap_target.pool_id,
+                    token_in_denom: std::mem::replace(token_in_denom, dex_symbol.into()),
+                }),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use osmosis_std::types::osmosis::gamm::v1beta1::{SwapAmountInRoute, SwapAmountOutRoute};
+
+    use currency::{lease::Atom, lpn::Usdc};
+    use finance::currency::Currency as _;
+
+    use crate::{error::Error, trx::test::INVALID_TICKER, SwapTarget};
+
+    #[test]
+    fn into_route() {
+        let path = vec![SwapTarget {
+            pool_id: 2,
+            target: Usdc::TICKER.into(),
+        }];
+        let expected = vec![SwapAmountInRoute {
+            pool_id: 2,
+            token_out_denom: Usdc::DEX_SYMBOL.into(),
+        }];
+        assert_eq!(Ok(expected), super::to_route(&path));
+    }
+
+    #[test]
+    fn into_route_err() {
+        let path = vec![SwapTarget {
+            pool_id: 2,
+            target: INVALID_TICKER.into(),
+        }];
+        assert!(matches!(super::to_route(&path), Err(Error::Platform(_))));
+    }
+
+    #[test]
+    fn into_out_route() {
+        let path = vec![
+            SwapTarget {
+                pool_id: 2,
+                target: Atom::TICKER.into(),
+            },
+            SwapTarget {
+                pool_id: 7,
+                target: Usdc::TICKER.into(),
+            },
+        ];
+        let expected = vec![
+            SwapAmountOutRoute {
+                pool_id: 2,
+                token_in_denom: "uin".into(),
+            },
+            SwapAmountOutRoute {
+                pool_id: 7,
+                token_in_denom: Atom::DEX_SYMBOL.into(),
+            },
+        ];
+        assert_eq!(Ok(expected), super::to_out_route("uin", &path));
+    }
+}
@@ -7,9 +7,9 @@ use finance::{
 };
 use lease::{
     api::{
-        dex::{ConnectionParams, Ics20Channel},
-        ExecuteMsg, InterestPaymentSpec, LoanForm, NewLeaseContract, NewLeaseForm, StateQuery,
-        StateResponse,
+        dex::{ConnectionParams, Ics20Channel, RelayingParams},
+        AnyQueryMsg, ExecuteMsg, InterestPaymentSpec, LoanForm, NewLeaseContract, NewLeaseForm,
+        StateQuery, StateResponse,
     },
     contract::{execute, instantiate, query, reply, sudo},
     error::ContractError,
@@ -19,12 +19,12 @@ use sdk::{
     cosmwasm_std::{to_binary, Addr, Binary, Coin as CwCoin, QueryRequest, WasmQuery},
     cw_multi_test::{AppResponse, Executor},
     neutron_sdk::{
-        bindings::msg::NeutronMsg,
+        bindings::{msg::NeutronMsg, types::ProtobufAny},
         sudo::msg::{RequestPacket, SudoMsg},
     },
     testing::CustomMessageReceiver,
 };
-use swap::trx as swap_trx;
+use swap::{trx::Dex, DexProtocol};
 
 use super::{ContractWrapper, MockApp, ADMIN, USER};
 
@@ -68,6 +68,8 @@ pub struct LeaseWrapperConfig {
     // LoanForm
     pub annual_margin_interest: Percent,
     pub interest_payment: InterestPaymentSpec,
+    pub max_slippage: Percent,
+    pub swap_fee: Percent,
     // Dex
     pub dex: ConnectionParams,
 }
@@ -89,6 +91,8 @@ impl Default for LeaseWrapperConfig {
                 Duration::from_secs(100),
                 Duration::from_secs(10),
             ),
+            max_slippage: Percent::from_percent(5),
+            swap_fee: Percent::ZERO,
 
             dex: ConnectionParams {
                 connection_id: "connection-0".into(),
@@ -96,6 +100,8 @@ impl Default for LeaseWrapperConfig {
                     local_endpoint: "channel-0".into(),
                     remote_endpoint: "channel-2048".into(),
                 },
+                relaying: RelayingParams::default(),
+                protocol: DexProtocol::OsmosisGamm,
             },
         }
     }
@@ -178,6 +184,8 @@ impl LeaseWrapper {
                 },
                 time_alarms: addresses.time_alarms,
                 market_price_oracle: addresses.oracle,
+                max_slippage: config.max_slippage,
+                swap_fee: config.swap_fee,
             },
             dex: config.dex,
         }
@@ -210,7 +218,7 @@ type LeaseContractWrapperReply = Box<
         ContractError,
         NewLeaseContract,
         ContractError,
-        StateQuery,
+        AnyQueryMsg,
         ContractError,
         SudoMsg,
         ContractError,
@@ -218,8 +226,6 @@ type LeaseContractWrapperReply = Box<
     >,
 >;
 
-// TODO split this mastodont into functions each per state to allow fine control
-// and checks over sent data and received swap results
 pub fn complete_lease_initialization<Lpn>(
     mock_app: &mut MockApp,
     neutron_message_receiver: &CustomMessageReceiver,
@@ -227,6 +233,28 @@ pub fn complete_lease_initialization<Lpn>(
     downpayment: CwCoin,
 ) where
     Lpn: Currency,
+{
+    // One for downpayment and one for LPP's funding
+    complete_lease_initialization_with_swaps::<Lpn>(
+        mock_app,
+        neutron_message_receiver,
+        lease_addr,
+        downpayment,
+        &[2857142857000, 142],
+    )
+}
+
+// TODO split this mastodont into functions each per state to allow fine control
+// and checks over sent data
+/// Complete the lease opening responding with `swap_amounts` out, one per swap message
+pub fn complete_lease_initialization_with_swaps<Lpn>(
+    mock_app: &mut MockApp,
+    neutron_message_receiver: &CustomMessageReceiver,
+    lease_addr: &Addr,
+    downpayment: CwCoin,
+    swap_amounts: &[Amount],
+) where
+    Lpn: Currency,
 {
     let NeutronMsg::RegisterInterchainAccount {
         connection_id,
@@ -306,8 +334,7 @@ pub fn complete_lease_initialization<Lpn>(
 
         assert_eq!(tx_conn_id, connection_id);
         assert_eq!(tx_ica_id, interchain_account_id);
-        // One for downpayment and one for LPP's funding
-        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs.len(), swap_amounts.len());
     }
 
     let StateResponse::Opening { .. } = mock_app.wrap().query(&QueryRequest::Wasm(WasmQuery::Smart {
@@ -317,8 +344,7 @@ pub fn complete_lease_initialization<Lpn>(
         panic!("Opening lease failed! Lease is expected to be in opening state!");
     };
 
-    // TODO pass the amounts as parameters once split this mastodon into multiple functions, see the TODO at the method signature
-    let swap_resp = swap_exact_in_resp(vec![2857142857000, 142]);
+    let swap_resp = swap_exact_in_resp(swap_amounts.iter().copied());
     send_response(mock_app, lease_addr, swap_resp);
 
     let StateResponse::Opened { .. } = mock_app.wrap().query(&QueryRequest::Wasm(WasmQuery::Smart {
@@ -329,21 +355,21 @@ pub fn complete_lease_initialization<Lpn>(
     };
 }
 
-fn swap_exact_in_resp<I>(amounts: I) -> Binary
+pub fn swap_exact_in_resp<I>(amounts: I) -> Binary
 where
     I: IntoIterator<Item = Amount>,
 {
     let msgs = amounts
         .into_iter()
-        .map(swap_trx::build_exact_amount_in_resp);
+        .map(|amount| DexProtocol::OsmosisGamm.build_exact_amount_in_resp(amount));
     trx::encode_msg_responses(msgs).into()
 }
 
-fn send_blank_response(mock_app: &mut MockApp, lease_addr: &Addr) -> AppResponse {
+pub fn send_blank_response(mock_app: &mut MockApp, lease_addr: &Addr) -> AppResponse {
     send_response(mock_app, lease_addr, Default::default())
 }
 
-fn send_response(mock_app: &mut MockApp, lease_addr: &Addr, resp: Binary) -> AppResponse {
+pub fn send_response(mock_app: &mut MockApp, lease_addr: &Addr, resp: Binary) -> AppResponse {
     mock_app
         .wasm_sudo(
             Addr::unchecked(lease_addr),
@@ -365,6 +391,19 @@ fn send_response(mock_app: &mut MockApp, lease_addr: &Addr, resp: Binary) -> App
         .unwrap()
 }
 
+/// Receive the next message expecting an interchain transaction as the only one sent
+pub fn expect_submit_tx(neutron_message_receiver: &CustomMessageReceiver) -> Vec<ProtobufAny> {
+    let NeutronMsg::SubmitTx { msgs, .. } = neutron_message_receiver.recv().expect("Expected to receive a `SubmitTx` message but no message was available!") else {
+        unreachable!("Unexpected message type!")
+    };
+
+    neutron_message_receiver
+        .try_recv()
+        .expect_err("Expected queue to be empty, but other message(s) has been sent!");
+
+    msgs
+}
+
 pub fn expect_ibc_transfer(
     neutron_message_receiver: &CustomMessageReceiver,
     ica_channel: &str,
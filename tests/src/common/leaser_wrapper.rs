@@ -24,6 +24,10 @@ impl LeaserWrapper {
 
     pub const GRACE_PERIOD: Duration = Duration::from_days(10);
 
+    pub const MAX_SLIPPAGE: Percent = Percent::from_permille(50);
+
+    pub const SWAP_FEE: Percent = Percent::ZERO;
+
     pub fn liability() -> Liability {
         Liability::new(
             Percent::from_percent(65),
@@ -56,6 +60,8 @@ impl LeaserWrapper {
                 Self::REPAYMENT_PERIOD,
                 Self::GRACE_PERIOD,
             ),
+            lease_max_slippage: Self::MAX_SLIPPAGE,
+            lease_swap_fee: Self::SWAP_FEE,
             time_alarms,
             market_price_oracle,
             profit,
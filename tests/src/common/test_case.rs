@@ -4,7 +4,7 @@ use finance::{
     currency::{Currency, Symbol},
     percent::Percent,
 };
-use lease::api::dex::{ConnectionParams, Ics20Channel};
+use lease::api::dex::{ConnectionParams, Ics20Channel, RelayingParams};
 use sdk::{
     cosmwasm_std::{Addr, Coin as CwCoin, Empty, Uint64},
     cw_multi_test::{next_block, Executor},
@@ -239,6 +239,8 @@ where
                         local_endpoint: "channel-0".into(),
                         remote_endpoint: "channel-422".into(),
                     },
+                    relaying: RelayingParams::default(),
+                    protocol: Default::default(),
                 }),
                 &[cwcoin::<Lpn, _>(3)],
             )
@@ -11,19 +11,30 @@ use finance::{
     fraction::Fraction as _,
     interest::InterestPeriod,
     percent::Percent,
-    price::{self, Price},
+    price::{self, total_of, Price},
+};
+use lease::api::{
+    opened::LiquidationPrices, ExecuteMsg, InterestPaymentSpec, LedgerEntry, LedgerRecord,
+    QueryMsg as LeaseQueryMsg, StateResponse,
 };
-use lease::api::{ExecuteMsg, StateQuery, StateResponse};
 use leaser::msg::{QueryMsg, QuoteResponse};
 use sdk::{
-    cosmwasm_std::{coin, Addr, Timestamp},
+    cosmos_sdk_proto::{
+        cosmos::bank::v1beta1::MsgSend,
+        traits::{Message as _, TypeUrl as _},
+    },
+    cosmwasm_std::{coin, Addr, Coin as CwCoin, Timestamp},
     cw_multi_test::{AppResponse, Executor},
     testing::{new_custom_msg_queue, CustomMessageReceiver},
 };
 
 use crate::common::{
     cwcoin, cwcoins,
-    lease_wrapper::complete_lease_initialization,
+    lease_wrapper::{
+        complete_lease_initialization, complete_lease_initialization_with_swaps,
+        expect_ibc_transfer, expect_submit_tx, send_blank_response, send_response,
+        swap_exact_in_resp,
+    },
     leaser_wrapper::LeaserWrapper,
     oracle_wrapper::{
         add_feeder, feed_a_price as oracle_feed_a_price, feed_price as oracle_feed_price,
@@ -43,6 +54,10 @@ type PaymentCoin = Coin<PaymentCurrency>;
 
 const DOWNPAYMENT: u128 = 1_000_000_000_000;
 
+/// The DEX account and its channel as set up by [`complete_lease_initialization`]
+const ICA_ADDR: &str = "ica0";
+const ICA_CHANNEL: &str = "channel-ica0";
+
 fn create_payment_coin(amount: u128) -> PaymentCoin {
     PaymentCoin::new(amount)
 }
@@ -152,18 +167,22 @@ where
 }
 
 fn get_lease_address(test_case: &TestCase<Lpn>) -> Addr {
-    let query_response: HashSet<Addr> = test_case
+    let query_response = customer_leases(test_case, USER);
+    assert_eq!(query_response.len(), 1);
+    query_response.iter().next().unwrap().clone()
+}
+
+fn customer_leases(test_case: &TestCase<Lpn>, owner: &str) -> HashSet<Addr> {
+    test_case
         .app
         .wrap()
         .query_wasm_smart(
             test_case.leaser_addr.clone().unwrap(),
             &QueryMsg::Leases {
-                owner: Addr::unchecked(USER),
+                owner: Addr::unchecked(owner),
             },
         )
-        .unwrap();
-    assert_eq!(query_response.len(), 1);
-    query_response.iter().next().unwrap().clone()
+        .unwrap()
 }
 
 fn repay(test_case: &mut TestCase<Lpn>, contract_addr: &Addr, payment: PaymentCoin) -> AppResponse {
@@ -184,12 +203,68 @@ fn close(test_case: &mut TestCase<Lpn>, contract_addr: &Addr) -> AppResponse {
         .execute_contract(
             Addr::unchecked(USER),
             contract_addr.clone(),
-            &ExecuteMsg::Close {},
+            &ExecuteMsg::Close {
+                dex_recipient: None,
+            },
             &[],
         )
         .unwrap()
 }
 
+fn close_position(
+    test_case: &mut TestCase<Lpn>,
+    contract_addr: &Addr,
+    amount: Option<LeaseCoin>,
+    payout_lpn: bool,
+) -> AppResponse {
+    test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(USER),
+            contract_addr.clone(),
+            &ExecuteMsg::ClosePosition {
+                amount: amount.map(Into::into),
+                payout_lpn,
+            },
+            &[],
+        )
+        .unwrap()
+}
+
+/// Respond to the swap of a position close and deliver the `payment` out of it
+fn complete_position_close(
+    test_case: &mut TestCase<Lpn>,
+    neutron_message_receiver: &CustomMessageReceiver,
+    contract_addr: &Addr,
+    payment: LpnCoin,
+) -> AppResponse {
+    assert_eq!(1, expect_submit_tx(neutron_message_receiver).len());
+    send_response(
+        &mut test_case.app,
+        contract_addr,
+        swap_exact_in_resp([payment.into()]),
+    );
+
+    complete_transfer_in(
+        test_case,
+        neutron_message_receiver,
+        contract_addr,
+        cwcoins::<Lpn, _>(payment),
+    )
+}
+
+/// Deliver `funds` from the DEX account to the lease and respond to the transfer
+fn complete_transfer_in(
+    test_case: &mut TestCase<Lpn>,
+    neutron_message_receiver: &CustomMessageReceiver,
+    contract_addr: &Addr,
+    funds: Vec<CwCoin>,
+) -> AppResponse {
+    assert_eq!(1, expect_submit_tx(neutron_message_receiver).len());
+    test_case.send_funds(contract_addr, funds);
+    send_blank_response(&mut test_case.app, contract_addr)
+}
+
 fn quote_borrow(test_case: &TestCase<Lpn>, downpayment: PaymentCoin) -> LpnCoin {
     LpnCoin::try_from(quote_query(test_case, downpayment).borrow).unwrap()
 }
@@ -213,7 +288,7 @@ fn state_query(test_case: &TestCase<Lpn>, contract_addr: &String) -> StateRespon
     test_case
         .app
         .wrap()
-        .query_wasm_smart(contract_addr, &StateQuery {})
+        .query_wasm_smart(contract_addr, &LeaseQueryMsg::State {})
         .unwrap()
 }
 
@@ -236,33 +311,44 @@ fn expected_open_state(
             .saturating_sub(last_paid.nanos()),
         now.nanos().saturating_sub(current_period_start.nanos()),
     );
+    let previous_margin_due =
+        calculate_interest(expected, quote_result.annual_interest_rate_margin, overdue);
+    let previous_interest_due =
+        calculate_interest(expected, quote_result.annual_interest_rate, overdue);
+    let current_margin_due =
+        calculate_interest(expected, quote_result.annual_interest_rate_margin, due);
+    let current_interest_due = calculate_interest(expected, quote_result.annual_interest_rate, due);
+    let liability = expected
+        + previous_margin_due
+        + previous_interest_due
+        + current_margin_due
+        + current_interest_due;
     StateResponse::Opened {
         amount: total.into(),
         loan_interest_rate: quote_result.annual_interest_rate,
         margin_interest_rate: quote_result.annual_interest_rate_margin,
         principal_due: expected.into(),
-        previous_margin_due: calculate_interest(
-            expected,
-            quote_result.annual_interest_rate_margin,
-            overdue,
-        )
-        .into(),
-        previous_interest_due: calculate_interest(
-            expected,
-            quote_result.annual_interest_rate,
-            overdue,
-        )
-        .into(),
-        current_margin_due: calculate_interest(
-            expected,
-            quote_result.annual_interest_rate_margin,
-            due,
-        )
-        .into(),
-        current_interest_due: calculate_interest(expected, quote_result.annual_interest_rate, due)
-            .into(),
+        previous_margin_due: previous_margin_due.into(),
+        previous_interest_due: previous_interest_due.into(),
+        late_payment_penalty_due: LpnCoin::new(0).into(),
+        current_margin_due: current_margin_due.into(),
+        current_interest_due: current_interest_due.into(),
+        ltv: Percent::from_ratio(liability, total_lpn),
+        liquidation_prices: expected_liquidation_prices(total, liability),
         validity: block_time(test_case),
         in_progress: None,
+        last_error: None,
+    }
+}
+
+fn expected_liquidation_prices(amount: LeaseCoin, liability: LpnCoin) -> LiquidationPrices {
+    let liability_params = LeaserWrapper::liability();
+    let price_at = |percent: Percent| total_of(percent.of(amount)).is(liability).into();
+    LiquidationPrices {
+        first_warning: price_at(liability_params.first_liq_warn_percent()),
+        second_warning: price_at(liability_params.second_liq_warn_percent()),
+        third_warning: price_at(liability_params.third_liq_warn_percent()),
+        liquidation: price_at(liability_params.max_percent()),
     }
 }
 
@@ -302,6 +388,127 @@ fn state_opened_when_no_payments() {
     assert_eq!(query_result, expected_result);
 }
 
+#[test]
+fn ledger_opened() {
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let quote_result = quote_query(&test_case, downpayment);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+
+    let ledger: Vec<LedgerEntry> = test_case
+        .app
+        .wrap()
+        .query_wasm_smart(
+            lease_address,
+            &LeaseQueryMsg::Ledger {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(1, ledger.len());
+    assert_eq!(0, ledger[0].id);
+    assert_eq!(block_time(&test_case), ledger[0].at);
+    assert!(matches!(
+        &ledger[0].record,
+        LedgerRecord::Open { amount, loan, .. }
+            if amount == &quote_result.total && loan == &quote_result.borrow
+    ));
+}
+
+#[test]
+fn open_with_swap_fee() {
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(ADMIN),
+            test_case.leaser_addr.clone().unwrap(),
+            &leaser::msg::ExecuteMsg::Config {
+                lease_interest_rate_margin: LeaserWrapper::INTEREST_RATE_MARGIN,
+                liability: LeaserWrapper::liability(),
+                lease_interest_payment: InterestPaymentSpec::new(
+                    LeaserWrapper::REPAYMENT_PERIOD,
+                    LeaserWrapper::GRACE_PERIOD,
+                ),
+                lease_max_slippage: LeaserWrapper::MAX_SLIPPAGE,
+                lease_swap_fee: Percent::from_percent(1),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let quote_result = quote_query(&test_case, downpayment);
+    let swap_fee = LpnCoin::try_from(quote_result.swap_fee.clone()).unwrap();
+    assert!(!swap_fee.is_zero());
+
+    let profit = test_case.profit_addr.clone().unwrap();
+    let profit_balance = balance::<Lpn>(&test_case, &profit);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+
+    assert_eq!(
+        profit_balance + swap_fee,
+        balance::<Lpn>(&test_case, &profit)
+    );
+
+    let ledger: Vec<LedgerEntry> = test_case
+        .app
+        .wrap()
+        .query_wasm_smart(
+            lease_address,
+            &LeaseQueryMsg::Ledger {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(matches!(
+        &ledger[0].record,
+        LedgerRecord::Open { swap_fee: fee, loan, .. }
+            if fee == &quote_result.swap_fee && loan == &quote_result.borrow
+    ));
+}
+
+#[test]
+fn open_across_alternative_pools() {
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(ADMIN),
+            test_case.oracle.clone().unwrap(),
+            &oracle::msg::ExecuteMsg::AlternativePools {
+                pool_id: 3,
+                alternatives: vec![33],
+            },
+            &[],
+        )
+        .unwrap();
+
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    try_init_lease(&mut test_case, downpayment, None);
+    let lease_address = get_lease_address(&test_case);
+
+    // both the downpayment and the loan are swapped through pool 3 and its alternative
+    complete_lease_initialization_with_swaps::<Lpn>(
+        &mut test_case.app,
+        &neutron_message_receiver,
+        &lease_address,
+        cwcoin(downpayment),
+        &[1428571428500, 1428571428500, 71, 71],
+    );
+
+    let StateResponse::Opened { amount, .. } = state_query(&test_case, &lease_address.into_string()) else {
+        panic!("The lease is expected to be open!");
+    };
+    assert_eq!(
+        LeaseCoin::new(DOWNPAYMENT + 1_857_142_857_142),
+        LeaseCoin::try_from(amount).unwrap()
+    );
+}
+
 #[test]
 #[ignore = "not yet implemented: proceed with TransferOut - Swap - TransferIn before landing to the same Lease::repay call"]
 fn state_opened_when_partially_paid() {
@@ -401,6 +608,7 @@ fn state_paid() {
     let expected_result = StateResponse::Paid {
         amount: expected_amount.into(),
         in_progress: None,
+        last_error: None,
     };
     let query_result = state_query(&test_case, &lease_address.into_string());
 
@@ -427,6 +635,7 @@ fn state_paid_with_max_ltv() {
     let expected_result = StateResponse::Paid {
         amount: expected_amount.into(),
         in_progress: None,
+        last_error: None,
     };
     let query_result = state_query(&test_case, &lease_address.into_string());
 
@@ -465,7 +674,8 @@ fn state_paid_when_overpaid() {
         query_result,
         StateResponse::Paid {
             amount: (downpayment + borrowed).into(),
-            in_progress: None
+            in_progress: None,
+            last_error: None,
         }
     );
 }
@@ -695,17 +905,26 @@ fn compare_state_with_manual_calculation() {
     );
 
     let query_result = state_query(&test_case, &lease_address.into_string());
+    let amount = Coin::<LeaseCurrency>::new(DOWNPAYMENT + 1_857_142_857_142);
+    let liability = LpnCoin::new(1_857_142_857_142 + 2 * 13_737_769_080 + 2 * 32_054_794_520);
     let expected_result = StateResponse::Opened {
-        amount: Coin::<LeaseCurrency>::new(DOWNPAYMENT + 1_857_142_857_142).into(),
+        amount: amount.into(),
         loan_interest_rate: quote_result.annual_interest_rate,
         margin_interest_rate: quote_result.annual_interest_rate_margin,
         principal_due: Coin::<Lpn>::new(1_857_142_857_142).into(),
         previous_margin_due: LpnCoin::new(13_737_769_080).into(),
         previous_interest_due: LpnCoin::new(32_054_794_520).into(),
+        late_payment_penalty_due: LpnCoin::new(0).into(),
         current_margin_due: LpnCoin::new(13_737_769_080).into(),
         current_interest_due: LpnCoin::new(32_054_794_520).into(),
+        ltv: Percent::from_ratio(
+            liability,
+            price::total(amount, price_lpn_of::<LeaseCurrency>()),
+        ),
+        liquidation_prices: expected_liquidation_prices(amount, liability),
         validity: block_time(&test_case),
         in_progress: None,
+        last_error: None,
     };
 
     assert_eq!(dbg!(query_result), expected_result);
@@ -824,6 +1043,435 @@ fn state_closed() {
     assert_eq!(query_result, expected_result);
 }
 
+#[test]
+fn close_position_partially() {
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+
+    let close = LeaseCoin::new(1_000_000_000_000);
+    let payment = LpnCoin::new(990_000_000_000);
+    close_position(&mut test_case, &lease_address, Some(close), false);
+    let response = complete_position_close(
+        &mut test_case,
+        &neutron_message_receiver,
+        &lease_address,
+        payment,
+    );
+
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-close-position"));
+
+    let StateResponse::Opened { amount, principal_due, .. } = state_query(&test_case, &lease_address.into_string()) else {
+        panic!("The lease is expected to stay open!");
+    };
+    assert_eq!(
+        LeaseCoin::new(DOWNPAYMENT + 1_857_142_857_142) - close,
+        LeaseCoin::try_from(amount).unwrap()
+    );
+    assert_eq!(
+        LpnCoin::new(1_857_142_857_142) - payment,
+        LpnCoin::try_from(principal_due).unwrap()
+    );
+}
+
+#[test]
+fn close_position_fully_paying_out() {
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+
+    let principal = LpnCoin::new(1_857_142_857_142);
+    let payment = LpnCoin::new(2_800_000_000_000);
+    let lease_lpn = balance::<Lpn>(&test_case, &lease_address);
+    close_position(&mut test_case, &lease_address, None, true);
+    let response = complete_position_close(
+        &mut test_case,
+        &neutron_message_receiver,
+        &lease_address,
+        payment,
+    );
+
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-close"));
+    assert_eq!(
+        StateResponse::Closed(),
+        state_query(&test_case, &lease_address.to_string())
+    );
+    assert_eq!(
+        lease_lpn + payment - principal,
+        balance::<Lpn>(&test_case, &Addr::unchecked(USER))
+    );
+    assert!(balance::<Lpn>(&test_case, &lease_address).is_zero());
+
+    let loan: Option<lpp::msg::LoanResponse<Lpn>> = test_case
+        .app
+        .wrap()
+        .query_wasm_smart(
+            test_case.lpp_addr.clone().unwrap(),
+            &lpp::msg::QueryMsg::Loan {
+                lease_addr: lease_address,
+            },
+        )
+        .unwrap();
+    assert_eq!(None, loan);
+}
+
+#[test]
+fn add_collateral() {
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+
+    let collateral = create_payment_coin(100_000_000_000);
+    let collateral_asset = LeaseCoin::new(99_000_000_000);
+    test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(USER),
+            lease_address.clone(),
+            &ExecuteMsg::AddCollateral(),
+            &cwcoins::<PaymentCurrency, _>(collateral),
+        )
+        .unwrap();
+
+    assert_eq!(
+        cwcoin(collateral),
+        expect_ibc_transfer(
+            &neutron_message_receiver,
+            ICA_CHANNEL,
+            lease_address.as_str(),
+            ICA_ADDR,
+            true,
+        )
+    );
+    send_blank_response(&mut test_case.app, &lease_address);
+
+    assert_eq!(1, expect_submit_tx(&neutron_message_receiver).len());
+    let response = send_response(
+        &mut test_case.app,
+        &lease_address,
+        swap_exact_in_resp([collateral_asset.into()]),
+    );
+
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-add-collateral"));
+
+    let StateResponse::Opened { amount, .. } = state_query(&test_case, &lease_address.into_string()) else {
+        panic!("The lease is expected to stay open!");
+    };
+    assert_eq!(
+        LeaseCoin::new(DOWNPAYMENT + 1_857_142_857_142) + collateral_asset,
+        LeaseCoin::try_from(amount).unwrap()
+    );
+}
+
+#[test]
+fn borrow_more() {
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let lease_address = open_lease(
+        &mut test_case,
+        &neutron_message_receiver,
+        downpayment,
+        Some(Percent::from_percent(20)),
+    );
+    let StateResponse::Opened { principal_due, .. } = state_query(&test_case, &lease_address.to_string()) else {
+        panic!("The lease is expected to be open!");
+    };
+    let principal = LpnCoin::try_from(principal_due).unwrap();
+
+    let borrow = LpnCoin::new(500_000_000_000);
+    let borrow_asset = LeaseCoin::new(495_000_000_000);
+    let response = test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(USER),
+            lease_address.clone(),
+            &ExecuteMsg::BorrowMore {
+                amount: borrow.into(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-borrow-more"));
+
+    assert_eq!(
+        cwcoin(borrow),
+        expect_ibc_transfer(
+            &neutron_message_receiver,
+            ICA_CHANNEL,
+            lease_address.as_str(),
+            ICA_ADDR,
+            true,
+        )
+    );
+    send_blank_response(&mut test_case.app, &lease_address);
+
+    assert_eq!(1, expect_submit_tx(&neutron_message_receiver).len());
+    send_response(
+        &mut test_case.app,
+        &lease_address,
+        swap_exact_in_resp([borrow_asset.into()]),
+    );
+
+    let StateResponse::Opened { amount, principal_due, .. } = state_query(&test_case, &lease_address.into_string()) else {
+        panic!("The lease is expected to stay open!");
+    };
+    assert_eq!(
+        LeaseCoin::new(DOWNPAYMENT + 1_857_142_857_142) + borrow_asset,
+        LeaseCoin::try_from(amount).unwrap()
+    );
+    assert_eq!(
+        principal + borrow,
+        LpnCoin::try_from(principal_due).unwrap()
+    );
+}
+
+#[test]
+fn cancel_opening() {
+    let (mut test_case, _neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let user = Addr::unchecked(USER);
+    let user_balance = balance::<PaymentCurrency>(&test_case, &user);
+    try_init_lease(&mut test_case, downpayment, None);
+    let lease_address = get_lease_address(&test_case);
+    assert_eq!(
+        user_balance - downpayment,
+        balance::<PaymentCurrency>(&test_case, &user)
+    );
+
+    let response = test_case
+        .app
+        .execute_contract(
+            user.clone(),
+            lease_address.clone(),
+            &ExecuteMsg::Cancel(),
+            &[],
+        )
+        .unwrap();
+
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-open-cancel"));
+    assert_eq!(
+        StateResponse::Closed(),
+        state_query(&test_case, &lease_address.to_string())
+    );
+    assert_eq!(user_balance, balance::<PaymentCurrency>(&test_case, &user));
+
+    let loan: Option<lpp::msg::LoanResponse<Lpn>> = test_case
+        .app
+        .wrap()
+        .query_wasm_smart(
+            test_case.lpp_addr.clone().unwrap(),
+            &lpp::msg::QueryMsg::Loan {
+                lease_addr: lease_address,
+            },
+        )
+        .unwrap();
+    assert_eq!(None, loan);
+}
+
+#[test]
+fn close_paid_to_dex_recipient() {
+    const DEX_RECIPIENT: &str = "osmo1recipient";
+
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+
+    let principal = LpnCoin::new(1_857_142_857_142);
+    test_case.send_funds(&Addr::unchecked(USER), cwcoins::<Lpn, _>(principal));
+    test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(USER),
+            lease_address.clone(),
+            &ExecuteMsg::Repay(),
+            &cwcoins::<Lpn, _>(principal),
+        )
+        .unwrap();
+    let lease_amount = LeaseCoin::new(DOWNPAYMENT + 1_857_142_857_142);
+    assert_eq!(
+        StateResponse::Paid {
+            amount: lease_amount.into(),
+            in_progress: None,
+            last_error: None,
+        },
+        state_query(&test_case, &lease_address.to_string())
+    );
+
+    test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(USER),
+            lease_address.clone(),
+            &ExecuteMsg::Close {
+                dex_recipient: Some(DEX_RECIPIENT.into()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let msgs = expect_submit_tx(&neutron_message_receiver);
+    assert_eq!(1, msgs.len());
+    assert_eq!(MsgSend::TYPE_URL, msgs[0].type_url);
+    let send = MsgSend::decode(msgs[0].value.as_slice()).unwrap();
+    assert_eq!(ICA_ADDR, send.from_address);
+    assert_eq!(DEX_RECIPIENT, send.to_address);
+    assert_eq!(1, send.amount.len());
+    assert_eq!(
+        Amount::from(lease_amount).to_string(),
+        send.amount[0].amount
+    );
+
+    let response = send_blank_response(&mut test_case.app, &lease_address);
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-close"));
+    assert_eq!(
+        StateResponse::Closed(),
+        state_query(&test_case, &lease_address.into_string())
+    );
+}
+
+#[test]
+fn operators() {
+    const OPERATOR: &str = "operator";
+    const STRANGER: &str = "stranger";
+
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+    let grant = ExecuteMsg::GrantOperator {
+        operator: Addr::unchecked(OPERATOR),
+    };
+
+    assert_unauthorized(
+        test_case
+            .app
+            .execute_contract(
+                Addr::unchecked(STRANGER),
+                lease_address.clone(),
+                &grant,
+                &[],
+            )
+            .unwrap_err(),
+    );
+    let response = test_case
+        .app
+        .execute_contract(Addr::unchecked(USER), lease_address.clone(), &grant, &[])
+        .unwrap();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-operator-grant"));
+
+    let response = change_close_policy(&mut test_case, &lease_address, OPERATOR).unwrap();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-close-policy"));
+    assert_unauthorized(change_close_policy(&mut test_case, &lease_address, STRANGER).unwrap_err());
+
+    test_case
+        .app
+        .execute_contract(
+            Addr::unchecked(USER),
+            lease_address.clone(),
+            &ExecuteMsg::RevokeOperator {
+                operator: Addr::unchecked(OPERATOR),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_unauthorized(change_close_policy(&mut test_case, &lease_address, OPERATOR).unwrap_err());
+    change_close_policy(&mut test_case, &lease_address, USER).unwrap();
+}
+
+#[test]
+fn transfer_ownership() {
+    const NEW_OWNER: &str = "user1";
+
+    let (mut test_case, neutron_message_receiver) = create_test_case();
+    let downpayment = create_payment_coin(DOWNPAYMENT);
+    let lease_address = open_lease(&mut test_case, &neutron_message_receiver, downpayment, None);
+    let transfer = ExecuteMsg::TransferOwnership {
+        new_customer: Addr::unchecked(NEW_OWNER),
+    };
+
+    let response = test_case
+        .app
+        .execute_contract(Addr::unchecked(USER), lease_address.clone(), &transfer, &[])
+        .unwrap();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-transfer-ownership"));
+
+    assert!(customer_leases(&test_case, USER).is_empty());
+    assert_eq!(
+        HashSet::from([lease_address.clone()]),
+        customer_leases(&test_case, NEW_OWNER)
+    );
+
+    assert_unauthorized(
+        test_case
+            .app
+            .execute_contract(Addr::unchecked(USER), lease_address.clone(), &transfer, &[])
+            .unwrap_err(),
+    );
+    assert_unauthorized(change_close_policy(&mut test_case, &lease_address, USER).unwrap_err());
+    change_close_policy(&mut test_case, &lease_address, NEW_OWNER).unwrap();
+}
+
+fn change_close_policy(
+    test_case: &mut TestCase<Lpn>,
+    contract_addr: &Addr,
+    sender: &str,
+) -> anyhow::Result<AppResponse> {
+    test_case.app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr.clone(),
+        &ExecuteMsg::ChangeClosePolicy {
+            stop_loss: None,
+            take_profit: None,
+        },
+        &[],
+    )
+}
+
+fn assert_unauthorized(err: anyhow::Error) {
+    assert!(err.root_cause().to_string().contains("Unauthorized"));
+}
+
+fn balance<C>(test_case: &TestCase<Lpn>, addr: &Addr) -> Coin<C>
+where
+    C: Currency,
+{
+    test_case
+        .app
+        .wrap()
+        .query_balance(addr, C::BANK_SYMBOL)
+        .unwrap()
+        .amount
+        .u128()
+        .into()
+}
+
 fn block_time(test_case: &TestCase<Lpn>) -> Timestamp {
     test_case.app.block_info().time
 }
@@ -454,6 +454,65 @@ fn test_swap_path() {
     assert_eq!(resp, expect);
 }
 
+#[test]
+fn test_swap_routes() {
+    let mut test_case = create_test_case();
+    let admin = Addr::unchecked(ADMIN);
+    let oracle = test_case.oracle.clone().unwrap();
+    test_case
+        .app
+        .execute_contract(
+            admin.clone(),
+            oracle.clone(),
+            &oracle::msg::ExecuteMsg::SwapTree { tree: swap_tree() },
+            &[],
+        )
+        .unwrap();
+
+    let alternatives = |pool_id| oracle::msg::ExecuteMsg::AlternativePools {
+        pool_id,
+        alternatives: vec![33],
+    };
+    test_case
+        .app
+        .execute_contract(Addr::unchecked(USER), oracle.clone(), &alternatives(3), &[])
+        .unwrap_err();
+    test_case
+        .app
+        .execute_contract(admin.clone(), oracle.clone(), &alternatives(4), &[])
+        .unwrap_err();
+    test_case
+        .app
+        .execute_contract(admin, oracle.clone(), &alternatives(3), &[])
+        .unwrap();
+
+    let resp: Vec<swap::SwapPath> = test_case
+        .app
+        .wrap()
+        .query_wasm_smart(
+            oracle,
+            &OracleQ::SwapRoutes {
+                from: Wbtc::TICKER.into(),
+                to: Weth::TICKER.into(),
+            },
+        )
+        .unwrap();
+
+    let route = |pool_id| {
+        vec![
+            SwapTarget {
+                pool_id,
+                target: BaseC::TICKER.into(),
+            },
+            SwapTarget {
+                pool_id: 2,
+                target: Weth::TICKER.into(),
+            },
+        ]
+    };
+    assert_eq!(resp, vec![route(3), route(33)]);
+}
+
 #[test]
 fn test_query_swap_tree() {
     let mut test_case = create_test_case();